@@ -227,7 +227,7 @@ mod tests {
 
     use crate::{
         test_cases::{get_test_cases, TestCase},
-        AsyncCache, CacheHitMetadata, CacheOpts, CacheSource, RemoteCacheOpts,
+        AsyncCache, CacheCompression, CacheHitMetadata, CacheOpts, CacheSource, RemoteCacheOpts,
     };
 
     #[tokio::test]
@@ -263,6 +263,7 @@ mod tests {
                 unused_team_id: Some("my-team".to_string()),
                 signature: false,
             }),
+            compression: CacheCompression::default(),
         };
 
         let api_client = APIClient::new(
@@ -345,6 +346,7 @@ mod tests {
                 unused_team_id: Some("my-team".to_string()),
                 signature: false,
             }),
+            compression: CacheCompression::default(),
         };
 
         // Initialize client with invalid API url to ensure that we don't hit the
@@ -437,6 +439,7 @@ mod tests {
                 unused_team_id: Some("my-team".to_string()),
                 signature: false,
             }),
+            compression: CacheCompression::default(),
         };
 
         let api_client = APIClient::new(
@@ -0,0 +1,118 @@
+use std::{
+    fmt,
+    io::{self, Cursor, ErrorKind, Read, Write},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// The codec used to compress cache archives when they are written. Archives
+/// are always read with the codec detected from their header, so changing
+/// this doesn't invalidate existing artifacts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheCompression {
+    #[default]
+    Zstd,
+    Gzip,
+    None,
+}
+
+impl fmt::Display for CacheCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CacheCompression::Zstd => "zstd",
+            CacheCompression::Gzip => "gzip",
+            CacheCompression::None => "none",
+        })
+    }
+}
+
+impl CacheCompression {
+    /// Detects the codec of an archive from its first bytes. Anything without
+    /// a known magic number is treated as an uncompressed tarball.
+    pub fn detect(header: &[u8]) -> Self {
+        if header.starts_with(&ZSTD_MAGIC) {
+            CacheCompression::Zstd
+        } else if header.starts_with(&GZIP_MAGIC) {
+            CacheCompression::Gzip
+        } else {
+            CacheCompression::None
+        }
+    }
+
+    pub(crate) fn encoder<'a>(self, writer: impl Write + 'a) -> io::Result<Box<dyn Write + 'a>> {
+        Ok(match self {
+            CacheCompression::Zstd => Box::new(zstd::Encoder::new(writer, 0)?.auto_finish()),
+            CacheCompression::Gzip => {
+                Box::new(GzEncoder::new(writer, flate2::Compression::default()))
+            }
+            CacheCompression::None => Box::new(writer),
+        })
+    }
+
+    // Reads enough of the archive to detect its codec and wraps it in the
+    // matching decoder
+    pub(crate) fn decoder<'a>(mut reader: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        let mut header = [0; ZSTD_MAGIC.len()];
+        let mut header_len = 0;
+        while header_len < header.len() {
+            match reader.read(&mut header[header_len..]) {
+                Ok(0) => break,
+                Ok(read) => header_len += read,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let compression = Self::detect(&header[..header_len]);
+        // Put back the bytes we consumed for detection
+        let reader = Cursor::new(header).take(header_len as u64).chain(reader);
+        Ok(match compression {
+            CacheCompression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+            CacheCompression::Gzip => Box::new(GzDecoder::new(reader)),
+            CacheCompression::None => Box::new(reader),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(CacheCompression::Zstd)]
+    #[test_case(CacheCompression::Gzip)]
+    #[test_case(CacheCompression::None)]
+    fn test_detects_written_codec(compression: CacheCompression) {
+        let contents = b"some archive contents".repeat(100);
+        let mut buffer = Vec::new();
+        {
+            let mut encoder = compression.encoder(&mut buffer).unwrap();
+            encoder.write_all(&contents).unwrap();
+        }
+
+        assert_eq!(CacheCompression::detect(&buffer), compression);
+
+        let mut decoded = Vec::new();
+        CacheCompression::decoder(buffer.as_slice())
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, contents);
+    }
+
+    #[test]
+    fn test_short_input_is_uncompressed() {
+        let mut decoded = Vec::new();
+        CacheCompression::decoder(&b"ab"[..])
+            .unwrap()
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, b"ab");
+    }
+}
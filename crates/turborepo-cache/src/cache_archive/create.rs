@@ -9,7 +9,7 @@ use std::{
 use tar::{EntryType, Header};
 use turbopath::{AbsoluteSystemPath, AnchoredSystemPath, IntoUnix};
 
-use crate::CacheError;
+use crate::{cache_archive::CacheCompression, CacheError};
 
 pub struct CacheWriter<'a> {
     builder: tar::Builder<Box<dyn Write + 'a>>,
@@ -39,23 +39,33 @@ impl<'a> CacheWriter<'a> {
         Ok(self.builder.finish()?)
     }
 
-    pub fn from_writer(writer: impl Write + 'a, use_compression: bool) -> Result<Self, CacheError> {
-        if use_compression {
-            let zw = zstd::Encoder::new(writer, 0)?.auto_finish();
-            Ok(CacheWriter {
-                builder: tar::Builder::new(Box::new(zw)),
-            })
+    pub fn from_writer(
+        writer: impl Write + 'a,
+        compression: CacheCompression,
+    ) -> Result<Self, CacheError> {
+        Ok(CacheWriter {
+            builder: tar::Builder::new(compression.encoder(writer)?),
+        })
+    }
+
+    // Makes a new CacheArchive at the specified path, compressing it with zstd
+    // if the path has a `.zst` extension
+    pub fn create(path: &AbsoluteSystemPath) -> Result<Self, CacheError> {
+        let compression = if path.extension() == Some("zst") {
+            CacheCompression::Zstd
         } else {
-            Ok(CacheWriter {
-                builder: tar::Builder::new(Box::new(writer)),
-            })
-        }
+            CacheCompression::None
+        };
+        Self::create_with_compression(path, compression)
     }
 
     // Makes a new CacheArchive at the specified path
     // Wires up the chain of writers:
-    // tar::Builder -> zstd::Encoder (optional) -> BufWriter -> File
-    pub fn create(path: &AbsoluteSystemPath) -> Result<Self, CacheError> {
+    // tar::Builder -> compression encoder (optional) -> BufWriter -> File
+    pub fn create_with_compression(
+        path: &AbsoluteSystemPath,
+        compression: CacheCompression,
+    ) -> Result<Self, CacheError> {
         let mut options = OpenOptions::new();
         options.write(true).create(true).truncate(true);
 
@@ -64,19 +74,7 @@ impl<'a> CacheWriter<'a> {
         // Flush to disk in 1mb chunks.
         let file_buffer = BufWriter::with_capacity(2usize.pow(20), file);
 
-        let is_compressed = path.extension() == Some("zst");
-
-        if is_compressed {
-            let zw = zstd::Encoder::new(file_buffer, 0)?.auto_finish();
-
-            Ok(CacheWriter {
-                builder: tar::Builder::new(Box::new(zw)),
-            })
-        } else {
-            Ok(CacheWriter {
-                builder: tar::Builder::new(Box::new(file_buffer)),
-            })
-        }
+        Self::from_writer(file_buffer, compression)
     }
 
     // Adds a user-cached item to the tar
@@ -451,4 +449,48 @@ mod tests {
 
         Ok(())
     }
+
+    #[test_case(CacheCompression::Zstd)]
+    #[test_case(CacheCompression::Gzip)]
+    #[test_case(CacheCompression::None)]
+    fn test_round_trip_compression(compression: CacheCompression) -> Result<()> {
+        let input_dir = tempdir()?;
+        let input_dir_path = AbsoluteSystemPath::new(input_dir.path().to_str().unwrap())?;
+        let files = [
+            ("dist/index.js", b"console.log('hello world');".to_vec()),
+            ("dist/data.bin", (0..=255u8).cycle().take(100_000).collect()),
+        ];
+        input_dir_path.join_component("dist").create_dir_all()?;
+        for (path, contents) in &files {
+            input_dir_path
+                .resolve(&AnchoredSystemPathBuf::from_raw(path)?)
+                .create_with_contents(contents)?;
+        }
+
+        // The filesystem cache always uses the `.tar.zst` name, the codec is
+        // detected from the contents on read
+        let archive_dir = tempdir()?;
+        let archive_path = AbsoluteSystemPathBuf::try_from(archive_dir.path().join("out.tar.zst"))?;
+        let mut archive = CacheWriter::create_with_compression(&archive_path, compression)?;
+        archive.add_file(input_dir_path, &AnchoredSystemPathBuf::from_raw("dist")?)?;
+        for (path, _) in &files {
+            archive.add_file(input_dir_path, &AnchoredSystemPathBuf::from_raw(path)?)?;
+        }
+        archive.finish()?;
+
+        let archive_bytes = fs::read(&archive_path)?;
+        assert_eq!(CacheCompression::detect(&archive_bytes), compression);
+
+        let restore_dir = tempdir()?;
+        let restore_dir_path = AbsoluteSystemPath::new(restore_dir.path().to_str().unwrap())?;
+        let restored = CacheReader::open(&archive_path)?.restore(restore_dir_path)?;
+        assert_eq!(restored.len(), files.len() + 1);
+        for (path, contents) in &files {
+            let restored_contents =
+                fs::read(restore_dir_path.resolve(&AnchoredSystemPathBuf::from_raw(path)?))?;
+            assert_eq!(&restored_contents, contents);
+        }
+
+        Ok(())
+    }
 }
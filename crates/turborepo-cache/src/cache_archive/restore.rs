@@ -1,4 +1,8 @@
-use std::{backtrace::Backtrace, collections::HashMap, io::Read};
+use std::{
+    backtrace::Backtrace,
+    collections::HashMap,
+    io::{self, Read},
+};
 
 use petgraph::graph::DiGraph;
 use sha2::{Digest, Sha512};
@@ -7,6 +11,7 @@ use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPathBuf
 
 use crate::{
     cache_archive::{
+        compression::CacheCompression,
         restore_directory::{restore_directory, CachedDirTree},
         restore_regular::restore_regular,
         restore_symlink::{
@@ -20,28 +25,41 @@ pub struct CacheReader<'a> {
     reader: Box<dyn Read + 'a>,
 }
 
-impl<'a> CacheReader<'a> {
-    pub fn from_reader(reader: impl Read + 'a, is_compressed: bool) -> Result<Self, CacheError> {
-        let reader: Box<dyn Read> = if is_compressed {
-            Box::new(zstd::Decoder::new(reader)?)
-        } else {
-            Box::new(reader)
-        };
+/// Keeps track of how reading the archive itself went, so that a corrupt
+/// archive can be told apart from a failure to write the restored files.
+struct ArchiveReader<R> {
+    inner: R,
+    bytes_read: usize,
+    failed: bool,
+}
 
-        Ok(CacheReader { reader })
+impl<R: Read> Read for ArchiveReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.inner.read(buf) {
+            Ok(read) => {
+                self.bytes_read += read;
+                Ok(read)
+            }
+            Err(err) => {
+                self.failed = true;
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<'a> CacheReader<'a> {
+    // The codec is detected from the archive header so artifacts written with
+    // any `CacheCompression` can be read back
+    pub fn from_reader(reader: impl Read + 'a) -> Result<Self, CacheError> {
+        Ok(CacheReader {
+            reader: CacheCompression::decoder(reader)?,
+        })
     }
 
     pub fn open(path: &AbsoluteSystemPathBuf) -> Result<Self, CacheError> {
         let file = path.open()?;
-        let is_compressed = path.extension() == Some("zst");
-
-        let reader: Box<dyn Read> = if is_compressed {
-            Box::new(zstd::Decoder::new(file)?)
-        } else {
-            Box::new(file)
-        };
-
-        Ok(CacheReader { reader })
+        Self::from_reader(file)
     }
 
     pub fn get_sha(mut self) -> Result<Vec<u8>, CacheError> {
@@ -80,10 +98,31 @@ impl<'a> CacheReader<'a> {
         // not apply for your path, it will clobber and re-start from the common
         // shared prefix.
         let dir_cache = CachedDirTree::new(anchor.to_owned());
-        let mut tr = tar::Archive::new(&mut self.reader);
-
-        Self::restore_entries(&mut tr, &mut restored, dir_cache, anchor)?;
-        Ok(restored)
+        let mut reader = ArchiveReader {
+            inner: &mut self.reader,
+            bytes_read: 0,
+            failed: false,
+        };
+        let result = Self::restore_entries(
+            &mut tar::Archive::new(&mut reader),
+            &mut restored,
+            dir_cache,
+            anchor,
+        );
+
+        match result {
+            // Decompression errors come from the underlying reader
+            Err(err) if reader.failed => {
+                Err(CacheError::Corrupt(err.to_string(), Backtrace::capture()))
+            }
+            Err(err) => Err(err),
+            // Even an archive without any files has an end-of-archive marker
+            Ok(()) if reader.bytes_read == 0 => Err(CacheError::Corrupt(
+                "archive is empty".to_string(),
+                Backtrace::capture(),
+            )),
+            Ok(()) => Ok(restored),
+        }
     }
 
     fn restore_entries<T: Read>(
@@ -96,8 +135,10 @@ impl<'a> CacheReader<'a> {
         // Save them and topologically sort them.
         let mut symlinks = Vec::new();
 
-        for entry in tr.entries()? {
-            let mut entry = entry?;
+        // Errors reading the entry headers mean the archive itself is bad
+        let corrupt = |err: io::Error| CacheError::Corrupt(err.to_string(), Backtrace::capture());
+        for entry in tr.entries().map_err(corrupt)? {
+            let mut entry = entry.map_err(corrupt)?;
             match restore_entry(&mut dir_cache, anchor, &mut entry) {
                 Err(CacheError::LinkTargetDoesNotExist(_, _)) => {
                     symlinks.push(entry);
@@ -190,7 +231,13 @@ mod tests {
     use tracing::debug;
     use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPathBuf};
 
-    use crate::cache_archive::{restore::CacheReader, restore_symlink::canonicalize_linkname};
+    use crate::{
+        cache_archive::{
+            restore::CacheReader, restore_symlink::canonicalize_linkname, CacheCompression,
+            CacheWriter,
+        },
+        CacheError,
+    };
 
     // Expected output of the cache
     #[derive(Debug)]
@@ -335,10 +382,8 @@ mod tests {
     fn test_name_traversal() -> Result<()> {
         let uncompressed_tar = include_bytes!("../../fixtures/name-traversal.tar");
         let compressed_tar = include_bytes!("../../fixtures/name-traversal.tar.zst");
-        for (tar_bytes, is_compressed) in
-            [(&uncompressed_tar[..], false), (&compressed_tar[..], true)]
-        {
-            let mut cache_reader = CacheReader::from_reader(tar_bytes, is_compressed)?;
+        for tar_bytes in [&uncompressed_tar[..], &compressed_tar[..]] {
+            let mut cache_reader = CacheReader::from_reader(tar_bytes)?;
             let output_dir = tempdir()?;
             let output_dir_path = output_dir.path().to_string_lossy();
             let anchor = AbsoluteSystemPath::new(&output_dir_path)?;
@@ -358,10 +403,8 @@ mod tests {
         let uncompressed_tar = include_bytes!("../../fixtures/windows-unsafe.tar");
         let compressed_tar = include_bytes!("../../fixtures/windows-unsafe.tar.zst");
 
-        for (tar_bytes, is_compressed) in
-            [(&uncompressed_tar[..], false), (&compressed_tar[..], true)]
-        {
-            let mut cache_reader = CacheReader::from_reader(tar_bytes, is_compressed)?;
+        for tar_bytes in [&uncompressed_tar[..], &compressed_tar[..]] {
+            let mut cache_reader = CacheReader::from_reader(tar_bytes)?;
             let output_dir = tempdir()?;
             let output_dir_path = output_dir.path().to_string_lossy();
             let anchor = AbsoluteSystemPath::new(&output_dir_path)?;
@@ -934,4 +977,60 @@ mod tests {
 
         Ok(())
     }
+
+    fn archive_bytes(compression: CacheCompression) -> Result<Vec<u8>> {
+        let input_dir = tempdir()?;
+        let input_path = AbsoluteSystemPath::new(input_dir.path().to_str().unwrap())?;
+        let file = AnchoredSystemPathBuf::from_raw("output.txt")?;
+        input_path
+            .resolve(&file)
+            .create_with_contents((0..100_000).map(|i| (i % 251) as u8).collect::<Vec<_>>())?;
+
+        let mut archive = Vec::new();
+        let mut writer = CacheWriter::from_writer(&mut archive, compression)?;
+        writer.add_file(input_path, &file)?;
+        // Finishing drops the writer, which flushes the compressed stream
+        writer.finish()?;
+
+        Ok(archive)
+    }
+
+    #[test_case(CacheCompression::Zstd, |archive| archive.truncate(archive.len() / 2) ; "truncated zstd")]
+    #[test_case(CacheCompression::Gzip, |archive| archive.truncate(archive.len() / 2) ; "truncated gzip")]
+    #[test_case(CacheCompression::None, |archive| archive.truncate(archive.len() / 2) ; "truncated tar")]
+    #[test_case(CacheCompression::None, |archive| archive[0] ^= 0xFF ; "bad header checksum")]
+    #[test_case(CacheCompression::None, |archive| archive.clear() ; "empty")]
+    fn test_corrupt_archive(
+        compression: CacheCompression,
+        corrupt: fn(&mut Vec<u8>),
+    ) -> Result<()> {
+        let mut archive = archive_bytes(compression)?;
+        corrupt(&mut archive);
+
+        let output_dir = tempdir()?;
+        let anchor = AbsoluteSystemPath::new(output_dir.path().to_str().unwrap())?;
+        let result = CacheReader::from_reader(archive.as_slice())?.restore(anchor);
+        assert!(
+            matches!(result, Err(CacheError::Corrupt(..))),
+            "expected a corrupt archive, got {result:?}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_error_is_not_corrupt() -> Result<()> {
+        let archive = archive_bytes(CacheCompression::Zstd)?;
+
+        // A directory where the file should be restored makes writing the output
+        // fail even though the archive is fine
+        let output_dir = tempdir()?;
+        let anchor = AbsoluteSystemPath::new(output_dir.path().to_str().unwrap())?;
+        anchor.join_component("output.txt").create_dir_all()?;
+        let result = CacheReader::from_reader(archive.as_slice())?.restore(anchor);
+        assert!(result.is_err());
+        assert!(!matches!(result, Err(CacheError::Corrupt(..))));
+
+        Ok(())
+    }
 }
@@ -2,18 +2,20 @@ use std::{backtrace::Backtrace, fs::OpenOptions};
 
 use camino::Utf8Path;
 use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
 use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPathBuf};
 use turborepo_analytics::AnalyticsSender;
 use turborepo_api_client::{analytics, analytics::AnalyticsEvent};
 
 use crate::{
-    cache_archive::{CacheReader, CacheWriter},
+    cache_archive::{CacheCompression, CacheReader, CacheWriter},
     CacheError, CacheHitMetadata, CacheSource,
 };
 
 pub struct FSCache {
     cache_directory: AbsoluteSystemPathBuf,
     analytics_recorder: Option<AnalyticsSender>,
+    compression: CacheCompression,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -42,6 +44,7 @@ impl FSCache {
         cache_dir: &Utf8Path,
         repo_root: &AbsoluteSystemPath,
         analytics_recorder: Option<AnalyticsSender>,
+        compression: CacheCompression,
     ) -> Result<Self, CacheError> {
         let cache_directory = Self::resolve_cache_dir(repo_root, cache_dir);
         cache_directory.create_dir_all()?;
@@ -49,6 +52,7 @@ impl FSCache {
         Ok(FSCache {
             cache_directory,
             analytics_recorder,
+            compression,
         })
     }
 
@@ -89,15 +93,29 @@ impl FSCache {
             return Ok(None);
         };
 
+        let meta_path = self
+            .cache_directory
+            .join_component(&format!("{}-meta.json", hash));
+
         let mut cache_reader = CacheReader::open(&cache_path)?;
 
-        let restored_files = cache_reader.restore(anchor)?;
+        let restored_files = match cache_reader.restore(anchor) {
+            Ok(restored_files) => restored_files,
+            Err(err @ CacheError::Corrupt(..)) => {
+                // Remove the artifact so that the task gets rebuilt and cached again
+                // instead of failing to restore on every run
+                warn!("removing corrupt cache artifact {cache_path}: {err}");
+                for path in [&cache_path, &meta_path] {
+                    if let Err(e) = path.remove_file() {
+                        debug!("failed to remove {path}: {e}");
+                    }
+                }
+                return Err(err);
+            }
+            Err(err) => return Err(err),
+        };
 
-        let meta = CacheMetadata::read(
-            &self
-                .cache_directory
-                .join_component(&format!("{}-meta.json", hash)),
-        )?;
+        let meta = CacheMetadata::read(&meta_path)?;
 
         self.log_fetch(analytics::CacheEvent::Hit, hash, meta.duration);
 
@@ -145,11 +163,13 @@ impl FSCache {
         files: &[AnchoredSystemPathBuf],
         duration: u64,
     ) -> Result<(), CacheError> {
+        // Artifacts keep the `.tar.zst` name regardless of codec, readers
+        // detect the codec from the archive header
         let cache_path = self
             .cache_directory
             .join_component(&format!("{}.tar.zst", hash));
 
-        let mut cache_item = CacheWriter::create(&cache_path)?;
+        let mut cache_item = CacheWriter::create_with_compression(&cache_path, self.compression)?;
 
         for file in files {
             cache_item.add_file(anchor, file)?;
@@ -233,6 +253,7 @@ mod test {
             Utf8Path::new(""),
             repo_root_path,
             Some(analytics_sender.clone()),
+            CacheCompression::default(),
         )?;
 
         let expected_miss = cache.fetch(repo_root_path, test_case.hash)?;
@@ -270,4 +291,41 @@ mod test {
         analytics_handle.close_with_timeout().await;
         Ok(())
     }
+
+    #[test]
+    fn test_corrupt_artifact_is_removed() -> Result<()> {
+        let repo_root = tempdir()?;
+        let repo_root_path = AbsoluteSystemPath::from_std_path(repo_root.path())?;
+        let output = repo_root_path.join_component("output.txt");
+        output.create_with_contents("output contents".repeat(1000))?;
+
+        let cache = FSCache::new(
+            Utf8Path::new("cache"),
+            repo_root_path,
+            None,
+            CacheCompression::default(),
+        )?;
+        let hash = "the-hash";
+        cache.put(
+            repo_root_path,
+            hash,
+            &[AnchoredSystemPathBuf::from_raw("output.txt")?],
+            10,
+        )?;
+
+        let artifact = repo_root_path.join_components(&["cache", "the-hash.tar.zst"]);
+        let contents = artifact.read()?;
+        artifact.create_with_contents(&contents[..contents.len() / 2])?;
+
+        let result = cache.fetch(repo_root_path, hash);
+        assert!(matches!(result, Err(CacheError::Corrupt(..))));
+        assert!(!artifact.exists());
+        assert!(!repo_root_path
+            .join_components(&["cache", "the-hash-meta.json"])
+            .exists());
+        // With the artifact gone the next fetch is a plain miss
+        assert!(cache.fetch(repo_root_path, hash)?.is_none());
+
+        Ok(())
+    }
 }
@@ -15,7 +15,7 @@ use turborepo_api_client::{
 };
 
 use crate::{
-    cache_archive::{CacheReader, CacheWriter},
+    cache_archive::{CacheCompression, CacheReader, CacheWriter},
     signature_authentication::ArtifactSignatureAuthenticator,
     upload_progress::{UploadProgress, UploadProgressQuery},
     CacheError, CacheHitMetadata, CacheOpts, CacheSource,
@@ -140,7 +140,9 @@ impl HTTPCache {
         anchor: &AbsoluteSystemPath,
         files: &[AnchoredSystemPathBuf],
     ) -> Result<(), CacheError> {
-        let mut cache_archive = CacheWriter::from_writer(writer, true)?;
+        // Remote artifacts are always zstd so that older versions of turbo and
+        // other clients sharing the remote cache can read them
+        let mut cache_archive = CacheWriter::from_writer(writer, CacheCompression::Zstd)?;
         for file in files {
             cache_archive.add_file(anchor, file)?;
         }
@@ -275,7 +277,7 @@ impl HTTPCache {
         root: &AbsoluteSystemPath,
         body: &[u8],
     ) -> Result<Vec<AnchoredSystemPathBuf>, CacheError> {
-        let mut cache_reader = CacheReader::from_reader(body, true)?;
+        let mut cache_reader = CacheReader::from_reader(body)?;
         cache_reader.restore(root)
     }
 }
@@ -287,7 +289,7 @@ mod test {
     use anyhow::Result;
     use futures::future::try_join_all;
     use tempfile::tempdir;
-    use turbopath::AbsoluteSystemPathBuf;
+    use turbopath::{AbsoluteSystemPathBuf, AnchoredSystemPathBuf};
     use turborepo_analytics::start_analytics;
     use turborepo_api_client::{analytics, APIClient};
     use turborepo_vercel_api_mock::start_test_server;
@@ -295,7 +297,7 @@ mod test {
     use crate::{
         http::{APIAuth, HTTPCache},
         test_cases::{get_test_cases, validate_analytics, TestCase},
-        CacheOpts, CacheSource,
+        CacheCompression, CacheOpts, CacheSource,
     };
 
     #[tokio::test]
@@ -316,6 +318,45 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_remote_artifacts_are_zstd() -> Result<()> {
+        let repo_root = tempdir()?;
+        let repo_root_path = AbsoluteSystemPathBuf::try_from(repo_root.path())?;
+        repo_root_path
+            .join_component("one")
+            .create_with_contents("one")?;
+
+        let api_client = APIClient::new("http://localhost:0", None, None, "2.0.0", true)?;
+        // The configured codec only applies to the local cache
+        let opts = CacheOpts {
+            compression: CacheCompression::Gzip,
+            ..CacheOpts::default()
+        };
+        let cache = HTTPCache::new(
+            api_client,
+            &opts,
+            repo_root_path.clone(),
+            APIAuth {
+                team_id: Some("my-team".to_string()),
+                token: "my-token".to_string(),
+                team_slug: None,
+            },
+            None,
+        );
+
+        let mut artifact = Vec::new();
+        cache
+            .write(
+                &mut artifact,
+                &repo_root_path,
+                &[AnchoredSystemPathBuf::from_raw("one")?],
+            )
+            .await?;
+        assert_eq!(CacheCompression::detect(&artifact), CacheCompression::Zstd);
+
+        Ok(())
+    }
+
     async fn round_trip_test(test_case: &TestCase, port: u16) -> Result<()> {
         let repo_root = tempdir()?;
         let repo_root_path = AbsoluteSystemPathBuf::try_from(repo_root.path())?;
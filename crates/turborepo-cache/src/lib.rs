@@ -24,6 +24,7 @@ mod upload_progress;
 use std::{backtrace, backtrace::Backtrace};
 
 pub use async_cache::AsyncCache;
+pub use cache_archive::CacheCompression;
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -69,6 +70,8 @@ pub enum CacheError {
     CreateUnsupportedFileType(#[backtrace] Backtrace),
     #[error("tar file is malformed")]
     MalformedTar(#[backtrace] Backtrace),
+    #[error("cache artifact is corrupt: {0}")]
+    Corrupt(String, #[backtrace] Backtrace),
     #[error("file name is not Windows-safe: {0}")]
     WindowsUnsafeName(String, #[backtrace] Backtrace),
     #[error("tar attempts to write outside of directory: {0}")]
@@ -111,6 +114,8 @@ pub struct CacheOpts {
     pub skip_filesystem: bool,
     pub workers: u32,
     pub remote_cache_opts: Option<RemoteCacheOpts>,
+    // Only used for the local cache, remote artifacts are always zstd
+    pub compression: CacheCompression,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -48,7 +48,14 @@ impl CacheMultiplexer {
         }
 
         let fs_cache = use_fs_cache
-            .then(|| FSCache::new(&opts.cache_dir, repo_root, analytics_recorder.clone()))
+            .then(|| {
+                FSCache::new(
+                    &opts.cache_dir,
+                    repo_root,
+                    analytics_recorder.clone(),
+                    opts.compression,
+                )
+            })
             .transpose()?;
 
         let http_cache = use_http_cache
@@ -144,9 +151,15 @@ impl CacheMultiplexer {
         anchor: &AbsoluteSystemPath,
         key: &str,
     ) -> Result<Option<(CacheHitMetadata, Vec<AnchoredSystemPathBuf>)>, CacheError> {
+        let mut fs_error = None;
         if let Some(fs) = &self.fs {
-            if let response @ Ok(Some(_)) = fs.fetch(anchor, key) {
-                return response;
+            match fs.fetch(anchor, key) {
+                response @ Ok(Some(_)) => return response,
+                Ok(None) => {}
+                // The corrupt artifact has been removed, but it's still worth checking
+                // the remote cache before reporting it
+                Err(err @ CacheError::Corrupt(..)) => fs_error = Some(err),
+                Err(err) => debug!("failed to fetch from fs cache: {:?}", err),
             }
         }
 
@@ -166,7 +179,10 @@ impl CacheMultiplexer {
             }
         }
 
-        Ok(None)
+        match fs_error {
+            Some(err) => Err(err),
+            None => Ok(None),
+        }
     }
 
     #[tracing::instrument(skip_all)]
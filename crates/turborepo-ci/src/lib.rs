@@ -102,6 +102,7 @@ impl Vendor {
 
 #[cfg(test)]
 mod tests {
+    use chrono::DateTime;
     use tracing::info;
 
     use super::*;
@@ -116,6 +117,31 @@ mod tests {
         unreachable!("vendor not found")
     }
 
+    #[test]
+    fn test_gitlab_behavior() {
+        let behavior = get_vendor("GitLab CI")
+            .behavior
+            .expect("GitLab CI supports collapsible sections");
+        let time = DateTime::from_timestamp(1700000000, 0).unwrap();
+
+        let header = (behavior.group_prefix)("web:build".to_string());
+        assert_eq!(
+            header(time),
+            "\x1B[0Ksection_start:1700000000:web_build\r\x1B[0Kweb:build\n"
+        );
+        let footer = (behavior.group_suffix)("web:build".to_string());
+        assert_eq!(
+            footer(time),
+            "\x1B[0Ksection_end:1700000000:web_build\r\x1B[0K\n"
+        );
+    }
+
+    #[test]
+    fn test_circleci_behavior() {
+        // CircleCI has no syntax for collapsing sections of a step's output
+        assert_eq!(get_vendor("CircleCI").behavior, None);
+    }
+
     struct TestCase {
         name: String,
         set_env: Vec<String>,
@@ -163,6 +189,16 @@ mod tests {
                 set_env: vec!["GITHUB_ACTIONS".to_string()],
                 want: Some(get_vendor("GitHub Actions")),
             },
+            TestCase {
+                name: "GitLab CI".to_string(),
+                set_env: vec!["GITLAB_CI".to_string()],
+                want: Some(get_vendor("GitLab CI")),
+            },
+            TestCase {
+                name: "CircleCI".to_string(),
+                set_env: vec!["CIRCLECI".to_string()],
+                want: Some(get_vendor("CircleCI")),
+            },
             TestCase {
                 name: "Codeship".to_string(),
                 set_env: vec!["CI_NAME=codeship".to_string()],
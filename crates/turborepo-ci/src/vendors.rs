@@ -4,6 +4,8 @@ use std::{
     sync::{Arc, OnceLock},
 };
 
+use chrono::{DateTime, Utc};
+
 use crate::vendor_behavior::VendorBehavior;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -27,6 +29,20 @@ pub struct Vendor {
 
 static VENDORS: OnceLock<[Vendor; 45]> = OnceLock::new();
 
+// Formats a GitLab collapsible section marker. Section names may only contain
+// letters, numbers, `_`, `.` and `-`.
+fn gitlab_section(marker: &str, time: DateTime<Utc>, group_name: &str, header: &str) -> String {
+    let section_name: String = group_name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.' | '-' => c,
+            _ => '_',
+        })
+        .collect();
+    let timestamp = time.timestamp();
+    format!("\x1B[0K{marker}:{timestamp}:{section_name}\r\x1B[0K{header}\n")
+}
+
 pub(crate) fn get_vendors() -> &'static [Vendor] {
     VENDORS
         .get_or_init(|| {
@@ -298,17 +314,17 @@ pub(crate) fn get_vendors() -> &'static [Vendor] {
                     behavior: Some(VendorBehavior::new(
                         |group_name| {
                             Arc::new(move |start_time| {
-                                let timestamp = start_time.timestamp();
-                                format!(
-                                    "\\e[0Ksection_start:{timestamp}:{group_name}\\r\\
-                                     e[0K{group_name}"
+                                gitlab_section(
+                                    "section_start",
+                                    start_time,
+                                    &group_name,
+                                    &group_name,
                                 )
                             })
                         },
                         |group_name| {
                             Arc::new(move |end_time| {
-                                let timestamp = end_time.timestamp();
-                                format!("\\e[0Ksection_end:{timestamp}:{group_name}\\r\\e[0K")
+                                gitlab_section("section_end", end_time, &group_name, "")
                             })
                         },
                     )),
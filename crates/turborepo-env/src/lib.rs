@@ -19,6 +19,8 @@ const DEFAULT_ENV_VARS: [&str; 1] = ["VERCEL_ANALYTICS_ID"];
 pub enum Error {
     #[error("Failed to parse regex: {0}")]
     Regex(#[from] regex::Error),
+    #[error("Invalid env file entry on line {line}: {content}")]
+    InvalidEnvFileLine { line: usize, content: String },
 }
 
 // TODO: Consider using immutable data structures here
@@ -89,6 +91,15 @@ pub struct DetailedMap {
 // A list of "k=v" strings for env variables and their values
 pub type EnvironmentVariablePairs = Vec<String>;
 
+// EnvironmentDiff holds the names of the variables that differ between an
+// environment and the environment it was derived from.
+// `added` includes variables that are present in both but with different values
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct EnvironmentDiff {
+    pub dropped: Vec<String>,
+    pub added: Vec<String>,
+}
+
 // WildcardMaps is a pair of EnvironmentVariableMaps.
 #[derive(Debug)]
 pub struct WildcardMaps {
@@ -134,6 +145,45 @@ impl EnvironmentVariableMap {
         self.0
     }
 
+    // Parses the contents of a `.env` style file. Blank lines and lines starting
+    // with `#` are skipped, an `export ` prefix is allowed, and values can be
+    // wrapped in single or double quotes.
+    pub fn from_env_file_contents(contents: &str) -> Result<Self, Error> {
+        let mut output = EnvironmentVariableMap::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = line.strip_prefix("export ").unwrap_or(line);
+            let Some((key, value)) = entry.split_once('=') else {
+                return Err(Error::InvalidEnvFileLine {
+                    line: index + 1,
+                    content: line.to_string(),
+                });
+            };
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(Error::InvalidEnvFileLine {
+                    line: index + 1,
+                    content: line.to_string(),
+                });
+            }
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|quote| {
+                    value
+                        .strip_prefix(*quote)
+                        .and_then(|value| value.strip_suffix(*quote))
+                })
+                .unwrap_or(value);
+            output.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(output)
+    }
+
     // Takes another EnvironmentVariableMap and adds it into `self`
     // Overwrites values if they already exist.
     pub fn union(&mut self, another: &EnvironmentVariableMap) {
@@ -150,6 +200,26 @@ impl EnvironmentVariableMap {
         }
     }
 
+    // Compares `self` against the environment it was derived from and returns
+    // the sorted names of the variables that were dropped or added
+    pub fn diff(&self, original: &EnvironmentVariableMap) -> EnvironmentDiff {
+        let mut dropped = original
+            .0
+            .keys()
+            .filter(|key| !self.0.contains_key(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut added = self
+            .0
+            .iter()
+            .filter(|(key, value)| original.0.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        dropped.sort();
+        added.sort();
+        EnvironmentDiff { dropped, added }
+    }
+
     // returns a WildcardMaps after processing wildcards against it.
     fn wildcard_map_from_wildcards(
         &self,
@@ -313,4 +383,65 @@ mod tests {
         let actual = super::wildcard_to_regex_pattern(pattern);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_from_env_file_contents() {
+        let contents =
+            "# comment\n\nFOO=bar\nexport BAZ = qux \nQUOTED=\"a b\"\nSINGLE='c=d'\nEMPTY=\n";
+        let env = super::EnvironmentVariableMap::from_env_file_contents(contents).unwrap();
+        assert_eq!(env.get("FOO").map(String::as_str), Some("bar"));
+        assert_eq!(env.get("BAZ").map(String::as_str), Some("qux"));
+        assert_eq!(env.get("QUOTED").map(String::as_str), Some("a b"));
+        assert_eq!(env.get("SINGLE").map(String::as_str), Some("c=d"));
+        assert_eq!(env.get("EMPTY").map(String::as_str), Some(""));
+        assert_eq!(env.len(), 5);
+    }
+
+    fn env(vars: &[(&str, &str)]) -> super::EnvironmentVariableMap {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<std::collections::HashMap<_, _>>()
+            .into()
+    }
+
+    #[test_case(
+        &[("PATH", "/bin"), ("HOME", "/home"), ("SECRET", "shh")],
+        &[("PATH", "/bin"), ("HOME", "/root"), ("TURBO_TOKEN", "abc")],
+        &["SECRET"],
+        &["HOME", "TURBO_TOKEN"]
+        ; "overlapping"
+    )]
+    #[test_case(
+        &[("PATH", "/bin"), ("HOME", "/home")],
+        &[("NODE_ENV", "production"), ("CI", "1")],
+        &["HOME", "PATH"],
+        &["CI", "NODE_ENV"]
+        ; "disjoint"
+    )]
+    #[test_case(
+        &[("PATH", "/bin")],
+        &[("PATH", "/bin")],
+        &[],
+        &[]
+        ; "identical"
+    )]
+    fn test_diff(
+        original: &[(&str, &str)],
+        derived: &[(&str, &str)],
+        dropped: &[&str],
+        added: &[&str],
+    ) {
+        let diff = env(derived).diff(&env(original));
+        assert_eq!(diff.dropped, dropped);
+        assert_eq!(diff.added, added);
+    }
+
+    #[test_case("NOT_AN_ASSIGNMENT" ; "missing equals")]
+    #[test_case("=value" ; "missing key")]
+    fn test_from_env_file_contents_invalid(contents: &str) {
+        assert!(matches!(
+            super::EnvironmentVariableMap::from_env_file_contents(contents),
+            Err(super::Error::InvalidEnvFileLine { line: 1, .. })
+        ));
+    }
 }
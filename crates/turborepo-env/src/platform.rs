@@ -28,6 +28,11 @@ impl PlatformEnv {
         Self { env_keys }
     }
 
+    /// Creates a `PlatformEnv` that expects the given env vars to be set
+    pub fn from_keys(env_keys: Vec<String>) -> Self {
+        Self { env_keys }
+    }
+
     pub fn disabled() -> bool {
         let turbo_platform_env_disabled =
             std::env::var(TURBO_PLATFORM_ENV_DISABLED_KEY).unwrap_or_default();
@@ -149,6 +149,14 @@ impl HashWatcher {
         self.query_tx.send(Query::GetHash(hash_spec, tx)).await?;
         rx.await?
     }
+
+    /// Returns the number of distinct files that currently have their hashes
+    /// tracked.
+    pub async fn hashed_file_count(&self) -> Result<usize, Error> {
+        let (tx, rx) = oneshot::channel();
+        self.query_tx.send(Query::HashedFileCount(tx)).await?;
+        Ok(rx.await?)
+    }
 }
 
 struct Subscriber {
@@ -162,6 +170,7 @@ struct Subscriber {
 #[derive(Debug)]
 enum Query {
     GetHash(HashSpec, oneshot::Sender<Result<GitHashes, Error>>),
+    HashedFileCount(oneshot::Sender<usize>),
 }
 
 // Version is a type that exists to stamp an asynchronous hash computation
@@ -309,6 +318,26 @@ impl FileHashes {
             .get_mut(key.package_path.as_str())
             .and_then(|states| states.get_mut(&key.inputs))
     }
+
+    // Files can be tracked by multiple specs for the same package, so we count
+    // each path once. Specs that are still pending aren't counted.
+    fn hashed_file_count(&self) -> usize {
+        self.0
+            .iter()
+            .flat_map(|(package_path, states)| {
+                states.values().flat_map(move |state| match state {
+                    HashState::Hashes(hashes) => Some(
+                        hashes
+                            .keys()
+                            .map(move |file| (package_path.as_str(), file.as_str())),
+                    ),
+                    HashState::Pending(..) | HashState::Unavailable(_) => None,
+                })
+            })
+            .flatten()
+            .collect::<HashSet<_>>()
+            .len()
+    }
 }
 
 struct HashUpdate {
@@ -424,8 +453,6 @@ impl Subscriber {
         self.handle_package_data_update(package_data, hashes, hash_update_tx);
     }
 
-    // We currently only support a single query, getting hashes for a given
-    // HashSpec.
     fn handle_query(
         &self,
         query: Query,
@@ -473,6 +500,9 @@ impl Subscriber {
                     let _ = tx.send(Err(Error::UnknownPackage(spec)));
                 }
             }
+            Query::HashedFileCount(tx) => {
+                let _ = tx.send(hashes.hashed_file_count());
+            }
         }
     }
 
@@ -1041,6 +1071,53 @@ mod tests {
         map
     }
 
+    #[test]
+    fn test_hashed_file_count() {
+        let mut hashes = FileHashes::new();
+        assert_eq!(hashes.hashed_file_count(), 0);
+
+        let root = AnchoredSystemPathBuf::try_from("").unwrap();
+        let foo_path = root.join_components(&["apps", "foo"]);
+        let file_hashes = |files: &[&str]| {
+            files
+                .iter()
+                .map(|file| (RelativeUnixPathBuf::new(*file).unwrap(), "hash".to_string()))
+                .collect::<GitHashes>()
+        };
+        hashes.insert(
+            HashSpec {
+                package_path: foo_path.clone(),
+                inputs: InputGlobs::Default,
+            },
+            HashState::Hashes(file_hashes(&["package.json", "src/index.ts"])),
+        );
+        // Overlaps with the default inputs, so only adds one new file
+        hashes.insert(
+            HashSpec {
+                package_path: foo_path.clone(),
+                inputs: InputGlobs::from_raw(vec!["src/**".to_string()]).unwrap(),
+            },
+            HashState::Hashes(file_hashes(&["src/index.ts", "src/util.ts"])),
+        );
+        // The same relative path in another package is a different file
+        hashes.insert(
+            HashSpec {
+                package_path: root.join_components(&["apps", "bar"]),
+                inputs: InputGlobs::Default,
+            },
+            HashState::Hashes(file_hashes(&["package.json"])),
+        );
+        hashes.insert(
+            HashSpec {
+                package_path: root.join_components(&["apps", "baz"]),
+                inputs: InputGlobs::Default,
+            },
+            HashState::Unavailable("not hashed".to_string()),
+        );
+
+        assert_eq!(hashes.hashed_file_count(), 4);
+    }
+
     #[test]
     fn test_file_hashes_ancestor() {
         let mut hashes = FileHashes::new();
@@ -1,4 +1,4 @@
-use std::{backtrace::Backtrace, env, fmt, fmt::Display, io, mem, process};
+use std::{backtrace::Backtrace, env, ffi::OsString, fmt, fmt::Display, io, mem, process};
 
 use biome_deserialize_macros::Deserializable;
 use camino::{Utf8Path, Utf8PathBuf};
@@ -40,8 +40,10 @@ pub const INVOCATION_DIR_ENV_VAR: &str = "TURBO_INVOCATION_DIR";
 
 // Default value for the --cache-workers argument
 const DEFAULT_NUM_WORKERS: u32 = 10;
-const SUPPORTED_GRAPH_FILE_EXTENSIONS: [&str; 8] =
-    ["svg", "png", "jpg", "pdf", "json", "html", "mermaid", "dot"];
+const DEFAULT_KILL_TIMEOUT_SECS: u64 = 5;
+const SUPPORTED_GRAPH_FILE_EXTENSIONS: [&str; 9] = [
+    "svg", "png", "jpg", "pdf", "json", "html", "mermaid", "mmd", "dot",
+];
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Deserializable, Serialize)]
 pub enum OutputLogsMode {
@@ -55,6 +57,8 @@ pub enum OutputLogsMode {
     NewOnly,
     #[serde(rename = "errors-only")]
     ErrorsOnly,
+    #[serde(rename = "errors-only-with-summary")]
+    ErrorsOnlyWithSummary,
 }
 
 impl Default for OutputLogsMode {
@@ -71,6 +75,7 @@ impl Display for OutputLogsMode {
             OutputLogsMode::HashOnly => "hash-only",
             OutputLogsMode::NewOnly => "new-only",
             OutputLogsMode::ErrorsOnly => "errors-only",
+            OutputLogsMode::ErrorsOnlyWithSummary => "errors-only-with-summary",
         })
     }
 }
@@ -83,6 +88,9 @@ impl From<OutputLogsMode> for turborepo_ui::tui::event::OutputLogs {
             OutputLogsMode::HashOnly => turborepo_ui::tui::event::OutputLogs::HashOnly,
             OutputLogsMode::NewOnly => turborepo_ui::tui::event::OutputLogs::NewOnly,
             OutputLogsMode::ErrorsOnly => turborepo_ui::tui::event::OutputLogs::ErrorsOnly,
+            OutputLogsMode::ErrorsOnlyWithSummary => {
+                turborepo_ui::tui::event::OutputLogs::ErrorsOnlyWithSummary
+            }
         }
     }
 }
@@ -95,6 +103,8 @@ pub enum LogOrder {
     Stream,
     #[serde(rename = "grouped")]
     Grouped,
+    #[serde(rename = "json")]
+    Json,
 }
 
 impl Default for LogOrder {
@@ -109,6 +119,7 @@ impl Display for LogOrder {
             LogOrder::Auto => "auto",
             LogOrder::Stream => "stream",
             LogOrder::Grouped => "grouped",
+            LogOrder::Json => "json",
         })
     }
 }
@@ -125,6 +136,46 @@ impl LogOrder {
 pub enum DryRunMode {
     Text,
     Json,
+    // Writes each task as a line of JSON as soon as it's planned
+    Ndjson,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
+pub enum OnlyMode {
+    /// Only run the specified tasks
+    Tasks,
+    /// Run the specified tasks and their direct dependencies
+    DirectDeps,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, ValueEnum)]
+pub enum ContinueMode {
+    /// Stop the run as soon as a task fails
+    #[default]
+    Never,
+    /// Keep running all remaining tasks after a failure
+    Always,
+    /// Keep running tasks whose dependencies all succeeded
+    DependenciesSuccessful,
+}
+
+impl Display for ContinueMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ContinueMode::Never => "never",
+            ContinueMode::Always => "always",
+            ContinueMode::DependenciesSuccessful => "dependencies-successful",
+        })
+    }
+}
+
+impl Display for OnlyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OnlyMode::Tasks => "tasks",
+            OnlyMode::DirectDeps => "direct-deps",
+        })
+    }
 }
 
 impl Display for DryRunMode {
@@ -132,6 +183,7 @@ impl Display for DryRunMode {
         f.write_str(match self {
             DryRunMode::Text => "text",
             DryRunMode::Json => "json",
+            DryRunMode::Ndjson => "ndjson",
         })
     }
 }
@@ -155,6 +207,56 @@ impl fmt::Display for EnvMode {
     }
 }
 
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Serialize, ValueEnum, Deserialize, Eq, Deserializable,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheCompression {
+    #[default]
+    Zstd,
+    Gzip,
+    None,
+}
+
+impl fmt::Display for CacheCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CacheCompression::Zstd => "zstd",
+            CacheCompression::Gzip => "gzip",
+            CacheCompression::None => "none",
+        })
+    }
+}
+
+impl From<CacheCompression> for turborepo_cache::CacheCompression {
+    fn from(value: CacheCompression) -> Self {
+        match value {
+            CacheCompression::Zstd => turborepo_cache::CacheCompression::Zstd,
+            CacheCompression::Gzip => turborepo_cache::CacheCompression::Gzip,
+            CacheCompression::None => turborepo_cache::CacheCompression::None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileFormat {
+    /// JSON that can be loaded in chrome://tracing
+    #[default]
+    Chrome,
+    /// Protobuf that can be loaded in https://ui.perfetto.dev
+    Perfetto,
+}
+
+impl fmt::Display for ProfileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProfileFormat::Chrome => "chrome",
+            ProfileFormat::Perfetto => "perfetto",
+        })
+    }
+}
+
 #[derive(Parser, Clone, Default, Debug, PartialEq)]
 #[clap(author, about = "The build system that makes ship happen", long_about = None)]
 #[clap(disable_help_subcommand = true)]
@@ -291,7 +393,14 @@ pub enum DaemonCommand {
         clean_logs: bool,
     },
     /// Shows the daemon logs
-    Logs,
+    Logs {
+        /// Keep printing new lines as they are written to the log file
+        #[clap(short, long)]
+        follow: bool,
+        /// The number of lines to show from the end of the log file
+        #[clap(short = 'n', long)]
+        lines: Option<usize>,
+    },
 }
 
 #[derive(Copy, Clone, Debug, Default, ValueEnum, Serialize, Eq, PartialEq)]
@@ -323,6 +432,23 @@ pub enum TelemetryCommand {
     Status,
 }
 
+#[derive(Subcommand, Copy, Clone, Debug, PartialEq)]
+pub enum ConfigCommand {
+    /// Sets a value in the global turbo config
+    Set {
+        key: ConfigKey,
+        #[clap(action = ArgAction::Set)]
+        value: bool,
+    },
+}
+
+/// Config values that can be set with `turbo config set`
+#[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
+pub enum ConfigKey {
+    #[value(name = "updateNotifier")]
+    UpdateNotifier,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, ValueEnum)]
 pub enum LinkTarget {
     RemoteCache,
@@ -331,51 +457,8 @@ pub enum LinkTarget {
 
 impl Args {
     pub fn new() -> Self {
-        // We always pass --single-package in from the shim.
-        // We need to omit it, and then add it in for run.
-        let arg_separator_position = env::args_os().position(|input_token| input_token == "--");
-
-        let single_package_position =
-            env::args_os().position(|input_token| input_token == "--single-package");
-
-        let is_single_package = match (arg_separator_position, single_package_position) {
-            (_, None) => false,
-            (None, Some(_)) => true,
-            (Some(arg_separator_position), Some(single_package_position)) => {
-                single_package_position < arg_separator_position
-            }
-        };
-
-        // Clap supports arbitrary iterators as input.
-        // We can remove all instances of --single-package
-        let single_package_free = std::env::args_os()
-            .enumerate()
-            .filter(|(index, input_token)| {
-                arg_separator_position
-                    .is_some_and(|arg_separator_position| index > &arg_separator_position)
-                    || input_token != "--single-package"
-            })
-            .map(|(_, input_token)| input_token);
-
-        let mut clap_args = match Args::try_parse_from(single_package_free) {
-            Ok(mut args) => {
-                // And then only add them back in when we're in `run`.
-                // The value can appear in two places in the struct.
-                // We defensively attempt to set both.
-                if let Some(ref mut execution_args) = args.execution_args {
-                    execution_args.single_package = is_single_package
-                }
-
-                if let Some(Command::Run {
-                    run_args: _,
-                    ref mut execution_args,
-                }) = args.command
-                {
-                    execution_args.single_package = is_single_package;
-                }
-
-                args
-            }
+        let mut clap_args = match Args::try_new(env::args_os()) {
+            Ok(args) => args,
             // Don't use error logger when displaying help text
             Err(e)
                 if matches!(
@@ -417,6 +500,57 @@ impl Args {
         clap_args
     }
 
+    /// Parses `argv` without exiting the process on errors, `--help`, or
+    /// `--version`.
+    pub fn try_new(argv: impl IntoIterator<Item = OsString>) -> Result<Self, clap::Error> {
+        let argv = join_summarize_values(expand_response_files(argv.into_iter().collect())?);
+        // We always pass --single-package in from the shim.
+        // We need to omit it, and then add it in for run.
+        let arg_separator_position = argv.iter().position(|input_token| input_token == "--");
+
+        let single_package_position = argv
+            .iter()
+            .position(|input_token| input_token == "--single-package");
+
+        let is_single_package = match (arg_separator_position, single_package_position) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(arg_separator_position), Some(single_package_position)) => {
+                single_package_position < arg_separator_position
+            }
+        };
+
+        // Clap supports arbitrary iterators as input.
+        // We can remove all instances of --single-package
+        let single_package_free = argv
+            .into_iter()
+            .enumerate()
+            .filter(|(index, input_token)| {
+                arg_separator_position
+                    .is_some_and(|arg_separator_position| index > &arg_separator_position)
+                    || input_token != "--single-package"
+            })
+            .map(|(_, input_token)| input_token);
+
+        let mut args = Args::try_parse_from(single_package_free)?;
+        // And then only add them back in when we're in `run`.
+        // The value can appear in two places in the struct.
+        // We defensively attempt to set both.
+        if let Some(ref mut execution_args) = args.execution_args {
+            execution_args.single_package = is_single_package
+        }
+
+        if let Some(Command::Run {
+            run_args: _,
+            ref mut execution_args,
+        }) = args.command
+        {
+            execution_args.single_package = is_single_package;
+        }
+
+        Ok(args)
+    }
+
     pub fn track(&self, tel: &GenericEventBuilder) {
         // track usage only
         track_usage!(tel, self.skip_infer, |val| val);
@@ -524,7 +658,10 @@ pub enum Command {
     /// identify common issues, suggest fixes, and improve performance.
     Scan,
     #[clap(hide = true)]
-    Config,
+    Config {
+        #[clap(subcommand)]
+        command: Option<ConfigCommand>,
+    },
     /// EXPERIMENTAL: List packages in your monorepo.
     Ls {
         /// Show only packages that are affected by changes between
@@ -540,6 +677,9 @@ pub enum Command {
         /// Get insight into a specific package, such as
         /// its dependencies and tasks
         packages: Vec<String>,
+        /// Show the dependency graph of the packages
+        #[clap(long, conflicts_with = "packages")]
+        graph: bool,
         /// Output format
         #[clap(long, value_enum)]
         output: Option<OutputFormat>,
@@ -679,8 +819,74 @@ pub enum GenerateCommand {
     Run(GeneratorCustomArgs),
 }
 
+/// Replaces any `@path` argument before the `--` separator with the contents
+/// of that file, one argument per line. Blank lines and lines starting with
+/// `#` are skipped. Arguments that don't name a file, such as `@scope/pkg`
+/// filters, are left untouched.
+fn expand_response_files(argv: Vec<OsString>) -> Result<Vec<OsString>, clap::Error> {
+    let mut expanded = Vec::with_capacity(argv.len());
+    let mut argv = argv.into_iter();
+    for arg in argv.by_ref() {
+        if arg == "--" {
+            expanded.push(arg);
+            break;
+        }
+        let Some(path) = arg
+            .to_str()
+            .and_then(|arg| arg.strip_prefix('@'))
+            .map(Utf8Path::new)
+            .filter(|path| path.is_file())
+        else {
+            expanded.push(arg);
+            continue;
+        };
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            clap::Error::raw(
+                clap::error::ErrorKind::Io,
+                format!("unable to read arguments from {path}: {err}\n"),
+            )
+        })?;
+        expanded.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(OsString::from),
+        );
+    }
+    expanded.extend(argv);
+    Ok(expanded)
+}
+
+/// Joins `--summarize true` and `--summarize false` before the `--` separator
+/// into `--summarize=<value>`. A path has to be passed with `=` so that
+/// `--summarize build` runs the `build` task, but the boolean values are still
+/// accepted as a separate argument.
+fn join_summarize_values(argv: Vec<OsString>) -> Vec<OsString> {
+    let mut joined = Vec::with_capacity(argv.len());
+    let mut argv = argv.into_iter().peekable();
+    while let Some(arg) = argv.next() {
+        if arg == "--" {
+            joined.push(arg);
+            break;
+        }
+        if arg == "--summarize" {
+            if let Some(value) = argv.next_if(|value| value == "true" || value == "false") {
+                let mut arg = arg;
+                arg.push("=");
+                arg.push(value);
+                joined.push(arg);
+                continue;
+            }
+        }
+        joined.push(arg);
+    }
+    joined.extend(argv);
+    joined
+}
+
 fn validate_graph_extension(s: &str) -> Result<String, String> {
-    match s.is_empty() {
+    match s.is_empty() || s == "mermaid" {
         true => Ok(s.to_string()),
         _ => match Utf8Path::new(s).extension() {
             Some(ext) if SUPPORTED_GRAPH_FILE_EXTENSIONS.contains(&ext) => Ok(s.to_string()),
@@ -713,14 +919,29 @@ pub struct ExecutionArgs {
     /// Override the filesystem cache directory.
     #[clap(long, value_parser = path_non_empty)]
     pub cache_dir: Option<Utf8PathBuf>,
+    /// Set the codec used to compress local cache artifacts. Remote cache
+    /// artifacts are always compressed with zstd. Existing artifacts are read
+    /// regardless of the codec they were written with.
+    #[clap(long, value_enum)]
+    pub cache_compression: Option<CacheCompression>,
     /// Limit the concurrency of task execution. Use 1 for serial (i.e.
     /// one-at-a-time) execution.
     #[clap(long)]
     pub concurrency: Option<String>,
     /// Continue execution even if a task exits with an error or non-zero
-    /// exit code. The default behavior is to bail
-    #[clap(long = "continue")]
-    pub continue_execution: bool,
+    /// exit code. The default behavior is to bail. Use
+    /// "dependencies-successful" to only skip the tasks that depend on a
+    /// failed task
+    #[clap(
+        long = "continue",
+        value_enum,
+        value_name = "CONTINUE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_value_t = ContinueMode::Never,
+        default_missing_value = "always"
+    )]
+    pub continue_execution: ContinueMode,
     /// Run turbo in single-package mode
     #[clap(long)]
     pub single_package: bool,
@@ -760,13 +981,21 @@ pub struct ExecutionArgs {
     pub output_logs: Option<OutputLogsMode>,
     /// Set type of task output order. Use "stream" to show
     /// output as soon as it is available. Use "grouped" to
-    /// show output when a command has finished execution. Use "auto" to let
-    /// turbo decide based on its own heuristics. (default auto)
+    /// show output when a command has finished execution. Use "json" to emit
+    /// task events as JSON lines. Use "auto" to let turbo decide based on its
+    /// own heuristics. (default auto)
     #[clap(long, value_enum)]
     pub log_order: Option<LogOrder>,
     /// Only executes the tasks specified, does not execute parent tasks.
-    #[clap(long)]
-    pub only: bool,
+    /// Use "direct-deps" to also execute the direct dependencies of the
+    /// specified tasks, but not their transitive dependencies.
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "tasks"
+    )]
+    pub only: Option<OnlyMode>,
     #[clap(long, hide = true)]
     pub pkg_inference_root: Option<String>,
     /// Ignore the local filesystem cache for all tasks. Only
@@ -774,12 +1003,13 @@ pub struct ExecutionArgs {
     #[clap(long, default_missing_value = "true")]
     pub remote_only: Option<Option<bool>>,
     /// Use "none" to remove prefixes from task logs. Use "task" to get task id
-    /// prefixing. Use "auto" to let turbo decide how to prefix the logs
-    /// based on the execution environment. In most cases this will be the same
-    /// as "task". Note that tasks running in parallel interleave their
-    /// logs, so removing prefixes can make it difficult to associate logs
-    /// with tasks. Use --log-order=grouped to prevent interleaving. (default
-    /// auto)
+    /// prefixing. Use "timestamp" to prefix each line with an ISO-8601
+    /// timestamp, or "task-timestamp" for both. Use "auto" to let turbo decide
+    /// how to prefix the logs based on the execution environment. In most
+    /// cases this will be the same as "task". Note that tasks running in
+    /// parallel interleave their logs, so removing prefixes can make it
+    /// difficult to associate logs with tasks. Use --log-order=grouped to
+    /// prevent interleaving. (default auto)
     #[clap(long, value_enum, default_value_t = LogPrefix::Auto)]
     pub log_prefix: LogPrefix,
     // NOTE: The following two are hidden because clap displays them in the help text incorrectly:
@@ -800,9 +1030,10 @@ impl ExecutionArgs {
         // default to false
         track_usage!(telemetry, self.framework_inference, |val: bool| !val);
 
-        track_usage!(telemetry, self.continue_execution, |val| val);
+        track_usage!(telemetry, self.continue_execution, |val| val
+            != ContinueMode::Never);
         track_usage!(telemetry, self.single_package, |val| val);
-        track_usage!(telemetry, self.only, |val| val);
+        track_usage!(telemetry, &self.only, Option::is_some);
         track_usage!(telemetry, self.remote_only().unwrap_or_default(), |val| val);
         track_usage!(telemetry, &self.cache_dir, Option::is_some);
         track_usage!(telemetry, &self.force, Option::is_some);
@@ -824,6 +1055,14 @@ impl ExecutionArgs {
             telemetry.track_arg_value("env-mode", env_mode, EventType::NonSensitive);
         }
 
+        if let Some(cache_compression) = self.cache_compression {
+            telemetry.track_arg_value(
+                "cache-compression",
+                cache_compression,
+                EventType::NonSensitive,
+            );
+        }
+
         if let Some(output_logs) = &self.output_logs {
             telemetry.track_arg_value("output-logs", output_logs, EventType::NonSensitive);
         }
@@ -832,6 +1071,14 @@ impl ExecutionArgs {
             telemetry.track_arg_value("log-order", log_order, EventType::NonSensitive);
         }
 
+        if let Some(only) = self.only {
+            telemetry.track_arg_value("only", only, EventType::NonSensitive);
+        }
+
+        if self.continue_execution != ContinueMode::Never {
+            telemetry.track_arg_value("continue", self.continue_execution, EventType::NonSensitive);
+        }
+
         if self.log_prefix != LogPrefix::default() {
             telemetry.track_arg_value("log-prefix", self.log_prefix, EventType::NonSensitive);
         }
@@ -853,18 +1100,43 @@ pub struct RunArgs {
     pub cache_workers: u32,
     #[clap(alias = "dry", long = "dry-run", num_args = 0..=1, default_missing_value = "text")]
     pub dry_run: Option<DryRunMode>,
+    /// Include a breakdown of the values that went into each task's hash in
+    /// task summaries, such as --dry=json output. Useful for debugging cache
+    /// misses.
+    #[clap(long)]
+    pub verbose_hashes: bool,
+    /// Print the values that went into the hash of the given task, e.g.
+    /// "web#build", and compare them with the last run summary that recorded
+    /// them. Useful for debugging cache misses.
+    #[clap(long, value_name = "TASK")]
+    pub why_hash: Option<String>,
     /// Generate a graph of the task execution and output to a file when a
     /// filename is specified (.svg, .png, .jpg, .pdf, .json,
-    /// .html, .mermaid, .dot). Outputs dot graph to stdout when if no filename
-    /// is provided
+    /// .html, .mermaid, .mmd, .dot). Outputs dot graph to stdout when if no
+    /// filename is provided, or a Mermaid graph when "mermaid" is provided
     #[clap(long, num_args = 0..=1, default_missing_value = "", value_parser = validate_graph_extension)]
     pub graph: Option<String>,
+    /// Print the resolved task graph, including each task's definition, as
+    /// JSON to stdout without running any tasks
+    #[clap(long, conflicts_with_all = ["graph", "dry_run"])]
+    pub graph_json: bool,
 
     /// Avoid saving task results to the cache. Useful for development/watch
     /// tasks.
     #[clap(long)]
     pub no_cache: bool,
 
+    /// Restore the outputs of cached tasks without executing tasks that
+    /// miss the cache. Useful for warming up the local cache from the remote
+    /// cache ahead of a build.
+    #[clap(long, conflicts_with_all = ["dry_run", "force"])]
+    pub only_restore: bool,
+
+    /// Don't replay the logs of tasks that hit the cache. The cache hit status
+    /// line is still shown.
+    #[clap(long)]
+    pub no_log_replay: bool,
+
     // clap does not have negation flags such as --daemon and --no-daemon
     // so we need to use a group to enforce that only one of them is set.
     // -----------------------
@@ -887,20 +1159,69 @@ pub struct RunArgs {
     /// All identifying data omitted from the profile.
     #[clap(long, value_parser=NonEmptyStringValueParser::new(), conflicts_with = "profile")]
     pub anon_profile: Option<String>,
+    /// Format of the --profile and --anon-profile output
+    #[clap(long, value_enum, default_value_t = ProfileFormat::Chrome)]
+    pub profile_format: ProfileFormat,
+    /// File to write a Chrome trace of task start and end times into.
+    /// Unlike --profile this only includes tasks, not turbo's internals.
+    #[clap(long, value_parser=NonEmptyStringValueParser::new())]
+    pub task_trace: Option<String>,
     /// Treat remote cache as read only
     #[clap(long, default_missing_value = "true")]
     pub remote_cache_read_only: Option<Option<bool>>,
-    /// Generate a summary of the turbo run
-    #[clap(long, default_missing_value = "true")]
-    pub summarize: Option<Option<bool>>,
+    /// Generate a summary of the turbo run, optionally written to the given
+    /// path relative to the repository root
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = NonEmptyStringValueParser::new()
+    )]
+    pub summarize: Option<Option<String>>,
 
     // Pass a string to enable posting Run Summaries to Vercel
     #[clap(long, hide = true)]
     pub experimental_space_id: Option<String>,
 
+    /// Load environment variables from a `.env` style file before running
+    /// tasks. Can be passed multiple times, later files take precedence.
+    #[clap(long, action = ArgAction::Append)]
+    pub env_file: Vec<String>,
+
+    /// Only run tasks in packages that changed since the given git ref.
+    /// Equivalent to `--filter "[<ref>]"` and can be combined with other
+    /// filters. Defaults to the merge base with the default branch. Requires
+    /// a git repository.
+    #[clap(
+        long,
+        value_name = "REF",
+        num_args = 0..=1,
+        require_equals = true,
+        conflicts_with = "affected"
+    )]
+    pub only_changed: Option<Option<String>>,
+
     /// Execute all tasks in parallel.
     #[clap(long)]
     pub parallel: bool,
+
+    /// Number of seconds to give running tasks to exit after being
+    /// interrupted when turbo stops early, before they are killed.
+    #[clap(long, value_name = "SECONDS", default_value_t = DEFAULT_KILL_TIMEOUT_SECS)]
+    pub kill_timeout: u64,
+
+    /// Warn about platform environment variables that tasks in loose env
+    /// mode use without declaring them in `env` or `passThroughEnv`. These
+    /// are the variables that would be missing in strict mode. Has no effect
+    /// when platform environment variable validation is disabled.
+    #[clap(long)]
+    pub warn_undeclared_env: bool,
+
+    /// Don't check tasks for platform environment variables that are
+    /// missing from their environment.
+    #[clap(long)]
+    pub no_platform_env_validation: bool,
 }
 
 impl Default for RunArgs {
@@ -908,16 +1229,28 @@ impl Default for RunArgs {
         Self {
             cache_workers: DEFAULT_NUM_WORKERS,
             dry_run: None,
+            verbose_hashes: false,
+            why_hash: None,
             graph: None,
+            graph_json: false,
             no_cache: false,
+            only_restore: false,
+            no_log_replay: false,
             daemon: false,
             no_daemon: false,
             profile: None,
             anon_profile: None,
+            profile_format: ProfileFormat::Chrome,
+            task_trace: None,
             remote_cache_read_only: None,
             summarize: None,
             experimental_space_id: None,
+            env_file: Vec::new(),
+            only_changed: None,
             parallel: false,
+            kill_timeout: DEFAULT_KILL_TIMEOUT_SECS,
+            warn_undeclared_env: false,
+            no_platform_env_validation: false,
         }
     }
 }
@@ -950,16 +1283,31 @@ impl RunArgs {
     }
 
     pub fn summarize(&self) -> Option<bool> {
-        let summarize = self.summarize?;
-        Some(summarize.unwrap_or(true))
+        let summarize = self.summarize.as_ref()?;
+        Some(summarize.as_deref() != Some("false"))
+    }
+
+    /// Path to write the run summary to if one was passed to `--summarize`
+    pub fn summarize_path(&self) -> Option<&str> {
+        match self.summarize.as_ref()?.as_deref()? {
+            "true" | "false" => None,
+            path => Some(path),
+        }
     }
 
     pub fn track(&self, telemetry: &CommandEventBuilder) {
         // default to true
         track_usage!(telemetry, self.no_cache, |val| val);
+        track_usage!(telemetry, self.only_restore, |val| val);
+        track_usage!(telemetry, self.no_log_replay, |val| val);
         track_usage!(telemetry, self.daemon, |val| val);
         track_usage!(telemetry, self.no_daemon, |val| val);
         track_usage!(telemetry, self.parallel, |val| val);
+        track_usage!(telemetry, self.verbose_hashes, |val| val);
+        track_usage!(telemetry, &self.why_hash, Option::is_some);
+        track_usage!(telemetry, self.graph_json, |val| val);
+        track_usage!(telemetry, self.warn_undeclared_env, |val| val);
+        track_usage!(telemetry, self.no_platform_env_validation, |val| val);
         track_usage!(
             telemetry,
             self.remote_cache_read_only().unwrap_or_default(),
@@ -969,8 +1317,10 @@ impl RunArgs {
         // default to None
         track_usage!(telemetry, &self.profile, Option::is_some);
         track_usage!(telemetry, &self.anon_profile, Option::is_some);
+        track_usage!(telemetry, &self.task_trace, Option::is_some);
         track_usage!(telemetry, &self.summarize, Option::is_some);
         track_usage!(telemetry, &self.experimental_space_id, Option::is_some);
+        track_usage!(telemetry, &self.only_changed, Option::is_some);
 
         // track values
         if let Some(dry_run) = &self.dry_run {
@@ -981,6 +1331,26 @@ impl RunArgs {
             telemetry.track_arg_value("cache-workers", self.cache_workers, EventType::NonSensitive);
         }
 
+        if self.kill_timeout != DEFAULT_KILL_TIMEOUT_SECS {
+            telemetry.track_arg_value("kill-timeout", self.kill_timeout, EventType::NonSensitive);
+        }
+
+        if !self.env_file.is_empty() {
+            telemetry.track_arg_value(
+                "env-file:length",
+                self.env_file.len(),
+                EventType::NonSensitive,
+            );
+        }
+
+        if self.profile_format != ProfileFormat::Chrome {
+            telemetry.track_arg_value(
+                "profile-format",
+                self.profile_format,
+                EventType::NonSensitive,
+            );
+        }
+
         if let Some(graph) = &self.graph {
             // track the extension used only
             let extension = Utf8Path::new(graph).extension().unwrap_or("stdout");
@@ -997,6 +1367,10 @@ pub enum LogPrefix {
     None,
     #[serde(rename = "task")]
     Task,
+    #[serde(rename = "timestamp")]
+    Timestamp,
+    #[serde(rename = "task-timestamp")]
+    TaskTimestamp,
 }
 
 impl Default for LogPrefix {
@@ -1011,6 +1385,8 @@ impl Display for LogPrefix {
             LogPrefix::Auto => write!(f, "auto"),
             LogPrefix::None => write!(f, "none"),
             LogPrefix::Task => write!(f, "task"),
+            LogPrefix::Timestamp => write!(f, "timestamp"),
+            LogPrefix::TaskTimestamp => write!(f, "task-timestamp"),
         }
     }
 }
@@ -1221,15 +1597,19 @@ pub async fn run(
                 Ok(1)
             }
         }
-        Command::Config => {
+        Command::Config { command } => {
             let base = CommandBase::new(cli_args.clone(), repo_root, version, color_config);
-            config::run(base).await?;
+            match command {
+                Some(ConfigCommand::Set { key, value }) => config::set(&base, *key, *value)?,
+                None => config::run(base).await?,
+            }
             Ok(0)
         }
         Command::Ls {
             affected,
             filter,
             packages,
+            graph,
             output,
         } => {
             warn!("ls command is experimental and may change in the future");
@@ -1237,12 +1617,13 @@ pub async fn run(
 
             event.track_call();
             let affected = *affected;
+            let graph = *graph;
             let output = *output;
             let filter = filter.clone();
             let packages = packages.clone();
             let base = CommandBase::new(cli_args, repo_root, version, color_config);
 
-            ls::run(base, packages, event, filter, affected, output).await?;
+            ls::run(base, packages, event, filter, affected, graph, output).await?;
 
             Ok(0)
         }
@@ -1334,7 +1715,8 @@ pub async fn run(
 
             if let Some((file_path, include_args)) = run_args.profile_file_and_include_args() {
                 // TODO: Do we want to handle the result / error?
-                let _ = logger.enable_chrome_tracing(file_path, include_args);
+                let _ =
+                    logger.enable_chrome_tracing(file_path, include_args, run_args.profile_format);
             }
 
             run_args.track(&event);
@@ -1408,7 +1790,7 @@ pub async fn run(
 
 #[cfg(test)]
 mod test {
-    use std::assert_matches::assert_matches;
+    use std::{assert_matches::assert_matches, ffi::OsString};
 
     use camino::Utf8PathBuf;
     use clap::Parser;
@@ -1472,7 +1854,10 @@ mod test {
         }
     }
 
-    use crate::cli::{Args, Command, DryRunMode, EnvMode, LogOrder, LogPrefix, OutputLogsMode};
+    use crate::cli::{
+        Args, Command, ConfigCommand, ConfigKey, ContinueMode, DryRunMode, EnvMode, LogOrder,
+        LogPrefix, OnlyMode, OutputLogsMode,
+    };
 
     #[test_case::test_case(
         &["turbo", "run", "build"],
@@ -1594,6 +1979,36 @@ mod test {
 		} ;
         "env_mode: specified strict"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--cache-compression", "gzip"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    cache_compression: Some(CacheCompression::Gzip),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+		} ;
+        "cache_compression: gzip"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--cache-compression", "none"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    cache_compression: Some(CacheCompression::None),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+		} ;
+        "cache_compression: none"
+	)]
     #[test_case::test_case(
 		&["turbo", "run", "build", "lint", "test"],
         Args {
@@ -1641,37 +2056,41 @@ mod test {
         "cache workers"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "--concurrency", "20"],
+		&["turbo", "run", "build", "--kill-timeout", "30"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
-                    tasks: vec!["build".to_string()],
-                    concurrency: Some("20".to_string()),
+                    tasks: vec ! ["build".to_string()],
                     ..get_default_execution_args()
                 }),
-                run_args: Box::new(get_default_run_args())
+                run_args: Box::new(RunArgs {
+                    kill_timeout: 30,
+                    ..get_default_run_args()
+                })
             }),
             ..Args::default()
         } ;
-        "concurrency"
+        "kill timeout"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "--continue"],
+		&["turbo", "run", "build", "--warn-undeclared-env"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
                     tasks: vec!["build".to_string()],
-                    continue_execution: true,
                     ..get_default_execution_args()
                 }),
-                run_args: Box::new(get_default_run_args())
+                run_args: Box::new(RunArgs {
+                    warn_undeclared_env: true,
+                    ..get_default_run_args()
+                })
             }),
             ..Args::default()
         } ;
-        "continue flag"
+        "warn undeclared env"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "--dry-run"],
+		&["turbo", "run", "build", "--no-platform-env-validation"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
@@ -1679,16 +2098,16 @@ mod test {
                     ..get_default_execution_args()
                 }),
                 run_args: Box::new(RunArgs {
-                    dry_run: Some(DryRunMode::Text),
+                    no_platform_env_validation: true,
                     ..get_default_run_args()
                 })
             }),
             ..Args::default()
         } ;
-        "dry run"
+        "no platform env validation"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "--dry-run", "json"],
+		&["turbo", "run", "build", "--only-restore"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
@@ -1696,70 +2115,299 @@ mod test {
                     ..get_default_execution_args()
                 }),
                 run_args: Box::new(RunArgs {
-                    dry_run: Some(DryRunMode::Json),
+                    only_restore: true,
                     ..get_default_run_args()
                 })
             }),
             ..Args::default()
         } ;
-        "dry run json"
+        "only restore"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "--filter", "water", "--filter", "earth", "--filter", "fire", "--filter", "air"],
+		&["turbo", "run", "build", "--no-log-replay"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
                     tasks: vec!["build".to_string()],
-                    filter: vec![
-                        "water".to_string(),
-                        "earth".to_string(),
-                        "fire".to_string(),
-                        "air".to_string()
-                    ],
                     ..get_default_execution_args()
                 }),
-                run_args: Box::new(get_default_run_args())
+                run_args: Box::new(RunArgs {
+                    no_log_replay: true,
+                    ..get_default_run_args()
+                })
             }),
             ..Args::default()
         } ;
-        "multiple filters"
+        "no log replay"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "-F", "water", "-F", "earth", "-F", "fire", "-F", "air"],
+		&["turbo", "run", "build", "--summarize=summary.json"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
-                    tasks: vec!["build".to_string()],
-                    filter: vec![
-                        "water".to_string(),
-                        "earth".to_string(),
-                        "fire".to_string(),
-                        "air".to_string()
-                    ],
+                    tasks: vec ! ["build".to_string()],
                     ..get_default_execution_args()
                 }),
-                run_args: Box::new(get_default_run_args())
+                run_args: Box::new(RunArgs {
+                    summarize: Some(Some("summary.json".to_string())),
+                    ..get_default_run_args()
+                })
             }),
             ..Args::default()
         } ;
-        "multiple filters short"
+        "summarize path"
 	)]
     #[test_case::test_case(
-		&["turbo", "run", "build", "--filter", "water", "-F", "earth", "--filter", "fire", "-F", "air"],
+		&["turbo", "run", "--summarize", "build"],
         Args {
             command: Some(Command::Run {
                 execution_args: Box::new(ExecutionArgs {
                     tasks: vec!["build".to_string()],
-                    filter: vec![
-                        "water".to_string(),
-                        "earth".to_string(),
-                        "fire".to_string(),
-                        "air".to_string()
-                    ],
                     ..get_default_execution_args()
                 }),
-                run_args: Box::new(get_default_run_args())
-            }),
+                run_args: Box::new(RunArgs {
+                    summarize: Some(Some("true".to_string())),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "summarize before task"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--task-trace", "trace.json"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec ! ["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    task_trace: Some("trace.json".to_string()),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "task trace"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--concurrency", "20"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    concurrency: Some("20".to_string()),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "concurrency"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--continue"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    continue_execution: ContinueMode::Always,
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "continue flag"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--continue=always"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    continue_execution: ContinueMode::Always,
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "continue always"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--continue=dependencies-successful"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    continue_execution: ContinueMode::DependenciesSuccessful,
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "continue dependencies successful"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "--continue", "build"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    continue_execution: ContinueMode::Always,
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "continue flag before task"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--dry-run"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    dry_run: Some(DryRunMode::Text),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "dry run"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--dry=ndjson"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    dry_run: Some(DryRunMode::Ndjson),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "dry run ndjson"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--dry-run", "json"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    dry_run: Some(DryRunMode::Json),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "dry run json"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--dry-run", "json", "--verbose-hashes"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    dry_run: Some(DryRunMode::Json),
+                    verbose_hashes: true,
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "dry run json verbose hashes"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--why-hash", "web#build"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    why_hash: Some("web#build".to_string()),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "why hash"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--filter", "water", "--filter", "earth", "--filter", "fire", "--filter", "air"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    filter: vec![
+                        "water".to_string(),
+                        "earth".to_string(),
+                        "fire".to_string(),
+                        "air".to_string()
+                    ],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "multiple filters"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "-F", "water", "-F", "earth", "-F", "fire", "-F", "air"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    filter: vec![
+                        "water".to_string(),
+                        "earth".to_string(),
+                        "fire".to_string(),
+                        "air".to_string()
+                    ],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "multiple filters short"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--filter", "water", "-F", "earth", "--filter", "fire", "-F", "air"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    filter: vec![
+                        "water".to_string(),
+                        "earth".to_string(),
+                        "fire".to_string(),
+                        "air".to_string()
+                    ],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
             ..Args::default()
         } ;
         "multiple filters short and long"
@@ -1809,6 +2457,23 @@ mod test {
         } ;
         "multiple global deps"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--env-file", ".env", "--env-file", ".env.local"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    env_file: vec![".env".to_string(), ".env.local".to_string()],
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "multiple env files"
+	)]
     #[test_case::test_case(
 		&["turbo", "run", "build", "--graph"],
         Args {
@@ -1843,6 +2508,40 @@ mod test {
         } ;
         "graph with output"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--graph", "mermaid"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    graph: Some("mermaid".to_string()),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "graph mermaid"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--graph-json"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    graph_json: true,
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "graph json"
+	)]
     #[test_case::test_case(
 		&["turbo", "run", "build", "--no-cache"],
         Args {
@@ -1939,6 +2638,21 @@ mod test {
         } ;
         "output logs hash only"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--output-logs", "errors-only-with-summary"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    output_logs: Some(OutputLogsMode::ErrorsOnlyWithSummary),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "output logs errors only with summary"
+	)]
     #[test_case::test_case(
 		&["turbo", "run", "build", "--log-order", "stream"],
         Args {
@@ -1969,6 +2683,103 @@ mod test {
         };
         "log order grouped"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--log-order", "json"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    log_order: Some(LogOrder::Json),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        };
+        "log order json"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "--only", "build"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    only: Some(OnlyMode::Tasks),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        };
+        "only"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--only=direct-deps"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    only: Some(OnlyMode::DirectDeps),
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        };
+        "only direct deps"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--only-changed"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    only_changed: Some(None),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        };
+        "only changed"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--only-changed=origin/main", "--filter", "web"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    filter: vec!["web".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    only_changed: Some(Some("origin/main".to_string())),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        };
+        "only changed with ref"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "--only-changed", "build"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                    only_changed: Some(None),
+                    ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        };
+        "only changed before task"
+	)]
     #[test_case::test_case(
 		&["turbo", "run", "build", "--log-prefix", "auto"],
         Args {
@@ -2014,6 +2825,36 @@ mod test {
         } ;
         "log prefix task"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--log-prefix", "timestamp"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    log_prefix: LogPrefix::Timestamp,
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "log prefix timestamp"
+	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--log-prefix", "task-timestamp"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    log_prefix: LogPrefix::TaskTimestamp,
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(get_default_run_args())
+            }),
+            ..Args::default()
+        } ;
+        "log prefix task timestamp"
+	)]
     #[test_case::test_case(
 		&["turbo", "run", "build"],
         Args {
@@ -2062,6 +2903,24 @@ mod test {
         } ;
         "profile"
 	)]
+    #[test_case::test_case(
+		&["turbo", "run", "build", "--profile", "profile_out", "--profile-format", "perfetto"],
+        Args {
+            command: Some(Command::Run {
+                execution_args: Box::new(ExecutionArgs {
+                    tasks: vec!["build".to_string()],
+                    ..get_default_execution_args()
+                }),
+                run_args: Box::new(RunArgs {
+                  profile: Some("profile_out".to_string()),
+                  profile_format: ProfileFormat::Perfetto,
+                  ..get_default_run_args()
+                })
+            }),
+            ..Args::default()
+        } ;
+        "profile with perfetto format"
+	)]
     // remote-only flag tests
     #[test_case::test_case(
 		&["turbo", "run", "build"],
@@ -2207,6 +3066,11 @@ mod test {
         "unexpected argument '--no-deps' found" ;
         "no-deps without filter or scope"
     )]
+    #[test_case::test_case(
+        &["turbo", "run", "build", "--graph-json", "--graph"],
+        "cannot be used with '--graph" ;
+        "graph-json with graph"
+    )]
     fn test_parse_run_failures(args: &[&str], expected: &str) {
         assert_matches!(
             Args::try_parse_from(args),
@@ -2214,6 +3078,34 @@ mod test {
         );
     }
 
+    #[test_case::test_case(
+        &["turbo", "daemon", "logs"],
+        DaemonCommand::Logs { follow: false, lines: None } ;
+        "daemon logs"
+    )]
+    #[test_case::test_case(
+        &["turbo", "daemon", "logs", "--follow", "--lines", "20"],
+        DaemonCommand::Logs { follow: true, lines: Some(20) } ;
+        "daemon logs follow with lines"
+    )]
+    #[test_case::test_case(
+        &["turbo", "daemon", "logs", "-f", "-n", "5"],
+        DaemonCommand::Logs { follow: true, lines: Some(5) } ;
+        "daemon logs short flags"
+    )]
+    fn test_parse_daemon(args: &[&str], expected: DaemonCommand) {
+        assert_eq!(
+            Args::try_parse_from(args).unwrap(),
+            Args {
+                command: Some(Command::Daemon {
+                    idle_time: "4h0m0s".to_string(),
+                    command: Some(expected),
+                }),
+                ..Args::default()
+            }
+        );
+    }
+
     #[test]
     fn test_parse_bin() {
         assert_eq!(
@@ -2304,6 +3196,34 @@ mod test {
         .test();
     }
 
+    #[test]
+    fn test_parse_config() {
+        assert_eq!(
+            Args::try_parse_from(["turbo", "config"]).unwrap(),
+            Args {
+                command: Some(Command::Config { command: None }),
+                ..Args::default()
+            }
+        );
+
+        assert_eq!(
+            Args::try_parse_from(["turbo", "config", "set", "updateNotifier", "false"]).unwrap(),
+            Args {
+                command: Some(Command::Config {
+                    command: Some(ConfigCommand::Set {
+                        key: ConfigKey::UpdateNotifier,
+                        value: false,
+                    })
+                }),
+                ..Args::default()
+            }
+        );
+
+        assert!(
+            Args::try_parse_from(["turbo", "config", "set", "updateNotifier", "nope"]).is_err()
+        );
+    }
+
     #[test]
     fn test_parse_unlink() {
         assert_eq!(
@@ -2632,6 +3552,7 @@ mod test {
         assert!(LogOrder::Auto.compatible_with_tui());
         assert!(!LogOrder::Stream.compatible_with_tui());
         assert!(!LogOrder::Grouped.compatible_with_tui());
+        assert!(!LogOrder::Json.compatible_with_tui());
     }
 
     #[test]
@@ -2662,4 +3583,101 @@ mod test {
         assert!(Args::try_parse_from(["turbo", "build", "--filter", "foo", "--affected"]).is_err(),);
         assert!(Args::try_parse_from(["turbo", "ls", "--filter", "foo", "--affected"]).is_err(),);
     }
+
+    #[test_case::test_case(&["turbo", "run", "build", "--single-package"], true, &[] ; "before separator")]
+    #[test_case::test_case(&["turbo", "run", "build", "--", "--single-package"], false, &["--single-package"] ; "after separator")]
+    #[test_case::test_case(
+        &["turbo", "run", "build", "--single-package", "--", "--single-package"],
+        true,
+        &["--single-package"] ;
+        "both sides of separator"
+    )]
+    #[test_case::test_case(&["turbo", "build", "--single-package"], true, &[] ; "run shorthand")]
+    fn test_try_new_single_package(
+        args: &[&str],
+        single_package: bool,
+        pass_through_args: &[&str],
+    ) {
+        let args = Args::try_new(args.iter().map(OsString::from)).unwrap();
+        let execution_args = match &args.command {
+            Some(Command::Run { execution_args, .. }) => execution_args.as_ref(),
+            _ => args.execution_args.as_ref().unwrap(),
+        };
+        assert_eq!(execution_args.single_package, single_package);
+        assert_eq!(execution_args.pass_through_args, pass_through_args);
+    }
+
+    #[test_case::test_case(&["turbo", "run", "build", "--summarize"], Some(true), None, &["build"] ; "bare")]
+    #[test_case::test_case(&["turbo", "run", "--summarize", "build"], Some(true), None, &["build"] ; "bare before task")]
+    #[test_case::test_case(&["turbo", "run", "build", "--summarize=false"], Some(false), None, &["build"] ; "equals false")]
+    #[test_case::test_case(&["turbo", "run", "build", "--summarize", "false"], Some(false), None, &["build"] ; "separate false")]
+    #[test_case::test_case(&["turbo", "run", "build", "--summarize", "true"], Some(true), None, &["build"] ; "separate true")]
+    #[test_case::test_case(
+        &["turbo", "run", "build", "--summarize=out/summary.json"],
+        Some(true),
+        Some("out/summary.json"),
+        &["build"] ;
+        "path"
+    )]
+    #[test_case::test_case(&["turbo", "run", "build", "--", "--summarize", "false"], None, None, &["build"] ; "after separator")]
+    fn test_try_new_summarize(
+        args: &[&str],
+        summarize: Option<bool>,
+        summarize_path: Option<&str>,
+        tasks: &[&str],
+    ) {
+        let args = Args::try_new(args.iter().map(OsString::from)).unwrap();
+        let Some(Command::Run {
+            run_args,
+            execution_args,
+        }) = args.command
+        else {
+            panic!("expected run command");
+        };
+        assert_eq!(run_args.summarize(), summarize);
+        assert_eq!(run_args.summarize_path(), summarize_path);
+        assert_eq!(execution_args.tasks, tasks);
+    }
+
+    #[test]
+    fn test_try_new_returns_errors() {
+        assert!(Args::try_new(["turbo", "run", "--not-a-flag"].map(OsString::from)).is_err());
+    }
+
+    #[test]
+    fn test_try_new_response_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let filters = Utf8Path::from_path(tmp.path()).unwrap().join("filters.txt");
+        std::fs::write(
+            &filters,
+            "# packages to build\n--filter=web\n\n--filter\n  docs  \n--filter=@repo/ui\n",
+        )
+        .unwrap();
+
+        let args = Args::try_new(
+            [
+                "turbo",
+                "run",
+                "build",
+                &format!("@{filters}"),
+                "--filter=@repo/utils",
+                "--",
+                &format!("@{filters}"),
+            ]
+            .map(OsString::from),
+        )
+        .unwrap();
+
+        let Some(Command::Run { execution_args, .. }) = args.command else {
+            panic!("expected run command");
+        };
+        assert_eq!(
+            execution_args.filter,
+            vec!["web", "docs", "@repo/ui", "@repo/utils"]
+        );
+        assert_eq!(
+            execution_args.pass_through_args,
+            vec![format!("@{filters}")]
+        );
+    }
 }
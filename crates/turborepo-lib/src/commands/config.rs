@@ -4,7 +4,14 @@ use turborepo_repository::{
     package_graph::PackageGraph, package_json::PackageJson, package_manager::PackageManager,
 };
 
-use crate::{cli, cli::EnvMode, commands::CommandBase, turbo_json::UIMode};
+use crate::{
+    cli,
+    cli::{CacheCompression, ConfigKey, EnvMode},
+    commands::CommandBase,
+    config,
+    rewrite_json::set_path,
+    turbo_json::UIMode,
+};
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +33,7 @@ struct ConfigOutput<'a> {
     scm_base: Option<&'a str>,
     scm_head: Option<&'a str>,
     cache_dir: &'a Utf8Path,
+    cache_compression: CacheCompression,
 }
 
 pub async fn run(base: CommandBase) -> Result<(), cli::Error> {
@@ -58,7 +66,70 @@ pub async fn run(base: CommandBase) -> Result<(), cli::Error> {
             scm_base: config.scm_base(),
             scm_head: config.scm_head(),
             cache_dir: config.cache_dir(),
+            cache_compression: config.cache_compression(),
         })?
     );
     Ok(())
 }
+
+/// Writes a value to the global config so it applies to every invocation
+pub fn set(base: &CommandBase, key: ConfigKey, value: bool) -> Result<(), cli::Error> {
+    let global_config_path = base.global_config_path()?;
+    let before = global_config_path
+        .read_existing_to_string()
+        .map_err(|e| config::Error::FailedToReadConfig {
+            config_path: global_config_path.clone(),
+            error: e,
+        })?
+        .unwrap_or_else(|| String::from("{}"));
+
+    let key = match key {
+        ConfigKey::UpdateNotifier => "updateNotifier",
+    };
+    let after = set_path(&before, &[key], &value.to_string())?;
+
+    config::write_to_disk(&global_config_path, &after)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::TempDir;
+    use turbopath::AbsoluteSystemPathBuf;
+    use turborepo_ui::ColorConfig;
+
+    use super::*;
+    use crate::{cli::Args, config::TurborepoConfigBuilder};
+
+    #[test]
+    fn test_set_update_notifier() {
+        let tmp_dir = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        let global_config_path = repo_root.join_components(&["global", "config.json"]);
+        global_config_path.ensure_dir().unwrap();
+        global_config_path
+            .create_with_contents(r#"{ "token": "hello" }"#)
+            .unwrap();
+
+        let base = CommandBase::new(Args::default(), repo_root, "", ColorConfig::new(false))
+            .with_override_global_config_path(global_config_path.clone());
+        assert!(TurborepoConfigBuilder::new(&base)
+            .build()
+            .unwrap()
+            .update_notifier());
+
+        set(&base, ConfigKey::UpdateNotifier, false).unwrap();
+
+        let contents: serde_json::Value =
+            serde_json::from_str(&global_config_path.read_to_string().unwrap()).unwrap();
+        assert_eq!(
+            contents,
+            serde_json::json!({ "token": "hello", "updateNotifier": false })
+        );
+
+        let config = TurborepoConfigBuilder::new(&base).build().unwrap();
+        assert!(!config.update_notifier());
+        assert_eq!(config.token(), Some("hello"));
+    }
+}
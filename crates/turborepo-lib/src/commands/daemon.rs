@@ -1,15 +1,16 @@
-use std::time::Duration;
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    time::Duration,
+};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use futures::FutureExt;
 use pidlock::PidlockError::AlreadyOwned;
-use serde_json::json;
 use time::{format_description, OffsetDateTime};
 use tokio::signal::ctrl_c;
 use tracing::{trace, warn};
 use turbopath::AbsoluteSystemPath;
 use turborepo_ui::{color, BOLD_GREEN, BOLD_RED, GREY};
-use which::which;
 
 use super::CommandBase;
 use crate::{
@@ -18,16 +19,20 @@ use crate::{
         endpoint::SocketOpenError, CloseReason, DaemonConnector, DaemonConnectorError, DaemonError,
         Paths,
     },
+    get_version,
     tracing::TurboSubscriber,
 };
 
 const DAEMON_NOT_RUNNING_MESSAGE: &str =
     "daemon is not running, run `turbo daemon start` to start it";
 
+/// How many lines of the log file `turbo daemon logs` shows by default
+const DEFAULT_LOG_LINES: usize = 10;
+
 /// Runs the daemon command.
 pub async fn daemon_client(command: &DaemonCommand, base: &CommandBase) -> Result<(), DaemonError> {
     let (can_start_server, can_kill_server) = match command {
-        DaemonCommand::Status { .. } | DaemonCommand::Logs => (false, false),
+        DaemonCommand::Status { .. } | DaemonCommand::Logs { .. } => (false, false),
         DaemonCommand::Stop => (false, true),
         DaemonCommand::Restart | DaemonCommand::Start => (true, true),
         DaemonCommand::Clean { .. } => (false, true),
@@ -85,10 +90,12 @@ pub async fn daemon_client(command: &DaemonCommand, base: &CommandBase) -> Resul
             );
         }
         DaemonCommand::Status { json } => {
+            let paths = connector.paths.clone();
             let mut client = match connector.connect().await {
                 Ok(status) => status,
                 Err(DaemonConnectorError::NotRunning) if *json => {
-                    println!("{}", json!({ "error": DAEMON_NOT_RUNNING_MESSAGE }));
+                    let status = DaemonStatus::not_running(&paths);
+                    println!("{}", serde_json::to_string_pretty(&status)?);
                     return Ok(());
                 }
                 Err(DaemonConnectorError::NotRunning) => {
@@ -106,11 +113,29 @@ pub async fn daemon_client(command: &DaemonCommand, base: &CommandBase) -> Resul
             let status = client.status().await?;
             let log_file = log_filename(&status.log_file)?;
             let paths = client.paths();
+            // older daemons don't report their pid, so fall back to the pid file
+            let pid = match status.pid {
+                0 => pidlock::Pidlock::new(paths.pid_file.clone().into())
+                    .get_owner()
+                    .ok()
+                    .flatten(),
+                pid => Some(pid),
+            };
+            // older daemons don't report their version, so assume it matches ours
+            let version = match status.version.as_str() {
+                "" => get_version().to_owned(),
+                version => version.to_owned(),
+            };
             let status = DaemonStatus {
-                uptime_ms: status.uptime_msec,
-                log_file: log_file.into(),
-                pid_file: paths.pid_file.to_owned(),
-                sock_file: paths.sock_file.to_owned(),
+                running: true,
+                error: None,
+                pid,
+                uptime_ms: Some(status.uptime_msec),
+                log_file: Some(log_file.into()),
+                pid_file: paths.pid_file.as_path().to_owned(),
+                sock_file: paths.sock_file.as_path().to_owned(),
+                version,
+                hashed_file_count: status.hashed_file_count,
             };
 
             if *json {
@@ -120,19 +145,26 @@ pub async fn daemon_client(command: &DaemonCommand, base: &CommandBase) -> Resul
                     "{} daemon is running",
                     color!(base.color_config, BOLD_GREEN, "✓")
                 );
-                println!(
-                    "log file: {}",
-                    color!(base.color_config, GREY, "{}", status.log_file)
-                );
-                println!(
-                    "uptime: {}",
-                    color!(
-                        base.color_config,
-                        GREY,
-                        "{}s",
-                        humantime::format_duration(Duration::from_millis(status.uptime_ms))
-                    )
-                );
+                if let Some(pid) = status.pid {
+                    println!("pid: {}", color!(base.color_config, GREY, "{}", pid));
+                }
+                if let Some(log_file) = &status.log_file {
+                    println!(
+                        "log file: {}",
+                        color!(base.color_config, GREY, "{}", log_file)
+                    );
+                }
+                if let Some(uptime_ms) = status.uptime_ms {
+                    println!(
+                        "uptime: {}",
+                        color!(
+                            base.color_config,
+                            GREY,
+                            "{}s",
+                            humantime::format_duration(Duration::from_millis(uptime_ms))
+                        )
+                    );
+                }
                 println!(
                     "pid file: {}",
                     color!(base.color_config, GREY, "{}", status.pid_file)
@@ -141,22 +173,31 @@ pub async fn daemon_client(command: &DaemonCommand, base: &CommandBase) -> Resul
                     "socket file: {}",
                     color!(base.color_config, GREY, "{}", status.sock_file)
                 );
+                println!(
+                    "version: {}",
+                    color!(base.color_config, GREY, "{}", status.version)
+                );
             }
         }
-        DaemonCommand::Logs => {
+        DaemonCommand::Logs { follow, lines } => {
             let log_file = if let Ok(log_file) = get_log_file_from_daemon(connector).await {
                 log_file
             } else {
                 get_log_file_from_folder(base).await?
             };
+            let log_file = Utf8Path::new(&log_file);
 
-            let tail = which("tail").map_err(|_| DaemonError::TailNotInstalled)?;
-
-            std::process::Command::new(tail)
-                .arg("-f")
-                .arg(log_file)
-                .status()
-                .expect("failed to execute tail");
+            let offset = tail_log_file(
+                log_file,
+                lines.unwrap_or(DEFAULT_LOG_LINES),
+                io::stdout().lock(),
+            )
+            .map_err(DaemonError::ReadLogFile)?;
+            if *follow {
+                follow_log_file(log_file, offset, io::stdout())
+                    .await
+                    .map_err(DaemonError::ReadLogFile)?;
+            }
         }
         DaemonCommand::Clean {
             clean_logs: should_clean_logs,
@@ -217,6 +258,54 @@ async fn get_log_file_from_folder(base: &CommandBase) -> Result<String, DaemonEr
         .to_string())
 }
 
+/// Writes the last `lines` lines of the log file to `writer`. Returns the size
+/// of the file so that following the log can start from where this left off.
+fn tail_log_file(log_file: &Utf8Path, lines: usize, mut writer: impl Write) -> io::Result<u64> {
+    let contents = std::fs::read(log_file)?;
+    let start = match lines {
+        0 => contents.len(),
+        lines => contents
+            .strip_suffix(b"\n")
+            .unwrap_or(&contents)
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, byte)| **byte == b'\n')
+            .nth(lines - 1)
+            .map_or(0, |(index, _)| index + 1),
+    };
+    writer.write_all(&contents[start..])?;
+    writer.flush()?;
+
+    Ok(contents.len() as u64)
+}
+
+/// Polls the log file for new content and writes it to `writer`, similar to
+/// `tail -f`. Runs until the process is stopped.
+async fn follow_log_file(
+    log_file: &Utf8Path,
+    mut offset: u64,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let mut file = std::fs::File::open(log_file)?;
+    loop {
+        let len = file.metadata()?.len();
+        // The log file was truncated, start over from the beginning
+        if len < offset {
+            offset = 0;
+        }
+        if len > offset {
+            file.seek(SeekFrom::Start(offset))?;
+            let mut buffer = Vec::new();
+            (&mut file).take(len - offset).read_to_end(&mut buffer)?;
+            writer.write_all(&buffer)?;
+            writer.flush()?;
+            offset = len;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
 fn clean(pid_file: &AbsoluteSystemPath, sock_file: &AbsoluteSystemPath) -> Result<(), DaemonError> {
     // remove pid and sock files
     let mut success = true;
@@ -321,12 +410,139 @@ pub async fn daemon_server(
     Ok(())
 }
 
-#[derive(serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct DaemonStatus {
-    pub uptime_ms: u64,
+    pub running: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub pid: Option<u32>,
+    pub uptime_ms: Option<u64>,
     // this comes from the daemon server, so we trust that
     // it is correct
-    pub log_file: Utf8PathBuf,
-    pub pid_file: turbopath::AbsoluteSystemPathBuf,
-    pub sock_file: turbopath::AbsoluteSystemPathBuf,
+    pub log_file: Option<Utf8PathBuf>,
+    pub pid_file: Utf8PathBuf,
+    pub sock_file: Utf8PathBuf,
+    pub version: String,
+    pub hashed_file_count: Option<u64>,
+}
+
+impl DaemonStatus {
+    fn not_running(paths: &Paths) -> Self {
+        Self {
+            running: false,
+            error: Some(DAEMON_NOT_RUNNING_MESSAGE.to_owned()),
+            pid: None,
+            uptime_ms: None,
+            log_file: None,
+            pid_file: paths.pid_file.as_path().to_owned(),
+            sock_file: paths.sock_file.as_path().to_owned(),
+            version: get_version().to_owned(),
+            hashed_file_count: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::TempDir;
+    use turbopath::AbsoluteSystemPathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_tail_log_file() {
+        let tmp = TempDir::new().unwrap();
+        let log_file = Utf8PathBuf::try_from(tmp.path().join("turbod.log")).unwrap();
+        std::fs::write(&log_file, "one\ntwo\nthree\nfour\n").unwrap();
+
+        let mut output = Vec::new();
+        let offset = tail_log_file(&log_file, 2, &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "three\nfour\n");
+        assert_eq!(offset, 19);
+
+        let mut output = Vec::new();
+        tail_log_file(&log_file, 10, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "one\ntwo\nthree\nfour\n"
+        );
+
+        let mut output = Vec::new();
+        tail_log_file(&log_file, 0, &mut output).unwrap();
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_daemon_status_json() {
+        let tmp = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let paths = Paths::from_repo_root(&repo_root);
+
+        let status = serde_json::to_value(DaemonStatus::not_running(&paths)).unwrap();
+        let mut keys = status.as_object().unwrap().keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "error",
+                "hashed_file_count",
+                "log_file",
+                "pid",
+                "pid_file",
+                "running",
+                "sock_file",
+                "uptime_ms",
+                "version"
+            ]
+        );
+        assert_eq!(status["running"], false);
+        assert_eq!(status["pid"], serde_json::Value::Null);
+
+        let status = serde_json::to_value(DaemonStatus {
+            running: true,
+            error: None,
+            pid: Some(1234),
+            uptime_ms: Some(1000),
+            log_file: Some("turbo.log".into()),
+            ..DaemonStatus::not_running(&paths)
+        })
+        .unwrap();
+        assert!(status.get("error").is_none());
+        assert_eq!(status["running"], true);
+        assert_eq!(status["pid"], 1234);
+        assert_eq!(status["uptime_ms"], 1000);
+        assert_eq!(status["version"], get_version());
+    }
+
+    #[test]
+    fn test_daemon_status_json_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let paths = Paths::from_repo_root(&repo_root);
+
+        let json = serde_json::to_string_pretty(&DaemonStatus {
+            running: true,
+            error: None,
+            pid: Some(1234),
+            uptime_ms: Some(1000),
+            log_file: Some("turbo.log".into()),
+            hashed_file_count: Some(42),
+            ..DaemonStatus::not_running(&paths)
+        })
+        .unwrap();
+        let status: DaemonStatus = serde_json::from_str(&json).unwrap();
+        assert!(status.running);
+        assert_eq!(status.error, None);
+        assert_eq!(status.pid, Some(1234));
+        assert_eq!(status.uptime_ms, Some(1000));
+        assert_eq!(status.hashed_file_count, Some(42));
+        assert_eq!(status.version, get_version());
+        assert_eq!(status.pid_file, paths.pid_file.as_path());
+
+        let json = serde_json::to_string(&DaemonStatus::not_running(&paths)).unwrap();
+        let status: DaemonStatus = serde_json::from_str(&json).unwrap();
+        assert!(!status.running);
+        assert_eq!(status.error.as_deref(), Some(DAEMON_NOT_RUNNING_MESSAGE));
+        assert_eq!(status.hashed_file_count, None);
+    }
 }
@@ -46,19 +46,7 @@ pub async fn sso_login(
 
     let after = set_path(&before, &["token"], &format!("\"{}\"", token.into_inner()))?;
 
-    global_config_path
-        .ensure_dir()
-        .map_err(|e| config::Error::FailedToSetConfig {
-            config_path: global_config_path.clone(),
-            error: e,
-        })?;
-
-    global_config_path
-        .create_with_contents(after)
-        .map_err(|e| config::Error::FailedToSetConfig {
-            config_path: global_config_path.clone(),
-            error: e,
-        })?;
+    config::write_to_disk(&global_config_path, &after)?;
 
     Ok(())
 }
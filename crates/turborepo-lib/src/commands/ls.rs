@@ -1,11 +1,13 @@
 //! A command for outputting info about packages and tasks in a turborepo.
 
+use std::collections::{BTreeSet, HashSet};
+
 use miette::Diagnostic;
 use serde::Serialize;
 use thiserror::Error;
 use turbopath::AnchoredSystemPath;
 use turborepo_repository::{
-    package_graph::{PackageName, PackageNode},
+    package_graph::{PackageGraph, PackageName, PackageNode},
     package_manager::PackageManager,
 };
 use turborepo_telemetry::events::command::CommandEventBuilder;
@@ -71,6 +73,27 @@ impl<'a> From<RepositoryDetails<'a>> for RepositoryDetailsDisplay<'a> {
     }
 }
 
+/// The dependency graph of the packages in the repository. External
+/// dependencies are included as nodes without a path.
+#[derive(Debug, PartialEq, Serialize)]
+struct PackageGraphDetails {
+    nodes: Vec<PackageGraphNode>,
+    edges: Vec<PackageGraphEdge>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct PackageGraphNode {
+    name: String,
+    path: Option<String>,
+    internal: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct PackageGraphEdge {
+    from: String,
+    to: String,
+}
+
 #[derive(Clone, Serialize)]
 struct PackageTask<'a> {
     name: &'a str,
@@ -118,6 +141,7 @@ pub async fn run(
     telemetry: CommandEventBuilder,
     filter: Vec<String>,
     affected: bool,
+    graph: bool,
     output: Option<OutputFormat>,
 ) -> Result<(), cli::Error> {
     let signal = get_signal()?;
@@ -136,7 +160,9 @@ pub async fn run(
     let run_builder = RunBuilder::new(base)?;
     let run = run_builder.build(&handler, telemetry).await?;
 
-    if packages.is_empty() {
+    if graph {
+        PackageGraphDetails::new(run.pkg_dep_graph(), run.filtered_pkgs()).print(output)?;
+    } else if packages.is_empty() {
         RepositoryDetails::new(&run).print(output)?;
     } else {
         match output {
@@ -231,6 +257,103 @@ impl<'a> RepositoryDetails<'a> {
     }
 }
 
+impl PackageGraphDetails {
+    fn new(package_graph: &PackageGraph, filtered_pkgs: &HashSet<PackageName>) -> Self {
+        let mut nodes = Vec::new();
+        let mut external_nodes = BTreeSet::new();
+        let mut edges = Vec::new();
+
+        for (package_name, package_info) in package_graph.packages() {
+            if !filtered_pkgs.contains(package_name) || matches!(package_name, PackageName::Root) {
+                continue;
+            }
+
+            nodes.push(PackageGraphNode {
+                name: package_name.to_string(),
+                path: Some(package_info.package_path().to_string()),
+                internal: true,
+            });
+
+            let dependencies = package_graph
+                .immediate_dependencies(&PackageNode::Workspace(package_name.clone()))
+                .unwrap_or_default();
+            for dependency in dependencies {
+                let PackageNode::Workspace(dependency @ PackageName::Other(_)) = dependency else {
+                    continue;
+                };
+                if filtered_pkgs.contains(dependency) {
+                    edges.push(PackageGraphEdge {
+                        from: package_name.to_string(),
+                        to: dependency.to_string(),
+                    });
+                }
+            }
+
+            for dependency in package_info
+                .unresolved_external_dependencies
+                .iter()
+                .flat_map(|dependencies| dependencies.keys())
+            {
+                external_nodes.insert(dependency.clone());
+                edges.push(PackageGraphEdge {
+                    from: package_name.to_string(),
+                    to: dependency.clone(),
+                });
+            }
+        }
+
+        nodes.sort();
+        nodes.extend(external_nodes.into_iter().map(|name| PackageGraphNode {
+            name,
+            path: None,
+            internal: false,
+        }));
+        edges.sort();
+
+        Self { nodes, edges }
+    }
+
+    /// Lines for the pretty output: one per edge, followed by one per package
+    /// that has no edges so that it isn't left out of the graph
+    fn pretty_lines(&self) -> Vec<String> {
+        let connected: HashSet<&str> = self
+            .edges
+            .iter()
+            .flat_map(|edge| [edge.from.as_str(), edge.to.as_str()])
+            .collect();
+        self.edges
+            .iter()
+            .map(|edge| format!("{} -> {}", edge.from, edge.to))
+            .chain(
+                self.nodes
+                    .iter()
+                    .filter(|node| !connected.contains(node.name.as_str()))
+                    .map(|node| node.name.clone()),
+            )
+            .collect()
+    }
+
+    fn pretty_print(&self) {
+        for line in self.pretty_lines() {
+            println!("{line}");
+        }
+    }
+
+    fn print(&self, output: Option<OutputFormat>) -> Result<(), cli::Error> {
+        match output {
+            Some(OutputFormat::Json) => {
+                let as_json = serde_json::to_string_pretty(&self)?;
+                println!("{}", as_json);
+            }
+            Some(OutputFormat::Pretty) | None => {
+                self.pretty_print();
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<'a> PackageDetails<'a> {
     fn new(run: &'a Run, package: &'a str) -> Result<Self, Error> {
         let color_config = run.color_config();
@@ -302,3 +425,119 @@ impl<'a> PackageDetails<'a> {
         println!();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+    use turbopath::AbsoluteSystemPath;
+    use turborepo_repository::{
+        discovery::{DiscoveryResponse, PackageDiscovery},
+        package_json::PackageJson,
+    };
+
+    use super::*;
+
+    struct MockDiscovery;
+    impl PackageDiscovery for MockDiscovery {
+        async fn discover_packages(
+            &self,
+        ) -> Result<DiscoveryResponse, turborepo_repository::discovery::Error> {
+            Ok(DiscoveryResponse {
+                package_manager: PackageManager::Npm,
+                workspaces: vec![],
+            })
+        }
+
+        async fn discover_packages_blocking(
+            &self,
+        ) -> Result<DiscoveryResponse, turborepo_repository::discovery::Error> {
+            self.discover_packages().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_package_graph_details() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPath::from_std_path(tmp.path()).unwrap();
+        let package_jsons = HashMap::from([
+            (
+                repo_root.join_components(&["packages", "web", "package.json"]),
+                PackageJson::from_value(json!({
+                    "name": "web",
+                    "dependencies": { "ui": "workspace:*" }
+                }))
+                .unwrap(),
+            ),
+            (
+                repo_root.join_components(&["packages", "ui", "package.json"]),
+                PackageJson::from_value(json!({
+                    "name": "ui",
+                    "dependencies": { "react": "18.0.0" }
+                }))
+                .unwrap(),
+            ),
+        ]);
+        let package_graph = PackageGraph::builder(repo_root, PackageJson::default())
+            .with_package_discovery(MockDiscovery)
+            .with_package_jsons(Some(package_jsons))
+            .build()
+            .await
+            .unwrap();
+        let filtered_pkgs = package_graph
+            .packages()
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        let details = PackageGraphDetails::new(&package_graph, &filtered_pkgs);
+
+        let path = |name: &str| ["packages", name].join(std::path::MAIN_SEPARATOR_STR);
+        assert_eq!(
+            serde_json::to_value(details).unwrap(),
+            json!({
+                "nodes": [
+                    { "name": "ui", "path": path("ui"), "internal": true },
+                    { "name": "web", "path": path("web"), "internal": true },
+                    { "name": "react", "path": null, "internal": false },
+                ],
+                "edges": [
+                    { "from": "ui", "to": "react" },
+                    { "from": "web", "to": "ui" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_package_graph_pretty_lines() {
+        let node = |name: &str, internal: bool| PackageGraphNode {
+            name: name.to_string(),
+            path: internal.then(|| format!("packages/{name}")),
+            internal,
+        };
+        let details = PackageGraphDetails {
+            nodes: vec![
+                node("docs", true),
+                node("ui", true),
+                node("web", true),
+                node("react", false),
+            ],
+            edges: vec![
+                PackageGraphEdge {
+                    from: "ui".to_string(),
+                    to: "react".to_string(),
+                },
+                PackageGraphEdge {
+                    from: "web".to_string(),
+                    to: "ui".to_string(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            details.pretty_lines(),
+            vec!["ui -> react", "web -> ui", "docs"]
+        );
+    }
+}
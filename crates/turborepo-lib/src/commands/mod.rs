@@ -84,6 +84,11 @@ impl CommandBase {
                     .execution_args()
                     .and_then(|execution_args| execution_args.cache_dir.clone()),
             )
+            .with_cache_compression(
+                self.args
+                    .execution_args()
+                    .and_then(|execution_args| execution_args.cache_compression),
+            )
             .with_root_turbo_json_path(
                 self.args
                     .root_turbo_json
@@ -109,6 +114,11 @@ impl CommandBase {
             )
             .with_run_summary(self.args.run_args().and_then(|args| args.summarize()))
             .with_allow_no_turbo_json(self.args.allow_no_turbo_json.then_some(true))
+            .with_platform_env_validation(
+                self.args
+                    .run_args()
+                    .and_then(|args| args.no_platform_env_validation.then_some(false)),
+            )
             .build()
     }
 
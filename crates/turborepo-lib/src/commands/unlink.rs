@@ -32,19 +32,7 @@ fn unlink_remote_caching(base: &mut CommandBase) -> Result<(), cli::Error> {
         let no_id = unset_path(&before, &["teamid"], false)?.unwrap_or(before);
         let no_slug = unset_path(&no_id, &["teamslug"], false)?.unwrap_or(no_id);
 
-        local_config_path
-            .ensure_dir()
-            .map_err(|error| config::Error::FailedToSetConfig {
-                config_path: local_config_path.clone(),
-                error,
-            })?;
-
-        local_config_path
-            .create_with_contents(no_slug)
-            .map_err(|error| config::Error::FailedToSetConfig {
-                config_path: local_config_path.clone(),
-                error,
-            })?;
+        config::write_to_disk(&local_config_path, &no_slug)?;
 
         "> Disabled Remote Caching"
     } else {
@@ -72,19 +60,7 @@ fn unlink_spaces(base: &mut CommandBase) -> Result<(), cli::Error> {
         let no_id = unset_path(&before, &["teamid"], false)?.unwrap_or(before);
         let no_slug = unset_path(&no_id, &["teamslug"], false)?.unwrap_or(no_id);
 
-        local_config_path
-            .ensure_dir()
-            .map_err(|e| config::Error::FailedToSetConfig {
-                config_path: local_config_path.clone(),
-                error: e,
-            })?;
-
-        local_config_path
-            .create_with_contents(no_slug)
-            .map_err(|e| config::Error::FailedToSetConfig {
-                config_path: local_config_path.clone(),
-                error: e,
-            })?;
+        config::write_to_disk(&local_config_path, &no_slug)?;
     }
 
     // Space config is _also_ in turbo.json.
@@ -9,7 +9,7 @@ use turbopath::AbsoluteSystemPathBuf;
 
 use super::{ConfigurationOptions, Error, ResolvedConfigurationOptions};
 use crate::{
-    cli::{EnvMode, LogOrder},
+    cli::{CacheCompression, EnvMode, LogOrder},
     turbo_json::UIMode,
 };
 
@@ -29,6 +29,7 @@ const TURBO_MAPPING: &[(&str, &str)] = [
     ("turbo_daemon", "daemon"),
     ("turbo_env_mode", "env_mode"),
     ("turbo_cache_dir", "cache_dir"),
+    ("turbo_cache_compression", "cache_compression"),
     ("turbo_preflight", "preflight"),
     ("turbo_scm_base", "scm_base"),
     ("turbo_scm_head", "scm_head"),
@@ -127,6 +128,17 @@ impl ResolvedConfigurationOptions for EnvVars {
 
         let cache_dir = self.output_map.get("cache_dir").map(|s| s.clone().into());
 
+        let cache_compression = self
+            .output_map
+            .get("cache_compression")
+            .map(|s| s.as_str())
+            .and_then(|s| match s {
+                "zstd" => Some(CacheCompression::Zstd),
+                "gzip" => Some(CacheCompression::Gzip),
+                "none" => Some(CacheCompression::None),
+                _ => None,
+            });
+
         let root_turbo_json_path = self
             .output_map
             .get("root_turbo_json_path")
@@ -154,6 +166,13 @@ impl ResolvedConfigurationOptions for EnvVars {
         // name we want to stick with.
         let spaces_id = None;
 
+        // The updater reads `NO_UPDATE_NOTIFIER` and `TURBO_NO_UPDATE_NOTIFIER`
+        // itself so there's nothing to pick up here.
+        let update_notifier = None;
+        // Platform env validation is turned off with `TURBO_PLATFORM_ENV_DISABLED`,
+        // which is read when the validation happens.
+        let platform_env_validation = None;
+
         let output = ConfigurationOptions {
             api_url: self.output_map.get("api_url").cloned(),
             login_url: self.output_map.get("login_url").cloned(),
@@ -174,6 +193,8 @@ impl ResolvedConfigurationOptions for EnvVars {
             remote_cache_read_only,
             run_summary,
             allow_no_turbo_json,
+            update_notifier,
+            platform_env_validation,
 
             // Processed numbers
             timeout,
@@ -181,6 +202,7 @@ impl ResolvedConfigurationOptions for EnvVars {
             spaces_id,
             env_mode,
             cache_dir,
+            cache_compression,
             root_turbo_json_path,
             log_order,
         };
@@ -267,6 +289,7 @@ mod test {
         env.insert("turbo_preflight".into(), "true".into());
         env.insert("turbo_env_mode".into(), "strict".into());
         env.insert("turbo_cache_dir".into(), cache_dir.clone().into());
+        env.insert("turbo_cache_compression".into(), "gzip".into());
         env.insert("turbo_root_turbo_json".into(), root_turbo_json.into());
         env.insert("turbo_force".into(), "1".into());
         env.insert("turbo_log_order".into(), "grouped".into());
@@ -297,6 +320,7 @@ mod test {
         assert_eq!(Some(true), config.daemon);
         assert_eq!(Some(EnvMode::Strict), config.env_mode);
         assert_eq!(cache_dir, config.cache_dir.unwrap());
+        assert_eq!(Some(CacheCompression::Gzip), config.cache_compression);
         assert_eq!(
             config.root_turbo_json_path,
             Some(AbsoluteSystemPathBuf::new(root_turbo_json).unwrap())
@@ -314,6 +338,7 @@ mod test {
         env.insert("turbo_ui".into(), "".into());
         env.insert("turbo_daemon".into(), "".into());
         env.insert("turbo_env_mode".into(), "".into());
+        env.insert("turbo_cache_compression".into(), "".into());
         env.insert("turbo_preflight".into(), "".into());
         env.insert("turbo_scm_head".into(), "".into());
         env.insert("turbo_scm_base".into(), "".into());
@@ -337,6 +362,7 @@ mod test {
         assert_eq!(config.ui, None);
         assert_eq!(config.daemon, None);
         assert_eq!(config.env_mode, None);
+        assert_eq!(config.cache_compression, None);
         assert!(!config.preflight());
         assert_eq!(config.scm_base(), None);
         assert_eq!(config.scm_head(), None);
@@ -14,10 +14,39 @@ impl ConfigFile {
         Ok(Self { path })
     }
 
+    /// The repository's config lives in `.turbo/config.json`. If that doesn't
+    /// exist, but `.turbo/config.toml` does, then the TOML file is used.
     pub fn local_config(repo_root: &AbsoluteSystemPath) -> Self {
         let path = repo_root.join_components(&[".turbo", "config.json"]);
+        let toml_path = repo_root.join_components(&[".turbo", "config.toml"]);
+        if !path.exists() && toml_path.exists() {
+            return Self { path: toml_path };
+        }
         Self { path }
     }
+
+    fn format(&self) -> ConfigFormat {
+        match self.path.extension() {
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+}
+
+/// Formats a config file can be written in, picked by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    fn parse(self, contents: &str) -> Result<ConfigurationOptions, Error> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(contents)?,
+            ConfigFormat::Toml => toml::from_str(contents)?,
+        })
+    }
 }
 
 impl ResolvedConfigurationOptions for ConfigFile {
@@ -34,9 +63,11 @@ impl ResolvedConfigurationOptions for ConfigFile {
             })?
             .filter(|s| !s.is_empty());
 
-        let global_config = contents
-            .as_deref()
-            .map_or_else(|| Ok(ConfigurationOptions::default()), serde_json::from_str)?;
+        let format = self.format();
+        let global_config = contents.as_deref().map_or_else(
+            || Ok(ConfigurationOptions::default()),
+            |contents| format.parse(contents),
+        )?;
         Ok(global_config)
     }
 }
@@ -101,3 +132,69 @@ fn global_auth_path() -> Result<AbsoluteSystemPathBuf, Error> {
 
     Ok(turbo_config_dir.join_components(&[TURBO_TOKEN_DIR, TURBO_TOKEN_FILE]))
 }
+
+#[cfg(test)]
+mod test {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_json_and_toml_config_match() {
+        let tmp_dir = TempDir::new().unwrap();
+        let dir = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        let json_path = dir.join_component("config.json");
+        json_path
+            .create_with_contents(
+                r#"{
+                    "teamslug": "my-team",
+                    "apiUrl": "https://example.com",
+                    "timeout": 40,
+                    "daemon": false,
+                    "ui": "stream"
+                }"#,
+            )
+            .unwrap();
+        let toml_path = dir.join_component("config.toml");
+        toml_path
+            .create_with_contents(
+                r#"
+teamslug = "my-team"
+apiUrl = "https://example.com"
+timeout = 40
+daemon = false
+ui = "stream"
+"#,
+            )
+            .unwrap();
+
+        let existing = ConfigurationOptions::default();
+        let json_config = ConfigFile::global_config(Some(json_path))
+            .unwrap()
+            .get_configuration_options(&existing)
+            .unwrap();
+        let toml_config = ConfigFile::global_config(Some(toml_path))
+            .unwrap()
+            .get_configuration_options(&existing)
+            .unwrap();
+
+        assert_eq!(json_config.team_slug(), Some("my-team"));
+        assert_eq!(json_config.api_url(), "https://example.com");
+        assert_eq!(json_config.timeout(), 40);
+        assert_eq!(json_config, toml_config);
+    }
+
+    #[test]
+    fn test_local_config_prefers_json() {
+        let tmp_dir = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        let toml_path = repo_root.join_components(&[".turbo", "config.toml"]);
+        toml_path.ensure_dir().unwrap();
+        toml_path.create_with_contents("timeout = 40\n").unwrap();
+        assert_eq!(ConfigFile::local_config(&repo_root).path, toml_path);
+
+        let json_path = repo_root.join_components(&[".turbo", "config.json"]);
+        json_path.create_with_contents("{}").unwrap();
+        assert_eq!(ConfigFile::local_config(&repo_root).path, json_path);
+    }
+}
@@ -3,7 +3,11 @@ mod file;
 mod override_env;
 mod turbo_json;
 
-use std::{collections::HashMap, ffi::OsString, io};
+use std::{
+    collections::HashMap,
+    ffi::{OsStr, OsString},
+    io::{self, Write},
+};
 
 use camino::{Utf8Path, Utf8PathBuf};
 use convert_case::{Case, Casing};
@@ -24,7 +28,7 @@ use turborepo_repository::package_graph::PackageName;
 
 pub use crate::turbo_json::{RawTurboJson, UIMode};
 use crate::{
-    cli::{EnvMode, LogOrder},
+    cli::{CacheCompression, EnvMode, LogOrder},
     commands::CommandBase,
     turbo_json::CONFIG_FILE,
 };
@@ -63,14 +67,20 @@ pub enum Error {
          one"
     )]
     NoTurboJSON,
+    #[error("Could not find the root turbo.json at {path}")]
+    RootTurboJsonNotFound { path: AbsoluteSystemPathBuf },
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error(transparent)]
     Io(#[from] io::Error),
     #[error(transparent)]
     Camino(#[from] camino::FromPathBufError),
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
+    #[error("Environment variable {name} used in config value for {field} is not set")]
+    UnsetConfigEnvVar { name: String, field: &'static str },
     #[error("Encountered an IO error while attempting to read {config_path}: {error}")]
     FailedToReadConfig {
         config_path: AbsoluteSystemPathBuf,
@@ -100,6 +110,38 @@ pub enum Error {
         #[label("`interruptible` set here")]
         span: Option<SourceSpan>,
     },
+    #[error("ready probes can only be set on persistent tasks")]
+    ReadyButNotPersistent {
+        #[source_code]
+        text: NamedSource,
+        #[label("`ready` set here")]
+        span: Option<SourceSpan>,
+    },
+    #[error("`ready` must set exactly one of `port` or `log`")]
+    InvalidReadyProbe {
+        #[source_code]
+        text: NamedSource,
+        #[label("invalid probe")]
+        span: Option<SourceSpan>,
+    },
+    #[error("invalid `timeout`: {source}")]
+    InvalidTaskTimeout {
+        #[source]
+        source: humantime::DurationError,
+        #[source_code]
+        text: NamedSource,
+        #[label("invalid duration")]
+        span: Option<SourceSpan>,
+    },
+    #[error("invalid `ready.log` pattern: {source}")]
+    InvalidReadyLogPattern {
+        #[source]
+        source: regex::Error,
+        #[source_code]
+        text: NamedSource,
+        #[label("invalid pattern")]
+        span: Option<SourceSpan>,
+    },
     #[error(transparent)]
     #[diagnostic(transparent)]
     InvalidEnvPrefix(Box<InvalidEnvPrefixError>),
@@ -247,6 +289,8 @@ pub struct ConfigurationOptions {
     pub(crate) scm_head: Option<String>,
     #[serde(rename = "cacheDir")]
     pub(crate) cache_dir: Option<Utf8PathBuf>,
+    #[serde(rename = "cacheCompression")]
+    pub(crate) cache_compression: Option<CacheCompression>,
     // This is skipped as we never want this to be stored in a file
     #[serde(skip)]
     pub(crate) root_turbo_json_path: Option<AbsoluteSystemPathBuf>,
@@ -256,6 +300,8 @@ pub struct ConfigurationOptions {
     pub(crate) remote_cache_read_only: Option<bool>,
     pub(crate) run_summary: Option<bool>,
     pub(crate) allow_no_turbo_json: Option<bool>,
+    pub(crate) update_notifier: Option<bool>,
+    pub(crate) platform_env_validation: Option<bool>,
 }
 
 #[derive(Default)]
@@ -317,6 +363,11 @@ impl ConfigurationOptions {
     }
 
     pub fn ui(&self) -> UIMode {
+        // JSON events are meant for machines so they don't depend on a TTY
+        if self.ui == Some(UIMode::Json) {
+            return UIMode::Json;
+        }
+
         // If we aren't hooked up to a TTY, then do not use TUI
         if !atty::is(atty::Stream::Stdout) {
             return UIMode::Stream;
@@ -358,6 +409,10 @@ impl ConfigurationOptions {
         self.env_mode.unwrap_or_default()
     }
 
+    pub fn cache_compression(&self) -> CacheCompression {
+        self.cache_compression.unwrap_or_default()
+    }
+
     pub fn cache_dir(&self) -> &Utf8Path {
         self.cache_dir.as_deref().unwrap_or_else(|| {
             Utf8Path::new(if cfg!(windows) {
@@ -397,6 +452,65 @@ impl ConfigurationOptions {
     pub fn allow_no_turbo_json(&self) -> bool {
         self.allow_no_turbo_json.unwrap_or_default()
     }
+
+    pub fn update_notifier(&self) -> bool {
+        self.update_notifier.unwrap_or(true)
+    }
+
+    pub fn platform_env_validation(&self) -> bool {
+        self.platform_env_validation.unwrap_or(true)
+    }
+
+    /// Expands `${NAME}` references in string values using `env`. Only string
+    /// values are expanded, other values are left untouched.
+    fn expand_env_vars(mut self, env: &HashMap<OsString, OsString>) -> Result<Self, Error> {
+        for (field, value) in [
+            ("apiUrl", &mut self.api_url),
+            ("loginUrl", &mut self.login_url),
+            ("teamSlug", &mut self.team_slug),
+            ("teamId", &mut self.team_id),
+            ("token", &mut self.token),
+            ("spacesId", &mut self.spaces_id),
+            ("scmBase", &mut self.scm_base),
+            ("scmHead", &mut self.scm_head),
+        ] {
+            if let Some(value) = value {
+                *value = expand_env_vars(value, field, env)?;
+            }
+        }
+        if let Some(cache_dir) = &mut self.cache_dir {
+            *cache_dir = expand_env_vars(cache_dir.as_str(), "cacheDir", env)?.into();
+        }
+        Ok(self)
+    }
+}
+
+/// Replaces every `${NAME}` in `value` with the value of the environment
+/// variable `NAME`. Referencing a variable that isn't set is an error.
+fn expand_env_vars(
+    value: &str,
+    field: &'static str,
+    env: &HashMap<OsString, OsString>,
+) -> Result<String, Error> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let var = env
+            .get(OsStr::new(name))
+            .ok_or_else(|| Error::UnsetConfigEnvVar {
+                name: name.to_owned(),
+                field,
+            })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&var.to_string_lossy());
+        rest = &rest[start + 2 + len + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
 }
 
 // Maps Some("") to None to emulate how Go handles empty strings
@@ -421,9 +535,35 @@ impl<'a> ResolvedConfigurationOptions for &'a ConfigurationOptions {
     }
 }
 
-fn get_lowercased_env_vars() -> HashMap<OsString, OsString> {
-    std::env::vars_os()
-        .map(|(k, v)| (k.to_ascii_lowercase(), v))
+/// Reads only the global config. The shim uses this to check settings like
+/// `updateNotifier` before the repository has been found.
+pub fn global_config() -> Result<ConfigurationOptions, Error> {
+    ConfigFile::global_config(None)?.get_configuration_options(&ConfigurationOptions::default())
+}
+
+/// Writes a config file by writing the contents to a temporary file next to
+/// it and renaming that over the config. The rename is atomic so an
+/// interrupted write never leaves a truncated config behind.
+pub fn write_to_disk(config_path: &AbsoluteSystemPath, contents: &str) -> Result<(), Error> {
+    let set_error = |error: io::Error| Error::FailedToSetConfig {
+        config_path: config_path.to_owned(),
+        error,
+    };
+    config_path.ensure_dir().map_err(set_error)?;
+    let config_dir = config_path
+        .parent()
+        .expect("config file should have a parent directory");
+    let mut file = tempfile::NamedTempFile::new_in(config_dir).map_err(set_error)?;
+    file.write_all(contents.as_bytes()).map_err(set_error)?;
+    file.as_file().sync_all().map_err(set_error)?;
+    file.persist(config_path.as_std_path())
+        .map_err(|error| set_error(error.error))?;
+    Ok(())
+}
+
+fn lowercase_env_vars(env: &HashMap<OsString, OsString>) -> HashMap<OsString, OsString> {
+    env.iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.clone()))
         .collect()
 }
 
@@ -450,7 +590,7 @@ impl TurborepoConfigBuilder {
     fn get_environment(&self) -> HashMap<OsString, OsString> {
         self.environment
             .clone()
-            .unwrap_or_else(get_lowercased_env_vars)
+            .unwrap_or_else(|| std::env::vars_os().collect())
     }
 
     pub fn build(&self) -> Result<ConfigurationOptions, Error> {
@@ -467,8 +607,9 @@ impl TurborepoConfigBuilder {
         let global_auth = AuthFile::global_auth(self.global_config_path.clone())?;
         let local_config = ConfigFile::local_config(&self.repo_root);
         let env_vars = self.get_environment();
-        let env_var_config = EnvVars::new(&env_vars)?;
-        let override_env_var_config = OverrideEnvVars::new(&env_vars)?;
+        let lowercased_env_vars = lowercase_env_vars(&env_vars);
+        let env_var_config = EnvVars::new(&lowercased_env_vars)?;
+        let override_env_var_config = OverrideEnvVars::new(&lowercased_env_vars)?;
 
         // These are ordered from highest to lowest priority
         let sources: [Box<dyn ResolvedConfigurationOptions>; 7] = [
@@ -486,14 +627,11 @@ impl TurborepoConfigBuilder {
             |mut acc, current_source| {
                 let current_source_config = current_source.get_configuration_options(&acc)?;
                 acc.merge(current_source_config);
-                Ok(acc)
+                Ok::<_, Error>(acc)
             },
-        );
+        )?;
 
-        // We explicitly do a let and return to help the Rust compiler see that there
-        // are no references still held by the folding.
-        #[allow(clippy::let_and_return)]
-        config
+        config.expand_env_vars(&env_vars)
     }
 }
 
@@ -502,11 +640,16 @@ mod test {
     use std::{collections::HashMap, ffi::OsString};
 
     use tempfile::TempDir;
+    use test_case::test_case;
     use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf};
 
-    use crate::config::{
-        ConfigurationOptions, TurborepoConfigBuilder, DEFAULT_API_URL, DEFAULT_LOGIN_URL,
-        DEFAULT_TIMEOUT,
+    use crate::{
+        cli::{CacheCompression, LogOrder},
+        config::{
+            expand_env_vars, write_to_disk, ConfigurationOptions, TurborepoConfigBuilder,
+            DEFAULT_API_URL, DEFAULT_LOGIN_URL, DEFAULT_TIMEOUT,
+        },
+        turbo_json::UIMode,
     };
 
     #[test]
@@ -624,4 +767,191 @@ mod test {
         assert!(!config.preflight());
         assert_eq!(config.timeout(), 123);
     }
+
+    #[test]
+    fn test_scm_base_from_turbo_json() {
+        let tmp_dir = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        repo_root
+            .join_component("turbo.json")
+            .create_with_contents(r#"{"scmBase": "develop"}"#)
+            .unwrap();
+
+        let builder = TurborepoConfigBuilder {
+            repo_root: repo_root.clone(),
+            override_config: ConfigurationOptions::default(),
+            global_config_path: None,
+            environment: Some(HashMap::default()),
+        };
+        assert_eq!(builder.build().unwrap().scm_base(), Some("develop"));
+
+        let builder = TurborepoConfigBuilder {
+            repo_root,
+            override_config: ConfigurationOptions::default(),
+            global_config_path: None,
+            environment: Some(HashMap::from([(
+                OsString::from("turbo_scm_base"),
+                OsString::from("release"),
+            )])),
+        };
+        assert_eq!(builder.build().unwrap().scm_base(), Some("release"));
+    }
+
+    #[test]
+    fn test_cache_compression_from_turbo_json() {
+        let tmp_dir = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        repo_root
+            .join_component("turbo.json")
+            .create_with_contents(r#"{"cacheCompression": "gzip"}"#)
+            .unwrap();
+
+        let builder = TurborepoConfigBuilder {
+            repo_root: repo_root.clone(),
+            override_config: ConfigurationOptions::default(),
+            global_config_path: None,
+            environment: Some(HashMap::default()),
+        };
+        assert_eq!(
+            builder.build().unwrap().cache_compression(),
+            CacheCompression::Gzip
+        );
+
+        // The --cache-compression flag takes precedence over turbo.json
+        let builder = TurborepoConfigBuilder {
+            repo_root,
+            override_config: ConfigurationOptions {
+                cache_compression: Some(CacheCompression::None),
+                ..Default::default()
+            },
+            global_config_path: None,
+            environment: Some(HashMap::default()),
+        };
+        assert_eq!(
+            builder.build().unwrap().cache_compression(),
+            CacheCompression::None
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let tmp_dir = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        repo_root
+            .join_component("turbo.json")
+            .create_with_contents(
+                r#"{"remoteCache": {"apiUrl": "https://${CACHE_HOST}/api", "teamSlug": "${TEAM}", "timeout": 20}}"#,
+            )
+            .unwrap();
+
+        let mut env: HashMap<OsString, OsString> = HashMap::new();
+        env.insert("CACHE_HOST".into(), "cache.example.com".into());
+        env.insert("TEAM".into(), "my-team".into());
+
+        let builder = TurborepoConfigBuilder {
+            repo_root,
+            override_config: Default::default(),
+            global_config_path: None,
+            environment: Some(env),
+        };
+
+        let config = builder.build().unwrap();
+        assert_eq!(config.api_url(), "https://cache.example.com/api");
+        assert_eq!(config.team_slug(), Some("my-team"));
+        assert_eq!(config.timeout(), 20);
+    }
+
+    #[test]
+    fn test_expand_unset_env_var() {
+        let tmp_dir = TempDir::new().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp_dir.path()).unwrap();
+        repo_root
+            .join_component("turbo.json")
+            .create_with_contents(r#"{"remoteCache": {"apiUrl": "https://${CACHE_HOST}/api"}}"#)
+            .unwrap();
+
+        let builder = TurborepoConfigBuilder {
+            repo_root,
+            override_config: Default::default(),
+            global_config_path: None,
+            environment: Some(HashMap::default()),
+        };
+
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Environment variable CACHE_HOST used in config value for apiUrl is not set"
+        );
+    }
+
+    #[test_case("plain", "plain" ; "no references")]
+    #[test_case("${A}${B}", "ab" ; "adjacent")]
+    #[test_case("x-${A}-y", "x-a-y" ; "surrounded")]
+    #[test_case("${A", "${A" ; "unterminated")]
+    fn test_expand_env_var_values(value: &str, expected: &str) {
+        let env = HashMap::from([
+            (OsString::from("A"), OsString::from("a")),
+            (OsString::from("B"), OsString::from("b")),
+        ]);
+        assert_eq!(expand_env_vars(value, "apiUrl", &env).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_expand_env_vars_keeps_case() {
+        let env = HashMap::from([(OsString::from("Team"), OsString::from("my-team"))]);
+        assert_eq!(
+            expand_env_vars("${Team}", "teamSlug", &env).unwrap(),
+            "my-team"
+        );
+        assert!(expand_env_vars("${TEAM}", "teamSlug", &env).is_err());
+    }
+
+    #[test]
+    fn test_json_ui_ignores_log_order() {
+        let config = ConfigurationOptions {
+            ui: Some(UIMode::Json),
+            log_order: Some(LogOrder::Grouped),
+            ..Default::default()
+        };
+        assert_eq!(config.ui(), UIMode::Json);
+    }
+
+    #[test]
+    fn test_write_to_disk() {
+        let tmp_dir = TempDir::new().unwrap();
+        let config_dir = AbsoluteSystemPath::from_std_path(tmp_dir.path())
+            .unwrap()
+            .join_component("turborepo");
+        let config_path = config_dir.join_component("config.json");
+
+        write_to_disk(&config_path, r#"{"token": "a"}"#).unwrap();
+        write_to_disk(&config_path, r#"{"token": "b"}"#).unwrap();
+
+        assert_eq!(config_path.read_to_string().unwrap(), r#"{"token": "b"}"#);
+        assert_eq!(
+            std::fs::read_dir(config_dir.as_std_path()).unwrap().count(),
+            1,
+            "temporary file should be renamed over the config"
+        );
+    }
+
+    #[test]
+    fn test_write_to_disk_failure_leaves_config() {
+        let tmp_dir = TempDir::new().unwrap();
+        let config_dir = AbsoluteSystemPath::from_std_path(tmp_dir.path()).unwrap();
+        // A non-empty directory can't be replaced by a rename
+        let config_path = config_dir.join_component("config.json");
+        let existing = config_path.join_component("existing");
+        existing.ensure_dir().unwrap();
+        existing.create_with_contents("contents").unwrap();
+
+        assert!(write_to_disk(&config_path, "{}").is_err());
+
+        assert_eq!(existing.read_to_string().unwrap(), "contents");
+        assert_eq!(
+            std::fs::read_dir(config_dir.as_std_path()).unwrap().count(),
+            1,
+            "temporary file should be cleaned up"
+        );
+    }
 }
@@ -23,6 +23,14 @@ impl<'a> ResolvedConfigurationOptions for TurboJsonReader<'a> {
         let turbo_json = RawTurboJson::read(self.repo_root, &turbo_json_path).or_else(|e| {
             if let Error::Io(e) = &e {
                 if matches!(e.kind(), std::io::ErrorKind::NotFound) {
+                    // A turbo.json that was explicitly asked for has to exist
+                    if existing_config.root_turbo_json_path.is_some()
+                        && !existing_config.allow_no_turbo_json()
+                    {
+                        return Err(Error::RootTurboJsonNotFound {
+                            path: turbo_json_path.clone(),
+                        });
+                    }
                     return Ok(Default::default());
                 }
             }
@@ -59,7 +67,12 @@ impl<'a> ResolvedConfigurationOptions for TurboJsonReader<'a> {
         opts.allow_no_package_manager = turbo_json.allow_no_package_manager;
         opts.daemon = turbo_json.daemon.map(|daemon| *daemon.as_inner());
         opts.env_mode = turbo_json.env_mode;
+        opts.platform_env_validation = turbo_json.platform_env_validation;
+        opts.scm_base = turbo_json
+            .scm_base
+            .map(|scm_base| scm_base.into_inner().into());
         opts.cache_dir = cache_dir;
+        opts.cache_compression = turbo_json.cache_compression;
         Ok(opts)
     }
 }
@@ -123,4 +136,36 @@ mod test {
         // Make sure we read the correct turbo.json
         assert_eq!(config.daemon(), Some(false));
     }
+
+    #[test]
+    fn test_missing_root_turbo_json_config() {
+        let tmpdir = tempdir().unwrap();
+        let tmpdir_path = AbsoluteSystemPath::new(tmpdir.path().to_str().unwrap()).unwrap();
+        let root_turbo_json_path = tmpdir_path.join_component("turbo.ci.json");
+        let existing_config = ConfigurationOptions {
+            root_turbo_json_path: Some(root_turbo_json_path.clone()),
+            ..Default::default()
+        };
+
+        let reader = TurboJsonReader::new(tmpdir_path);
+        let err = reader
+            .get_configuration_options(&existing_config)
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::RootTurboJsonNotFound { path } if path == &root_turbo_json_path),
+            "expected missing root turbo.json error, got {err}"
+        );
+
+        // Without an explicit path a missing turbo.json is fine
+        let config = reader
+            .get_configuration_options(&ConfigurationOptions::default())
+            .unwrap();
+        assert_eq!(config.daemon(), None);
+
+        let existing_config = ConfigurationOptions {
+            allow_no_turbo_json: Some(true),
+            ..existing_config
+        };
+        assert!(reader.get_configuration_options(&existing_config).is_ok());
+    }
 }
@@ -247,8 +247,8 @@ pub enum DaemonError {
     #[error("failed to determine package manager: {0}")]
     PackageManager(#[from] turborepo_repository::package_manager::Error),
 
-    #[error("`tail` is not installed. Please install it to use this feature.")]
-    TailNotInstalled,
+    #[error("unable to read log file: {0}")]
+    ReadLogFile(#[source] io::Error),
 
     #[error("could not find log file")]
     LogFileNotFound,
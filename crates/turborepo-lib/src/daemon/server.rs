@@ -479,10 +479,24 @@ impl proto::turbod_server::Turbod for TurboGrpcServiceInner {
         &self,
         _request: tonic::Request<proto::StatusRequest>,
     ) -> Result<tonic::Response<proto::StatusResponse>, tonic::Status> {
+        // Counting hashed files waits on file watching, so don't let it hold up the
+        // status
+        let hashed_file_count = tokio::time::timeout(
+            Duration::from_millis(100),
+            self.file_watching.hash_watcher.hashed_file_count(),
+        )
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .map(|count| count as u64);
+
         Ok(tonic::Response::new(proto::StatusResponse {
             daemon_status: Some(proto::DaemonStatus {
                 uptime_msec: self.start_time.elapsed().as_millis() as u64,
                 log_file: self.log_file.to_string(),
+                version: crate::get_version().to_string(),
+                pid: std::process::id(),
+                hashed_file_count,
             }),
         }))
     }
@@ -100,6 +100,7 @@ pub struct EngineBuilder<'a> {
     tasks: Vec<Spanned<TaskName<'static>>>,
     root_enabled_tasks: HashSet<TaskName<'static>>,
     tasks_only: bool,
+    include_direct_dependencies: bool,
     add_all_tasks: bool,
     should_validate_engine: bool,
 }
@@ -120,6 +121,7 @@ impl<'a> EngineBuilder<'a> {
             tasks: Vec::new(),
             root_enabled_tasks: HashSet::new(),
             tasks_only: false,
+            include_direct_dependencies: false,
             add_all_tasks: false,
             should_validate_engine: true,
         }
@@ -130,6 +132,13 @@ impl<'a> EngineBuilder<'a> {
         self
     }
 
+    /// When used with `tasks_only`, the direct dependencies of the specified
+    /// tasks are included as well, but not their transitive dependencies
+    pub fn with_direct_dependencies(mut self, include_direct_dependencies: bool) -> Self {
+        self.include_direct_dependencies = include_direct_dependencies;
+        self
+    }
+
     pub fn with_root_tasks<I: IntoIterator<Item = TaskName<'static>>>(mut self, tasks: I) -> Self {
         self.root_enabled_tasks = tasks
             .into_iter()
@@ -186,6 +195,21 @@ impl<'a> EngineBuilder<'a> {
         }
     }
 
+    // Returns if the edge from `task_id` to its dependency `dependency_id` should
+    // be added to the engine
+    fn is_dependency_allowed(
+        &self,
+        allowed_tasks: Option<&HashSet<TaskId<'static>>>,
+        task_id: &TaskId<'static>,
+        dependency_id: &TaskId<'static>,
+    ) -> bool {
+        let Some(allowed_tasks) = allowed_tasks else {
+            return true;
+        };
+        allowed_tasks.contains(dependency_id)
+            || (self.include_direct_dependencies && allowed_tasks.contains(task_id))
+    }
+
     pub fn build(mut self) -> Result<super::Engine, Error> {
         // If there are no affected packages, we don't need to go through all this work
         // we can just exit early.
@@ -361,10 +385,12 @@ impl<'a> EngineBuilder<'a> {
                     // We don't need to add an edge from the root node if we're in this branch
                     if let PackageNode::Workspace(dependency_workspace) = dependency_workspace {
                         let from_task_id = TaskId::from_graph(dependency_workspace, from);
-                        if let Some(allowed_tasks) = &allowed_tasks {
-                            if !allowed_tasks.contains(&from_task_id) {
-                                return;
-                            }
+                        if !self.is_dependency_allowed(
+                            allowed_tasks.as_ref(),
+                            &to_task_id,
+                            &from_task_id,
+                        ) {
+                            return;
                         }
                         let from_task_index = engine.get_index(&from_task_id);
                         has_topo_deps = true;
@@ -381,10 +407,8 @@ impl<'a> EngineBuilder<'a> {
                     .task_id()
                     .unwrap_or_else(|| TaskId::new(to_task_id.package(), dep.task()))
                     .into_owned();
-                if let Some(allowed_tasks) = &allowed_tasks {
-                    if !allowed_tasks.contains(&from_task_id) {
-                        continue;
-                    }
+                if !self.is_dependency_allowed(allowed_tasks.as_ref(), &to_task_id, &from_task_id) {
+                    continue;
                 }
                 has_deps = true;
                 let from_task_index = engine.get_index(&from_task_id);
@@ -802,6 +826,55 @@ mod test {
         assert_eq!(all_dependencies(&engine), expected);
     }
 
+    #[test]
+    fn test_workspace_extends_root_task() {
+        let repo_root_dir = TempDir::with_prefix("repo").unwrap();
+        let repo_root = AbsoluteSystemPathBuf::new(repo_root_dir.path().to_str().unwrap()).unwrap();
+        let package_graph = mock_package_graph(
+            &repo_root,
+            package_jsons! {
+                repo_root,
+                "a" => [],
+                "b" => []
+            },
+        );
+        let turbo_jsons = vec![
+            (
+                PackageName::Root,
+                turbo_json(json!({
+                    "tasks": {
+                        "build": { "inputs": ["src/**"], "outputs": ["dist/**"] },
+                    }
+                })),
+            ),
+            (
+                PackageName::from("b"),
+                turbo_json(json!({
+                    "extends": ["//"],
+                    "tasks": {
+                        "build": { "outputs": [".next/**"] },
+                    }
+                })),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let loader = TurboJsonLoader::noop(turbo_jsons);
+        let engine = EngineBuilder::new(&repo_root, &package_graph, loader, false)
+            .with_tasks(Some(Spanned::new(TaskName::from("build"))))
+            .with_workspaces(vec![PackageName::from("a"), PackageName::from("b")])
+            .build()
+            .unwrap();
+
+        let a_build = engine.task_definition(&TaskId::new("a", "build")).unwrap();
+        assert_eq!(a_build.inputs, vec!["src/**".to_string()]);
+        assert_eq!(a_build.outputs.inclusions, vec!["dist/**".to_string()]);
+
+        let b_build = engine.task_definition(&TaskId::new("b", "build")).unwrap();
+        assert_eq!(b_build.inputs, vec!["src/**".to_string()]);
+        assert_eq!(b_build.outputs.inclusions, vec![".next/**".to_string()]);
+    }
+
     #[test]
     fn test_dependencies_on_unspecified_packages() {
         let repo_root_dir = TempDir::with_prefix("repo").unwrap();
@@ -1229,6 +1302,49 @@ mod test {
         assert_eq!(all_dependencies(&engine), expected);
     }
 
+    #[test]
+    fn test_engine_tasks_only_direct_deps() {
+        let repo_root_dir = TempDir::with_prefix("repo").unwrap();
+        let repo_root = AbsoluteSystemPathBuf::new(repo_root_dir.path().to_str().unwrap()).unwrap();
+        let package_graph = mock_package_graph(
+            &repo_root,
+            package_jsons! {
+                repo_root,
+                "a" => ["b"],
+                "b" => ["c"],
+                "c" => []
+            },
+        );
+        let turbo_jsons = vec![(
+            PackageName::Root,
+            turbo_json(json!({
+                "tasks": {
+                    "build": { "dependsOn": ["^build", "prepare"] },
+                    "prepare": {},
+                }
+            })),
+        )]
+        .into_iter()
+        .collect();
+        let loader = TurboJsonLoader::noop(turbo_jsons);
+        let engine = EngineBuilder::new(&repo_root, &package_graph, loader, false)
+            .with_tasks_only(true)
+            .with_direct_dependencies(true)
+            .with_tasks(Some(Spanned::new(TaskName::from("build"))))
+            .with_workspaces(vec![PackageName::from("a")])
+            .with_root_tasks(vec![TaskName::from("build"), TaskName::from("prepare")])
+            .build()
+            .unwrap();
+
+        // Direct dependencies of `a#build` are included, but none of theirs
+        let expected = deps! {
+            "a#build" => ["b#build", "a#prepare"],
+            "a#prepare" => ["___ROOT___"],
+            "b#build" => ["___ROOT___"]
+        };
+        assert_eq!(all_dependencies(&engine), expected);
+    }
+
     #[allow(clippy::duplicated_attributes)]
     #[test_case("build", None)]
     #[test_case("build:prod", None)]
@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
 
 use futures::{stream::FuturesUnordered, StreamExt};
 use tokio::sync::{mpsc, oneshot, Semaphore};
@@ -49,8 +52,13 @@ impl From<mpsc::error::SendError<Message<VisitorData, VisitorResult>>> for Execu
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct StopExecution;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopExecution {
+    /// Stop scheduling any further tasks
+    AllTasks,
+    /// Only stop scheduling tasks that depend on the task that failed
+    DependentTasks,
+}
 
 impl Engine {
     /// Execute a task graph by sending task ids to the visitor
@@ -60,6 +68,9 @@ impl Engine {
     /// type which will stop any further execution of tasks.
     /// This will not stop any task which is currently running, simply it will
     /// stop scheduling new tasks.
+    /// If a task only stops its dependents, any task that depends on it,
+    /// directly or transitively, is skipped instead of being sent to the
+    /// visitor. The skipped tasks are returned once execution finishes.
     // (olszewski) The current impl requires that the visitor receiver is read until
     // finish even once a task sends back the stop signal. This is suboptimal
     // since it would mean the visitor would need to also track if
@@ -68,7 +79,7 @@ impl Engine {
         self: Arc<Self>,
         options: ExecutionOptions,
         visitor: mpsc::Sender<Message<VisitorData, VisitorResult>>,
-    ) -> Result<(), ExecuteError> {
+    ) -> Result<Vec<TaskId<'static>>, ExecuteError> {
         let ExecutionOptions {
             parallel,
             concurrency,
@@ -79,11 +90,16 @@ impl Engine {
 
         let (walker, mut nodes) = Walker::new(&self.task_graph).walk();
         let walker = Arc::new(Mutex::new(walker));
+        // Nodes that failed or were skipped because one of their dependencies did
+        let stopped_nodes = Arc::new(Mutex::new(HashSet::new()));
+        let skipped_tasks = Arc::new(Mutex::new(Vec::new()));
 
         while let Some((node_id, done)) = nodes.recv().await {
             let visitor = visitor.clone();
             let sema = sema.clone();
             let walker = walker.clone();
+            let stopped_nodes = stopped_nodes.clone();
+            let skipped_tasks = skipped_tasks.clone();
             let this = self.clone();
 
             tasks.push(tokio::spawn(async move {
@@ -102,6 +118,31 @@ impl Engine {
                     return Ok(());
                 };
 
+                // The walker only emits a node once all of its dependencies are done, so
+                // any failed dependency has already been recorded at this point.
+                {
+                    let mut stopped_nodes = stopped_nodes.lock().expect("lock poisoned");
+                    if this
+                        .task_graph
+                        .neighbors_directed(node_id, petgraph::Direction::Outgoing)
+                        .any(|dependency| stopped_nodes.contains(&dependency))
+                    {
+                        debug!("skipping {task_id} since one of its dependencies failed");
+                        stopped_nodes.insert(node_id);
+                        skipped_tasks
+                            .lock()
+                            .expect("lock poisoned")
+                            .push(task_id.clone());
+                        if done.send(()).is_err() {
+                            debug!(
+                                "Graph walk done receiver closed before node was finished \
+                                 processing"
+                            );
+                        }
+                        return Ok(());
+                    }
+                }
+
                 // Acquire the semaphore unless parallel
                 let _permit = match parallel {
                     false => Some(sema.acquire().await.expect(
@@ -114,20 +155,26 @@ impl Engine {
                 let (message, result) = Message::new(task_id.clone());
                 visitor.send(message).await?;
 
-                if let Err(StopExecution) = result.await.unwrap_or_else(|_| {
+                match result.await.unwrap_or_else(|_| {
                     // If the visitor doesn't send a callback, then we assume the task finished
                     tracing::trace!(
                         "Engine visitor dropped callback sender without sending result"
                     );
                     Ok(())
                 }) {
-                    if walker
-                        .lock()
-                        .expect("Walker mutex poisoned")
-                        .cancel()
-                        .is_err()
-                    {
-                        debug!("Unable to cancel graph walk");
+                    Ok(()) => (),
+                    Err(StopExecution::AllTasks) => {
+                        if walker
+                            .lock()
+                            .expect("Walker mutex poisoned")
+                            .cancel()
+                            .is_err()
+                        {
+                            debug!("Unable to cancel graph walk");
+                        }
+                    }
+                    Err(StopExecution::DependentTasks) => {
+                        stopped_nodes.lock().expect("lock poisoned").insert(node_id);
                     }
                 }
                 if done.send(()).is_err() {
@@ -141,7 +188,8 @@ impl Engine {
             res.expect("unable to join task")?;
         }
 
-        Ok(())
+        let skipped_tasks = std::mem::take(&mut *skipped_tasks.lock().expect("lock poisoned"));
+        Ok(skipped_tasks)
     }
 }
 
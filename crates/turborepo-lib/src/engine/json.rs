@@ -0,0 +1,104 @@
+use std::io;
+
+use serde::Serialize;
+
+use super::{Built, Engine, TaskNode};
+use crate::run::summary::TaskSummaryTaskDefinition;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskGraph {
+    tasks: Vec<TaskGraphNode>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskGraphNode {
+    task_id: String,
+    dependencies: Vec<String>,
+    definition: Option<TaskSummaryTaskDefinition>,
+}
+
+impl Engine<Built> {
+    /// Writes the resolved task graph along with each task's definition as
+    /// JSON. The root node is left out as it isn't a task.
+    pub fn json_graph<W: io::Write>(&self, writer: W, is_single: bool) -> Result<(), io::Error> {
+        let display_node = |node: &TaskNode| match node {
+            TaskNode::Task(task) if is_single => task.task().to_string(),
+            node => node.to_string(),
+        };
+
+        let mut tasks = self
+            .task_graph
+            .node_indices()
+            .filter_map(|index| {
+                let TaskNode::Task(task_id) = &self.task_graph[index] else {
+                    return None;
+                };
+                let mut dependencies = self
+                    .task_graph
+                    .neighbors_directed(index, petgraph::Direction::Outgoing)
+                    .filter(|dependency| *dependency != self.root_index)
+                    .map(|dependency| display_node(&self.task_graph[dependency]))
+                    .collect::<Vec<_>>();
+                dependencies.sort();
+                dependencies.dedup();
+
+                Some(TaskGraphNode {
+                    task_id: display_node(&self.task_graph[index]),
+                    dependencies,
+                    definition: self
+                        .task_definitions
+                        .get(task_id)
+                        .cloned()
+                        .map(TaskSummaryTaskDefinition::from),
+                })
+            })
+            .collect::<Vec<_>>();
+        tasks.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+
+        serde_json::to_writer_pretty(writer, &TaskGraph { tasks })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{run::task_id::TaskId, task_graph::TaskDefinition};
+
+    #[test]
+    fn test_json_graph() {
+        let mut engine = Engine::new();
+        let lib_build = TaskId::new("lib", "build");
+        let web_build = TaskId::new("web", "build");
+        let lib = engine.get_index(&lib_build);
+        let web = engine.get_index(&web_build);
+        engine.task_graph.add_edge(web, lib, ());
+        engine.connect_to_root(&lib_build);
+        engine.add_definition(lib_build, TaskDefinition::default());
+        engine.add_definition(
+            web_build,
+            TaskDefinition {
+                persistent: true,
+                ..Default::default()
+            },
+        );
+        let engine = engine.seal();
+
+        let mut bytes = Vec::new();
+        engine.json_graph(&mut bytes, false).unwrap();
+        let graph: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        let tasks = graph["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0]["taskId"], json!("lib#build"));
+        assert_eq!(tasks[0]["dependencies"], json!([]));
+        assert_eq!(tasks[0]["definition"]["persistent"], json!(false));
+        assert_eq!(tasks[1]["taskId"], json!("web#build"));
+        assert_eq!(tasks[1]["dependencies"], json!(["lib#build"]));
+        assert_eq!(tasks[1]["definition"]["persistent"], json!(true));
+    }
+}
@@ -80,3 +80,43 @@ fn render_graph<W: io::Write>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::run::task_id::TaskId;
+
+    #[test]
+    fn test_mermaid_graph() {
+        let mut graph = Graph::new();
+        let root = graph.add_node(TaskNode::Root);
+        let web = graph.add_node(TaskNode::from(TaskId::new("web", "build")));
+        let ui = graph.add_node(TaskNode::from(TaskId::new("ui", "build")));
+        graph.add_edge(web, ui, ());
+        graph.add_edge(ui, root, ());
+
+        let mut bytes = Vec::new();
+        render_graph(&mut bytes, &graph, false).unwrap();
+        let output = String::from_utf8(bytes).unwrap();
+
+        assert!(output.starts_with("graph TD\n"), "{output}");
+        let edges = output
+            .lines()
+            .skip(1)
+            .map(|line| {
+                // Strip the generated node ids, leaving only the node labels
+                line.split(" --> ")
+                    .map(|node| node.trim().split_once('(').unwrap().1)
+                    .collect::<Vec<_>>()
+                    .join(" --> ")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                "\"ui#build\") --> \"___ROOT___\")",
+                "\"web#build\") --> \"ui#build\")",
+            ]
+        );
+    }
+}
@@ -2,6 +2,7 @@ mod builder;
 mod execute;
 
 mod dot;
+mod json;
 mod mermaid;
 
 use std::{
@@ -459,7 +460,10 @@ impl Engine<Built> {
                         .ok_or_else(|| ValidateError::MissingPackageJson {
                             package: dep_id.package().to_string(),
                         })?;
+                    // A ready probe lets dependents start once the task is ready instead of
+                    // waiting for it to exit
                     if task_definition.persistent
+                        && task_definition.ready.is_none()
                         && package_json.scripts.contains_key(dep_id.task())
                     {
                         let (span, text) = self
@@ -589,9 +593,10 @@ impl fmt::Display for TaskNode {
 #[cfg(test)]
 mod test {
 
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, sync::Arc};
 
     use tempfile::TempDir;
+    use test_case::test_case;
     use turbopath::AbsoluteSystemPath;
     use turborepo_repository::{
         discovery::{DiscoveryResponse, PackageDiscovery, WorkspaceData},
@@ -599,7 +604,7 @@ mod test {
     };
 
     use super::*;
-    use crate::run::task_id::TaskName;
+    use crate::{run::task_id::TaskName, task_graph::ReadyProbe};
 
     struct DummyDiscovery<'a>(&'a TempDir);
 
@@ -717,6 +722,43 @@ mod test {
         engine.validate(&graph, 4, UIMode::Stream).expect("ok");
     }
 
+    #[test_case(None, false ; "without ready probe")]
+    #[test_case(Some(ReadyProbe::Port(3000)), true ; "with ready probe")]
+    #[tokio::test]
+    async fn test_dependency_on_persistent_task(ready: Option<ReadyProbe>, is_valid: bool) {
+        let tmp = tempfile::TempDir::with_prefix("persistent_dependency").unwrap();
+
+        let mut engine = Engine::new();
+        let a_dev = TaskId::new("a", "dev");
+        let b_build = TaskId::new("b", "build");
+        let a_dev_index = engine.get_index(&a_dev);
+        let b_build_index = engine.get_index(&b_build);
+        engine.add_definition(
+            a_dev.clone(),
+            TaskDefinition {
+                persistent: true,
+                ready,
+                ..Default::default()
+            },
+        );
+        engine.add_definition(b_build.clone(), TaskDefinition::default());
+        engine.task_graph.add_edge(b_build_index, a_dev_index, ());
+        engine.connect_to_root(&a_dev);
+        let engine = engine.seal();
+
+        let graph = PackageGraph::builder(
+            AbsoluteSystemPath::from_std_path(tmp.path()).unwrap(),
+            PackageJson::default(),
+        )
+        .with_package_discovery(DummyDiscovery(&tmp))
+        .build()
+        .await
+        .unwrap();
+
+        let result = engine.validate(&graph, 10, UIMode::Stream);
+        assert_eq!(result.is_ok(), is_valid, "{result:?}");
+    }
+
     #[tokio::test]
     async fn test_prune_persistent_tasks() {
         // Verifies that we can prune the `Engine` to include only the persistent tasks
@@ -813,4 +855,92 @@ mod test {
         assert!(tasks.contains(&&TaskNode::Task(a_dev_task_id)));
         assert!(tasks.contains(&&TaskNode::Task(b_build_task_id)));
     }
+
+    #[tokio::test]
+    async fn test_execute_skips_dependents_of_failed_task() {
+        let mut engine = Engine::new();
+
+        // `b` depends on `a` and `c` depends on `b`, `d` is independent
+        let a_build = TaskId::new("a", "build");
+        let b_build = TaskId::new("b", "build");
+        let c_build = TaskId::new("c", "build");
+        let d_build = TaskId::new("d", "build");
+        for task_id in [&a_build, &b_build, &c_build, &d_build] {
+            engine.get_index(task_id);
+            engine.add_definition(task_id.clone(), TaskDefinition::default());
+        }
+        engine.connect_to_root(&a_build);
+        engine.connect_to_root(&d_build);
+        let a_idx = engine.get_index(&a_build);
+        let b_idx = engine.get_index(&b_build);
+        let c_idx = engine.get_index(&c_build);
+        engine.task_graph.add_edge(b_idx, a_idx, ());
+        engine.task_graph.add_edge(c_idx, b_idx, ());
+        let engine = Arc::new(engine.seal());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Message<TaskId<'static>, _>>(4);
+        let visitor = tokio::spawn(async move {
+            let mut visited = Vec::new();
+            while let Some(Message { info, callback }) = rx.recv().await {
+                let result = match info.package() == "a" {
+                    true => Err(StopExecution::DependentTasks),
+                    false => Ok(()),
+                };
+                callback.send(result).unwrap();
+                visited.push(info);
+            }
+            visited
+        });
+
+        let skipped = engine
+            .execute(ExecutionOptions::new(false, 10), tx)
+            .await
+            .unwrap();
+        let mut visited = visitor.await.unwrap();
+        visited.sort();
+
+        assert_eq!(visited, vec![a_build, d_build]);
+        // Skipped dependents are reported back instead of silently dropped
+        assert_eq!(skipped, vec![b_build, c_build]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_runs_dependents_of_dropped_task() {
+        let mut engine = Engine::new();
+
+        // `b` depends on `a`
+        let a_build = TaskId::new("a", "build");
+        let b_build = TaskId::new("b", "build");
+        for task_id in [&a_build, &b_build] {
+            engine.get_index(task_id);
+            engine.add_definition(task_id.clone(), TaskDefinition::default());
+        }
+        engine.connect_to_root(&a_build);
+        let a_idx = engine.get_index(&a_build);
+        let b_idx = engine.get_index(&b_build);
+        engine.task_graph.add_edge(b_idx, a_idx, ());
+        let engine = Arc::new(engine.seal());
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Message<TaskId<'static>, _>>(4);
+        let visitor = tokio::spawn(async move {
+            let mut visited = Vec::new();
+            while let Some(Message { info, callback }) = rx.recv().await {
+                // The visitor skips a declined task by dropping its callback
+                if info.package() != "a" {
+                    callback.send(Ok(())).unwrap();
+                }
+                visited.push(info);
+            }
+            visited
+        });
+
+        let skipped = engine
+            .execute(ExecutionOptions::new(false, 10), tx)
+            .await
+            .unwrap();
+        let visited = visitor.await.unwrap();
+
+        assert_eq!(visited, vec![a_build, b_build]);
+        assert!(skipped.is_empty());
+    }
 }
@@ -6,7 +6,7 @@
 
 mod traits;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use capnp::message::{Builder, HeapAllocator};
 pub use traits::TurboHash;
@@ -58,6 +58,7 @@ pub struct TaskHashable<'a> {
     pub(crate) resolved_env_vars: EnvVarPairs,
     pub(crate) pass_through_env: &'a [String],
     pub(crate) env_mode: EnvMode,
+    pub(crate) env_values: &'a BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone)]
@@ -260,6 +261,16 @@ impl From<TaskHashable<'_>> for Builder<HeapAllocator> {
             }
         }
 
+        // Only set when present so tasks without env values keep their hashes
+        if !task_hashable.env_values.is_empty() {
+            let mut env_values_builder = builder
+                .reborrow()
+                .init_env_values(task_hashable.env_values.len() as u32);
+            for (i, (key, value)) in task_hashable.env_values.iter().enumerate() {
+                env_values_builder.set(i as u32, &format!("{key}={value}"));
+            }
+        }
+
         // We're okay to unwrap here because we haven't hit the nesting
         // limit and the message will not have cycles.
         let size = builder
@@ -381,6 +392,8 @@ impl From<GlobalHashable<'_>> for Builder<HeapAllocator> {
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
     use test_case::test_case;
     use turborepo_lockfiles::Package;
 
@@ -407,11 +420,47 @@ mod test {
             resolved_env_vars: vec![],
             pass_through_env: &["pass_thru_env".to_string()],
             env_mode: EnvMode::Loose,
+            env_values: &BTreeMap::new(),
         };
 
         assert_eq!(task_hashable.hash(), "1f8b13161f57fca1");
     }
 
+    #[test]
+    fn task_hashable_env_values() {
+        let hash = |env_values: &BTreeMap<String, String>| {
+            TaskHashable {
+                global_hash: "global_hash",
+                task_dependency_hashes: vec![],
+                package_dir: None,
+                hash_of_files: "hash_of_files",
+                external_deps_hash: None,
+                task: "task",
+                outputs: TaskOutputs::default(),
+                pass_through_args: &[],
+                env: &[],
+                resolved_env_vars: vec![],
+                pass_through_env: &[],
+                env_mode: EnvMode::Strict,
+                env_values,
+            }
+            .hash()
+        };
+
+        let empty = hash(&BTreeMap::new());
+        let node_options = hash(&BTreeMap::from([(
+            "NODE_OPTIONS".to_string(),
+            "--max-old-space-size=4096".to_string(),
+        )]));
+        let changed_node_options = hash(&BTreeMap::from([(
+            "NODE_OPTIONS".to_string(),
+            "--max-old-space-size=8192".to_string(),
+        )]));
+
+        assert_ne!(empty, node_options);
+        assert_ne!(node_options, changed_node_options);
+    }
+
     #[test]
     fn global_hashable() {
         let global_file_hash_map = vec![(
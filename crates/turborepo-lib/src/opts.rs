@@ -1,4 +1,4 @@
-use std::{backtrace, backtrace::Backtrace};
+use std::{backtrace, backtrace::Backtrace, time::Duration};
 
 use camino::Utf8PathBuf;
 use thiserror::Error;
@@ -8,11 +8,12 @@ use turborepo_cache::{CacheOpts, RemoteCacheOpts};
 
 use crate::{
     cli::{
-        Command, DryRunMode, EnvMode, ExecutionArgs, LogOrder, LogPrefix, OutputLogsMode, RunArgs,
+        Command, ContinueMode, DryRunMode, EnvMode, ExecutionArgs, LogOrder, LogPrefix, OnlyMode,
+        OutputLogsMode, RunArgs,
     },
     commands::CommandBase,
     config::ConfigurationOptions,
-    run::task_id::TaskId,
+    run::task_id::{TaskId, TASK_DELIMITER},
     turbo_json::UIMode,
 };
 
@@ -58,23 +59,39 @@ impl Opts {
             cmd.push_str(" --affected");
         }
 
+        match &self.scope_opts.only_changed {
+            Some(Some(git_ref)) => {
+                cmd.push_str(" --only-changed=");
+                cmd.push_str(git_ref);
+            }
+            Some(None) => cmd.push_str(" --only-changed"),
+            None => (),
+        }
+
         if self.run_opts.parallel {
             cmd.push_str(" --parallel");
         }
 
-        if self.run_opts.continue_on_error {
-            cmd.push_str(" --continue");
+        match self.run_opts.continue_on_error {
+            ContinueMode::Never => (),
+            ContinueMode::Always => cmd.push_str(" --continue"),
+            ContinueMode::DependenciesSuccessful => {
+                cmd.push_str(" --continue=dependencies-successful")
+            }
         }
 
         if let Some(dry) = self.run_opts.dry_run {
             match dry {
                 DryRunMode::Json => cmd.push_str(" --dry=json"),
+                DryRunMode::Ndjson => cmd.push_str(" --dry=ndjson"),
                 DryRunMode::Text => cmd.push_str(" --dry"),
             }
         }
 
-        if self.run_opts.only {
-            cmd.push_str(" --only");
+        match self.run_opts.only {
+            Some(OnlyMode::Tasks) => cmd.push_str(" --only"),
+            Some(OnlyMode::DirectDeps) => cmd.push_str(" --only=direct-deps"),
+            None => (),
         }
 
         if !self.run_opts.pass_through_args.is_empty() {
@@ -132,6 +149,10 @@ struct OptsInputs<'a> {
 pub struct RunCacheOpts {
     pub(crate) skip_reads: bool,
     pub(crate) skip_writes: bool,
+    // Tasks that miss the cache aren't executed
+    pub(crate) only_restore: bool,
+    // Cached logs aren't replayed on a cache hit unless a task opts back in
+    pub(crate) skip_log_replay: bool,
     pub(crate) task_output_logs_override: Option<OutputLogsMode>,
 }
 
@@ -140,6 +161,8 @@ impl<'a> From<OptsInputs<'a>> for RunCacheOpts {
         RunCacheOpts {
             skip_reads: inputs.config.force(),
             skip_writes: inputs.run_args.no_cache,
+            only_restore: inputs.run_args.only_restore,
+            skip_log_replay: inputs.run_args.no_log_replay,
             task_output_logs_override: inputs.execution_args.output_logs,
         }
     }
@@ -151,26 +174,50 @@ pub struct RunOpts {
     pub(crate) concurrency: u32,
     pub(crate) parallel: bool,
     pub(crate) env_mode: EnvMode,
+    pub(crate) env_files: Vec<String>,
     pub(crate) cache_dir: Utf8PathBuf,
     // Whether or not to infer the framework for each workspace.
     pub(crate) framework_inference: bool,
     pub profile: Option<String>,
-    pub(crate) continue_on_error: bool,
+    pub(crate) task_trace: Option<String>,
+    pub(crate) continue_on_error: ContinueMode,
+    // How long running tasks are given to exit when the run is stopped early
+    pub(crate) kill_timeout: Duration,
+    // Whether to warn about platform env vars that loose mode tasks don't declare
+    pub(crate) warn_undeclared_env: bool,
+    // Whether to check tasks for missing platform env vars
+    pub(crate) platform_env_validation: bool,
     pub(crate) pass_through_args: Vec<String>,
-    pub(crate) only: bool,
+    pub(crate) only: Option<OnlyMode>,
     pub(crate) dry_run: Option<DryRunMode>,
+    pub(crate) verbose_hashes: bool,
+    // Task whose hash inputs are printed at the end of the run
+    pub(crate) why_hash: Option<String>,
     pub graph: Option<GraphOpts>,
     pub(crate) daemon: Option<bool>,
     pub(crate) single_package: bool,
     pub log_prefix: ResolvedLogPrefix,
     pub log_order: ResolvedLogOrder,
     pub summarize: bool,
+    // Where to write the run summary instead of `.turbo/runs`
+    pub(crate) summary_path: Option<String>,
     pub(crate) experimental_space_id: Option<String>,
     pub is_github_actions: bool,
     pub ui_mode: UIMode,
 }
 
 impl RunOpts {
+    /// Whether `--why-hash` targets this task. A bare task name matches the
+    /// task in every package.
+    pub fn explains_hash_of(&self, task_id: &TaskId) -> bool {
+        self.why_hash
+            .as_deref()
+            .map_or(false, |why_hash| match why_hash.contains(TASK_DELIMITER) {
+                true => task_id.to_string() == why_hash,
+                false => task_id.task() == why_hash,
+            })
+    }
+
     pub fn args_for_task(&self, task_id: &TaskId) -> Option<Vec<String>> {
         if !self.pass_through_args.is_empty()
             && self
@@ -188,19 +235,31 @@ impl RunOpts {
 #[derive(Clone, Debug)]
 pub enum GraphOpts {
     Stdout,
+    Mermaid,
     File(String),
+    Json,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ResolvedLogOrder {
     Stream,
     Grouped,
+    Json,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum ResolvedLogPrefix {
     Task,
     None,
+    Timestamp,
+    TaskTimestamp,
+}
+
+impl ResolvedLogPrefix {
+    /// Whether each line of task output should start with a timestamp
+    pub fn timestamps(&self) -> bool {
+        matches!(self, Self::Timestamp | Self::TaskTimestamp)
+    }
 }
 
 const DEFAULT_CONCURRENCY: u32 = 10;
@@ -217,18 +276,29 @@ impl<'a> TryFrom<OptsInputs<'a>> for RunOpts {
             .transpose()?
             .unwrap_or(DEFAULT_CONCURRENCY);
 
-        let graph = inputs.run_args.graph.as_deref().map(|file| match file {
-            "" => GraphOpts::Stdout,
-            f => GraphOpts::File(f.to_string()),
-        });
+        let graph = if inputs.run_args.graph_json {
+            Some(GraphOpts::Json)
+        } else {
+            inputs.run_args.graph.as_deref().map(|file| match file {
+                "" => GraphOpts::Stdout,
+                "mermaid" => GraphOpts::Mermaid,
+                f => GraphOpts::File(f.to_string()),
+            })
+        };
 
-        let (is_github_actions, log_order, log_prefix) = match inputs.config.log_order() {
+        let ui_mode = inputs.config.ui();
+        // The JSON UI is made up of the same task events as `--log-order=json`
+        let log_order = match ui_mode {
+            UIMode::Json => LogOrder::Json,
+            _ => inputs.config.log_order(),
+        };
+        let (is_github_actions, log_order, log_prefix) = match log_order {
             LogOrder::Auto if turborepo_ci::Vendor::get_constant() == Some("GITHUB_ACTIONS") => (
                 true,
                 ResolvedLogOrder::Grouped,
                 match inputs.execution_args.log_prefix {
-                    LogPrefix::Task => ResolvedLogPrefix::Task,
-                    _ => ResolvedLogPrefix::None,
+                    LogPrefix::Auto | LogPrefix::None => ResolvedLogPrefix::None,
+                    log_prefix => log_prefix.into(),
                 },
             ),
 
@@ -243,6 +313,8 @@ impl<'a> TryFrom<OptsInputs<'a>> for RunOpts {
                 ResolvedLogOrder::Grouped,
                 inputs.execution_args.log_prefix.into(),
             ),
+            // Task output is wrapped in JSON events so prefixes aren't needed
+            LogOrder::Json => (false, ResolvedLogOrder::Json, ResolvedLogPrefix::None),
         };
 
         Ok(Self {
@@ -250,6 +322,7 @@ impl<'a> TryFrom<OptsInputs<'a>> for RunOpts {
             log_prefix,
             log_order,
             summarize: inputs.config.run_summary(),
+            summary_path: inputs.run_args.summarize_path().map(str::to_owned),
             experimental_space_id: inputs
                 .run_args
                 .experimental_space_id
@@ -259,17 +332,24 @@ impl<'a> TryFrom<OptsInputs<'a>> for RunOpts {
             concurrency,
             parallel: inputs.run_args.parallel,
             profile: inputs.run_args.profile.clone(),
+            task_trace: inputs.run_args.task_trace.clone(),
             continue_on_error: inputs.execution_args.continue_execution,
+            kill_timeout: Duration::from_secs(inputs.run_args.kill_timeout),
+            warn_undeclared_env: inputs.run_args.warn_undeclared_env,
+            platform_env_validation: inputs.config.platform_env_validation(),
             pass_through_args: inputs.execution_args.pass_through_args.clone(),
             only: inputs.execution_args.only,
             daemon: inputs.config.daemon(),
             single_package: inputs.execution_args.single_package,
             graph,
             dry_run: inputs.run_args.dry_run,
+            verbose_hashes: inputs.run_args.verbose_hashes,
+            why_hash: inputs.run_args.why_hash.clone(),
             env_mode: inputs.config.env_mode(),
+            env_files: inputs.run_args.env_file.clone(),
             cache_dir: inputs.config.cache_dir().into(),
             is_github_actions,
-            ui_mode: inputs.config.ui(),
+            ui_mode,
         })
     }
 }
@@ -301,6 +381,8 @@ impl From<LogPrefix> for ResolvedLogPrefix {
             // We default to task-prefixed logs
             LogPrefix::Auto | LogPrefix::Task => ResolvedLogPrefix::Task,
             LogPrefix::None => ResolvedLogPrefix::None,
+            LogPrefix::Timestamp => ResolvedLogPrefix::Timestamp,
+            LogPrefix::TaskTimestamp => ResolvedLogPrefix::TaskTimestamp,
         }
     }
 }
@@ -311,6 +393,9 @@ pub struct ScopeOpts {
     pub global_deps: Vec<String>,
     pub filter_patterns: Vec<String>,
     pub affected_range: Option<(Option<String>, Option<String>)>,
+    /// Git ref to select changed packages from, `Some(None)` uses the merge
+    /// base with the default branch
+    pub only_changed: Option<Option<String>>,
 }
 
 impl<'a> TryFrom<OptsInputs<'a>> for ScopeOpts {
@@ -338,6 +423,7 @@ impl<'a> TryFrom<OptsInputs<'a>> for ScopeOpts {
             pkg_inference_root,
             affected_range,
             filter_patterns: inputs.execution_args.filter.clone(),
+            only_changed: inputs.run_args.only_changed.clone(),
         })
     }
 }
@@ -373,6 +459,7 @@ impl<'a> From<OptsInputs<'a>> for CacheOpts {
             workers: inputs.run_args.cache_workers,
             skip_remote,
             remote_cache_opts,
+            compression: inputs.config.cache_compression().into(),
         }
     }
 }
@@ -393,12 +480,15 @@ impl ScopeOpts {
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
     use test_case::test_case;
     use turborepo_cache::CacheOpts;
 
-    use super::RunOpts;
+    use super::{OptsInputs, RunOpts};
     use crate::{
-        cli::DryRunMode,
+        cli::{ContinueMode, DryRunMode, ExecutionArgs, OnlyMode, RunArgs},
+        config::ConfigurationOptions,
         opts::{Opts, RunCacheOpts, ScopeOpts},
         turbo_json::UIMode,
     };
@@ -407,10 +497,10 @@ mod test {
     struct TestCaseOpts {
         filter_patterns: Vec<String>,
         tasks: Vec<String>,
-        only: bool,
+        only: Option<OnlyMode>,
         pass_through_args: Vec<String>,
         parallel: bool,
-        continue_on_error: bool,
+        continue_on_error: ContinueMode,
         dry_run: Option<DryRunMode>,
         affected: Option<(String, String)>,
     }
@@ -424,11 +514,19 @@ mod test {
     #[test_case(
         TestCaseOpts {
             tasks: vec!["build".to_string()],
-            only: true,
+            only: Some(OnlyMode::Tasks),
             ..Default::default()
         },
         "turbo run build --only"
     )]
+    #[test_case(
+        TestCaseOpts {
+            tasks: vec!["build".to_string()],
+            only: Some(OnlyMode::DirectDeps),
+            ..Default::default()
+        },
+        "turbo run build --only=direct-deps"
+    )]
     #[test_case(
         TestCaseOpts {
             filter_patterns: vec!["my-app".to_string()],
@@ -452,11 +550,20 @@ mod test {
             filter_patterns: vec!["my-app".to_string()],
             tasks: vec!["build".to_string()],
             parallel: true,
-            continue_on_error: true,
+            continue_on_error: ContinueMode::Always,
             ..Default::default()
         },
         "turbo run build --filter=my-app --parallel --continue"
     )]
+    #[test_case    (
+        TestCaseOpts {
+            filter_patterns: vec!["my-app".to_string()],
+            tasks: vec!["build".to_string()],
+            continue_on_error: ContinueMode::DependenciesSuccessful,
+            ..Default::default()
+        },
+        "turbo run build --filter=my-app --continue=dependencies-successful"
+    )]
     #[test_case    (
         TestCaseOpts {
             filter_patterns: vec!["my-app".to_string()],
@@ -498,19 +605,27 @@ mod test {
             concurrency: 10,
             parallel: opts_input.parallel,
             env_mode: crate::cli::EnvMode::Loose,
+            env_files: vec![],
             cache_dir: camino::Utf8PathBuf::new(),
             framework_inference: true,
             profile: None,
+            task_trace: None,
             continue_on_error: opts_input.continue_on_error,
+            kill_timeout: Duration::from_secs(5),
+            warn_undeclared_env: false,
+            platform_env_validation: true,
             pass_through_args: opts_input.pass_through_args,
             only: opts_input.only,
             dry_run: opts_input.dry_run,
+            verbose_hashes: false,
+            why_hash: None,
             graph: None,
             ui_mode: UIMode::Stream,
             single_package: false,
             log_prefix: crate::opts::ResolvedLogPrefix::Task,
             log_order: crate::opts::ResolvedLogOrder::Stream,
             summarize: false,
+            summary_path: None,
             experimental_space_id: None,
             is_github_actions: false,
             daemon: None,
@@ -524,6 +639,7 @@ mod test {
             affected_range: opts_input
                 .affected
                 .map(|(base, head)| (Some(base), Some(head))),
+            only_changed: None,
         };
         let opts = Opts {
             run_opts,
@@ -534,4 +650,50 @@ mod test {
         let synthesized = opts.synthesize_command();
         assert_eq!(synthesized, expected);
     }
+
+    #[test_case(false, None, None, None ; "not affected")]
+    #[test_case(true, None, None, Some((None, None)) ; "default range")]
+    #[test_case(
+        true,
+        Some("develop"),
+        None,
+        Some((Some("develop"), None)) ;
+        "configured base"
+    )]
+    #[test_case(
+        true,
+        Some("develop"),
+        Some("my-branch"),
+        Some((Some("develop"), Some("my-branch"))) ;
+        "configured base and head"
+    )]
+    fn test_affected_range_uses_scm_config(
+        affected: bool,
+        scm_base: Option<&str>,
+        scm_head: Option<&str>,
+        expected: Option<(Option<&str>, Option<&str>)>,
+    ) {
+        let run_args = RunArgs::default();
+        let execution_args = ExecutionArgs {
+            affected,
+            ..Default::default()
+        };
+        let config = ConfigurationOptions {
+            scm_base: scm_base.map(|b| b.to_string()),
+            scm_head: scm_head.map(|h| h.to_string()),
+            ..Default::default()
+        };
+        let scope_opts = ScopeOpts::try_from(OptsInputs {
+            run_args: &run_args,
+            execution_args: &execution_args,
+            config: &config,
+            api_auth: &None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            scope_opts.affected_range,
+            expected.map(|(base, head)| (base.map(|b| b.to_string()), head.map(|h| h.to_string())))
+        );
+    }
 }
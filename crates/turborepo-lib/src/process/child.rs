@@ -419,10 +419,18 @@ impl ChildCommandChannel {
     pub async fn stop(&self) -> Result<(), mpsc::error::SendError<ChildCommand>> {
         self.0.send(ChildCommand::Stop).await
     }
+
+    pub async fn stop_with_grace(
+        &self,
+        grace: Duration,
+    ) -> Result<(), mpsc::error::SendError<ChildCommand>> {
+        self.0.send(ChildCommand::StopWithGrace(grace)).await
+    }
 }
 
 pub enum ChildCommand {
     Stop,
+    StopWithGrace(Duration),
     Kill,
 }
 
@@ -506,6 +514,17 @@ impl Child {
 
     /// Perform a graceful shutdown of the `Child` process.
     pub async fn stop(&mut self) -> Option<ChildExit> {
+        self.stop_inner(None).await
+    }
+
+    /// Perform a graceful shutdown of the `Child` process, giving it `grace`
+    /// to exit before it is killed instead of the timeout it was spawned
+    /// with.
+    pub async fn stop_with_grace(&mut self, grace: Duration) -> Option<ChildExit> {
+        self.stop_inner(Some(grace)).await
+    }
+
+    async fn stop_inner(&mut self, grace: Option<Duration>) -> Option<ChildExit> {
         let mut watch = self.exit_channel.clone();
 
         let fut = async {
@@ -520,7 +539,10 @@ impl Child {
 
             // if this fails, it's because the channel is dropped (toctou)
             // we can just ignore it
-            child.stop().await.ok();
+            match grace {
+                Some(grace) => child.stop_with_grace(grace).await.ok(),
+                None => child.stop().await.ok(),
+            };
         };
 
         let (_, code) = join! {
@@ -772,6 +794,10 @@ impl ChildStateManager {
                 self.shutdown_style.process(child).await
             }
             // we received a command to kill the child process
+            Some(ChildCommand::StopWithGrace(grace)) => {
+                debug!("stopping child process with {grace:?} grace period");
+                ShutdownStyle::Graceful(grace).process(child).await
+            }
             Some(ChildCommand::Kill) => {
                 debug!("killing child process");
                 ShutdownStyle::Kill.process(child).await
@@ -42,6 +42,14 @@ struct ProcessManagerInner {
     size: Option<PtySize>,
 }
 
+/// How to retry spawning a child that failed for a reason that is likely to
+/// go away on its own, e.g. hitting the process limit under heavy load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnRetry {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct PtySize {
     rows: u16,
@@ -120,6 +128,19 @@ impl ProcessManager {
         Some(child)
     }
 
+    /// Spawn a new child process, retrying failures that look transient
+    /// according to `retry`. The result has the same meaning as `spawn`.
+    pub async fn spawn_with_retry(
+        &self,
+        command: Command,
+        stop_timeout: Duration,
+        retry: SpawnRetry,
+    ) -> Option<io::Result<child::Child>> {
+        retry
+            .run(|| self.spawn(command.clone(), stop_timeout))
+            .await
+    }
+
     /// Stop the process manager, closing all child processes. On posix
     /// systems this will send a SIGINT, and on windows it will just kill
     /// the process immediately.
@@ -127,6 +148,14 @@ impl ProcessManager {
         self.close(|mut c| async move { c.stop().await }).await
     }
 
+    /// Stop the process manager, closing all child processes. Children are
+    /// given `grace` to exit after being interrupted before they are killed,
+    /// regardless of the timeout they were spawned with.
+    pub async fn stop_with_grace(&self, grace: Duration) {
+        self.close(move |mut c| async move { c.stop_with_grace(grace).await })
+            .await
+    }
+
     /// Stop the process manager, waiting for all child processes to exit.
     ///
     /// If you want to set a timeout, use `tokio::time::timeout` and
@@ -176,6 +205,48 @@ impl ProcessManager {
     }
 }
 
+impl Default for SpawnRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+impl SpawnRetry {
+    /// Calls `spawn` until it succeeds, fails with an error that isn't
+    /// transient, or `max_attempts` have been made. The delay between attempts
+    /// doubles after each one.
+    async fn run<T>(
+        &self,
+        mut spawn: impl FnMut() -> Option<io::Result<T>>,
+    ) -> Option<io::Result<T>> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            match spawn() {
+                Some(Err(e)) if attempt < self.max_attempts && is_transient_spawn_error(&e) => {
+                    debug!("failed to spawn process, retrying in {backoff:?}: {e}");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Spawn errors caused by temporarily running out of resources (EAGAIN,
+/// ENOMEM) as opposed to the command being missing or not executable.
+fn is_transient_spawn_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::OutOfMemory
+    )
+}
+
 impl ProcessManagerInner {
     fn pty_size(&mut self) -> Option<PtySize> {
         if self.size.is_none() {
@@ -222,6 +293,49 @@ mod test {
 
     const STOPPED_EXIT: Option<ChildExit> = Some(ChildExit::Killed);
 
+    #[test_case(&[io::ErrorKind::WouldBlock], true, 2 ; "transient error is retried")]
+    #[test_case(&[io::ErrorKind::WouldBlock, io::ErrorKind::OutOfMemory], true, 3 ; "retried until success")]
+    #[test_case(&[io::ErrorKind::NotFound], false, 1 ; "not found fails fast")]
+    #[test_case(&[io::ErrorKind::WouldBlock; 3], false, 3 ; "gives up after max attempts")]
+    #[tokio::test]
+    async fn test_spawn_retry(
+        errors: &[io::ErrorKind],
+        expected_success: bool,
+        expected_attempts: usize,
+    ) {
+        let retry = SpawnRetry {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+        };
+        let mut attempts = 0;
+        let result = retry
+            .run(|| {
+                let result = match errors.get(attempts) {
+                    Some(kind) => Err(io::Error::from(*kind)),
+                    None => Ok(()),
+                };
+                attempts += 1;
+                Some(result)
+            })
+            .await;
+
+        assert_eq!(result.unwrap().is_ok(), expected_success);
+        assert_eq!(attempts, expected_attempts);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_retry_stops_when_closed() {
+        let mut attempts = 0;
+        let result: Option<io::Result<()>> = SpawnRetry::default()
+            .run(|| {
+                attempts += 1;
+                None
+            })
+            .await;
+        assert!(result.is_none());
+        assert_eq!(attempts, 1);
+    }
+
     #[tokio::test]
     async fn test_basic() {
         let manager = ProcessManager::new(false);
@@ -382,6 +496,41 @@ mod test {
         );
     }
 
+    #[test_case(Duration::from_secs(2), true ; "enough grace")]
+    #[test_case(Duration::from_millis(100), false ; "grace elapses")]
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_stop_with_grace(grace: Duration, cleaned_up: bool) {
+        let manager = ProcessManager::new(false);
+        // the spawn timeout is ignored in favor of the given grace period
+        let mut child = manager
+            .spawn(
+                get_script_command("sleep_5_cleanup.js"),
+                Duration::from_millis(100),
+            )
+            .unwrap()
+            .unwrap();
+
+        let mut out = Vec::new();
+        let (exit, _) = join! {
+            child.wait_with_piped_outputs(&mut out),
+            async {
+                // give the child time to register its signal handler
+                sleep(Duration::from_millis(500)).await;
+                manager.stop_with_grace(grace).await
+            },
+        };
+
+        assert_eq!(exit.unwrap(), STOPPED_EXIT);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("here"), "child should have started: {out}");
+        assert_eq!(
+            out.contains("cleaned up"),
+            cleaned_up,
+            "unexpected output: {out}"
+        );
+    }
+
     #[tokio::test]
     async fn test_wait_multiple_tasks() {
         let manager = ProcessManager::new(false);
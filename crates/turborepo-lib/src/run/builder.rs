@@ -6,6 +6,7 @@ use std::{
 };
 
 use chrono::Local;
+use tokio::sync::mpsc;
 use tracing::debug;
 use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf};
 use turborepo_analytics::{start_analytics, AnalyticsHandle, AnalyticsSender};
@@ -38,14 +39,15 @@ use {
 };
 
 use crate::{
-    cli::DryRunMode,
+    cli::{DryRunMode, OnlyMode},
     commands::CommandBase,
     engine::{Engine, EngineBuilder},
-    opts::Opts,
+    opts::{Opts, ResolvedLogOrder},
     process::ProcessManager,
     run::{scope, task_access::TaskAccess, task_id::TaskName, Error, Run, RunCache},
     shim::TurboState,
     signal::{SignalHandler, SignalSubscriber},
+    task_graph::TaskTimingEvent,
     turbo_json::{TurboJson, TurboJsonLoader, UIMode},
     DaemonConnector,
 };
@@ -71,6 +73,7 @@ pub struct RunBuilder {
     should_validate_engine: bool,
     // If true, we will add all tasks to the graph, even if they are not specified
     add_all_tasks: bool,
+    task_timing_sender: Option<mpsc::Sender<TaskTimingEvent>>,
 }
 
 impl RunBuilder {
@@ -115,6 +118,7 @@ impl RunBuilder {
             allow_no_turbo_json,
             should_validate_engine: true,
             add_all_tasks: false,
+            task_timing_sender: None,
         })
     }
 
@@ -151,6 +155,13 @@ impl RunBuilder {
         self
     }
 
+    /// Sends a timing event for each task as it finishes
+    #[allow(dead_code)]
+    pub fn with_task_timing_sender(mut self, sender: mpsc::Sender<TaskTimingEvent>) -> Self {
+        self.task_timing_sender = Some(sender);
+        self
+    }
+
     pub fn calculate_filtered_packages(
         repo_root: &AbsoluteSystemPath,
         opts: &Opts,
@@ -244,7 +255,10 @@ impl RunBuilder {
             run_telemetry.track_remote_cache(self.api_client.base_url());
         }
         let _is_structured_output = self.opts.run_opts.graph.is_some()
-            || matches!(self.opts.run_opts.dry_run, Some(DryRunMode::Json));
+            || matches!(
+                self.opts.run_opts.dry_run,
+                Some(DryRunMode::Json | DryRunMode::Ndjson)
+            );
 
         let is_single_package = self.opts.run_opts.single_package;
         repo_telemetry.track_type(if is_single_package {
@@ -452,7 +466,9 @@ impl RunBuilder {
         ));
 
         let should_print_prelude = self.should_print_prelude_override.unwrap_or_else(|| {
-            self.opts.run_opts.dry_run.is_none() && self.opts.run_opts.graph.is_none()
+            self.opts.run_opts.dry_run.is_none()
+                && self.opts.run_opts.graph.is_none()
+                && self.opts.run_opts.log_order != ResolvedLogOrder::Json
         });
 
         Ok(Run {
@@ -476,6 +492,7 @@ impl RunBuilder {
             signal_handler: signal_handler.clone(),
             daemon,
             should_print_prelude,
+            task_timing_sender: self.task_timing_sender,
         })
     }
 
@@ -493,7 +510,8 @@ impl RunBuilder {
             self.opts.run_opts.single_package,
         )
         .with_root_tasks(root_turbo_json.tasks.keys().cloned())
-        .with_tasks_only(self.opts.run_opts.only)
+        .with_tasks_only(self.opts.run_opts.only.is_some())
+        .with_direct_dependencies(self.opts.run_opts.only == Some(OnlyMode::DirectDeps))
         .with_workspaces(filtered_pkgs.cloned().collect())
         .with_tasks(self.opts.run_opts.tasks.iter().map(|task| {
             // TODO: Pull span info from command
@@ -51,6 +51,8 @@ pub struct RunCache {
     warnings: Arc<Mutex<Vec<String>>>,
     reads_disabled: bool,
     writes_disabled: bool,
+    only_restore: bool,
+    skip_log_replay: bool,
     repo_root: AbsoluteSystemPathBuf,
     color_selector: ColorSelector,
     daemon_client: Option<DaemonClient<DaemonConnector>>,
@@ -85,6 +87,8 @@ impl RunCache {
             warnings: Default::default(),
             reads_disabled: opts.skip_reads,
             writes_disabled: opts.skip_writes,
+            only_restore: opts.only_restore,
+            skip_log_replay: opts.skip_log_replay,
             repo_root: repo_root.to_owned(),
             color_selector,
             daemon_client,
@@ -113,6 +117,7 @@ impl RunCache {
         }
 
         let caching_disabled = !task_definition.cache;
+        let log_replay = task_definition.log_replay.unwrap_or(!self.skip_log_replay);
 
         TaskCache {
             expanded_outputs: Vec::new(),
@@ -122,6 +127,7 @@ impl RunCache {
             task_id,
             task_output_logs,
             caching_disabled,
+            log_replay,
             log_file_path,
             daemon_client: self.daemon_client.clone(),
             ui: self.ui,
@@ -149,6 +155,8 @@ pub struct TaskCache {
     hash: String,
     task_output_logs: OutputLogsMode,
     caching_disabled: bool,
+    // Whether logs are replayed on a cache hit
+    log_replay: bool,
     log_file_path: AbsoluteSystemPathBuf,
     daemon_client: Option<DaemonClient<DaemonConnector>>,
     ui: ColorConfig,
@@ -161,6 +169,11 @@ impl TaskCache {
         self.task_output_logs
     }
 
+    /// Whether the task should be skipped instead of executed on a cache miss
+    pub fn only_restore(&self) -> bool {
+        self.run_cache.only_restore
+    }
+
     pub fn is_caching_disabled(&self) -> bool {
         self.caching_disabled
     }
@@ -175,7 +188,10 @@ impl TaskCache {
     }
 
     pub fn on_error(&self, terminal_output: &mut impl CacheOutput) -> Result<(), Error> {
-        if self.task_output_logs == OutputLogsMode::ErrorsOnly {
+        if matches!(
+            self.task_output_logs,
+            OutputLogsMode::ErrorsOnly | OutputLogsMode::ErrorsOnlyWithSummary
+        ) {
             terminal_output.status(
                 &format!(
                     "cache miss, executing {}",
@@ -189,6 +205,25 @@ impl TaskCache {
         Ok(())
     }
 
+    /// Prints the status line for a task that ran successfully if its logs
+    /// were suppressed while it ran
+    pub fn on_success(&self, terminal_output: &mut impl CacheOutput) {
+        if self.task_output_logs == OutputLogsMode::ErrorsOnlyWithSummary {
+            let cache_status = if self.caching_disabled || self.run_cache.reads_disabled {
+                "cache bypass"
+            } else {
+                "cache miss"
+            };
+            terminal_output.status(
+                &format!(
+                    "{cache_status}, suppressing logs {}",
+                    color!(self.ui, GREY, "{}", self.hash)
+                ),
+                CacheResult::Miss,
+            );
+        }
+    }
+
     pub fn output_writer<W: Write>(&self, writer: W) -> Result<LogWriter<W>, Error> {
         let mut log_writer = LogWriter::default();
 
@@ -201,7 +236,10 @@ impl TaskCache {
 
         if !matches!(
             self.task_output_logs,
-            OutputLogsMode::None | OutputLogsMode::HashOnly | OutputLogsMode::ErrorsOnly
+            OutputLogsMode::None
+                | OutputLogsMode::HashOnly
+                | OutputLogsMode::ErrorsOnly
+                | OutputLogsMode::ErrorsOnlyWithSummary
         ) {
             log_writer.with_writer(writer);
         }
@@ -221,7 +259,9 @@ impl TaskCache {
         if self.caching_disabled || self.run_cache.reads_disabled {
             if !matches!(
                 self.task_output_logs,
-                OutputLogsMode::None | OutputLogsMode::ErrorsOnly
+                OutputLogsMode::None
+                    | OutputLogsMode::ErrorsOnly
+                    | OutputLogsMode::ErrorsOnlyWithSummary
             ) {
                 terminal_output.status(
                     &format!(
@@ -263,20 +303,40 @@ impl TaskCache {
             // Note that we currently don't use the output globs when restoring, but we
             // could in the future to avoid doing unnecessary file I/O. We also
             // need to pass along the exclusion globs as well.
-            let cache_status = self
+            let cache_status = match self
                 .run_cache
                 .cache
                 .fetch(&self.run_cache.repo_root, &self.hash)
-                .await?;
+                .await
+            {
+                Ok(cache_status) => cache_status,
+                // The corrupt artifact has already been removed, so treat this as a miss
+                // and let the task rebuild its outputs
+                Err(err @ CacheError::Corrupt(..)) => {
+                    telemetry.track_error(TrackedErrors::ErrorFetchingFromCache);
+                    terminal_output.error(&format!(
+                        "{err}, discarded cache entry {}",
+                        color!(self.ui, GREY, "{}", self.hash)
+                    ));
+                    None
+                }
+                Err(err) => return Err(err.into()),
+            };
 
             let Some((cache_hit_metadata, restored_files)) = cache_status else {
                 if !matches!(
                     self.task_output_logs,
-                    OutputLogsMode::None | OutputLogsMode::ErrorsOnly
+                    OutputLogsMode::None
+                        | OutputLogsMode::ErrorsOnly
+                        | OutputLogsMode::ErrorsOnlyWithSummary
                 ) {
+                    let action = match self.run_cache.only_restore {
+                        true => "skipping",
+                        false => "executing",
+                    };
                     terminal_output.status(
                         &format!(
-                            "cache miss, executing {}",
+                            "cache miss, {action} {}",
                             color!(self.ui, GREY, "{}", self.hash)
                         ),
                         CacheResult::Miss,
@@ -324,27 +384,30 @@ impl TaskCache {
         };
 
         match self.task_output_logs {
-            OutputLogsMode::HashOnly | OutputLogsMode::NewOnly => {
+            OutputLogsMode::Full if self.log_replay => {
+                debug!("log file path: {}", self.log_file_path);
                 terminal_output.status(
                     &format!(
-                        "cache hit{}, suppressing logs {}",
+                        "cache hit{}, replaying logs {}",
                         more_context,
                         color!(self.ui, GREY, "{}", self.hash)
                     ),
                     CacheResult::Hit,
                 );
+                self.replay_log_file(terminal_output)?;
             }
-            OutputLogsMode::Full => {
-                debug!("log file path: {}", self.log_file_path);
+            OutputLogsMode::Full
+            | OutputLogsMode::HashOnly
+            | OutputLogsMode::NewOnly
+            | OutputLogsMode::ErrorsOnlyWithSummary => {
                 terminal_output.status(
                     &format!(
-                        "cache hit{}, replaying logs {}",
+                        "cache hit{}, suppressing logs {}",
                         more_context,
                         color!(self.ui, GREY, "{}", self.hash)
                     ),
                     CacheResult::Hit,
                 );
-                self.replay_log_file(terminal_output)?;
             }
             // Note that if we're restoring from cache, the task succeeded
             // so we know we don't need to print anything for errors
@@ -522,3 +585,268 @@ fn fallible_write(mut writer: impl Write, message: &str) {
         error!("cannot write to logs: {:?}", err);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+    use turbopath::RelativeUnixPath;
+    use turborepo_api_client::APIClient;
+    use turborepo_cache::{CacheCompression, CacheOpts};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingOutput {
+        statuses: Vec<String>,
+        replayed: bool,
+    }
+
+    impl CacheOutput for RecordingOutput {
+        fn status(&mut self, message: &str, _result: CacheResult) {
+            self.statuses.push(message.to_owned());
+        }
+
+        fn error(&mut self, _message: &str) {}
+
+        fn replay_logs(
+            &mut self,
+            _log_file: &AbsoluteSystemPath,
+        ) -> Result<(), turborepo_ui::Error> {
+            self.replayed = true;
+            Ok(())
+        }
+    }
+
+    fn local_cache(repo_root: &AbsoluteSystemPath) -> AsyncCache {
+        AsyncCache::new(
+            &CacheOpts {
+                cache_dir: ".turbo/cache".into(),
+                remote_cache_read_only: false,
+                skip_remote: true,
+                skip_filesystem: false,
+                workers: 1,
+                remote_cache_opts: None,
+                compression: CacheCompression::default(),
+            },
+            repo_root,
+            APIClient::new("http://localhost", None, None, "2.0.0", false).unwrap(),
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test_case(None, false, true ; "replayed by default")]
+    #[test_case(None, true, false ; "skipped with flag")]
+    #[test_case(Some(true), true, true ; "task opts back in")]
+    #[test_case(Some(false), false, false ; "task opts out")]
+    #[tokio::test]
+    async fn test_log_replay(
+        task_log_replay: Option<bool>,
+        skip_log_replay: bool,
+        expect_replay: bool,
+    ) {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let package_info = PackageInfo {
+            package_json_path: AnchoredSystemPathBuf::from_raw("apps/web/package.json").unwrap(),
+            ..Default::default()
+        };
+        let log_file = repo_root.join_components(&["apps", "web", ".turbo", "turbo-build.log"]);
+        log_file.ensure_dir().unwrap();
+        log_file.create_with_contents("building\n").unwrap();
+
+        let cache = local_cache(&repo_root);
+        let hash = "0123456789abcdef";
+        cache
+            .put(
+                repo_root.clone(),
+                hash.to_string(),
+                vec![repo_root.anchor(&log_file).unwrap()],
+                0,
+            )
+            .await
+            .unwrap();
+        cache.wait().await.unwrap();
+
+        let run_cache = Arc::new(RunCache::new(
+            cache,
+            &repo_root,
+            &RunCacheOpts {
+                skip_log_replay,
+                ..Default::default()
+            },
+            ColorSelector::default(),
+            None,
+            ColorConfig::new(true),
+            false,
+        ));
+        let task_definition = TaskDefinition {
+            log_replay: task_log_replay,
+            ..Default::default()
+        };
+        let mut task_cache = run_cache.task_cache(
+            &task_definition,
+            &package_info,
+            TaskId::new("web", "build"),
+            hash,
+        );
+
+        let mut output = RecordingOutput::default();
+        let status = task_cache
+            .restore_outputs(&mut output, &PackageTaskEventBuilder::new("web", "build"))
+            .await
+            .unwrap();
+        assert!(status.is_some());
+        assert_eq!(output.replayed, expect_replay);
+        assert_eq!(
+            output.statuses.len(),
+            1,
+            "the cache hit status is shown either way"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_outputs_recovers_from_corrupt_artifact() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let package_info = PackageInfo {
+            package_json_path: AnchoredSystemPathBuf::from_raw("apps/web/package.json").unwrap(),
+            ..Default::default()
+        };
+        for file in ["apps/web/.turbo/turbo-build.log", "apps/web/dist/index.js"] {
+            let file = repo_root.join_unix_path(RelativeUnixPath::new(file).unwrap());
+            file.ensure_dir().unwrap();
+            file.create_with_contents("contents".repeat(1000)).unwrap();
+        }
+
+        let run_cache = Arc::new(RunCache::new(
+            local_cache(&repo_root),
+            &repo_root,
+            &RunCacheOpts::default(),
+            ColorSelector::default(),
+            None,
+            ColorConfig::new(true),
+            false,
+        ));
+        let task_definition = TaskDefinition {
+            outputs: TaskOutputs {
+                inclusions: vec!["dist/**".to_string()],
+                exclusions: vec![],
+            },
+            ..Default::default()
+        };
+        let hash = "0123456789abcdef";
+        let telemetry = PackageTaskEventBuilder::new("web", "build");
+        let task_cache = || {
+            run_cache.task_cache(
+                &task_definition,
+                &package_info,
+                TaskId::new("web", "build"),
+                hash,
+            )
+        };
+
+        task_cache()
+            .save_outputs(Duration::from_millis(1), &telemetry)
+            .await
+            .unwrap();
+        run_cache.cache.wait().await.unwrap();
+
+        let artifact = repo_root.join_components(&[".turbo", "cache", "0123456789abcdef.tar.zst"]);
+        let contents = artifact.read().unwrap();
+        artifact
+            .create_with_contents(&contents[..contents.len() / 2])
+            .unwrap();
+
+        // The corrupt artifact is treated as a miss so the task runs again
+        let mut output = RecordingOutput::default();
+        let status = task_cache()
+            .restore_outputs(&mut output, &telemetry)
+            .await
+            .unwrap();
+        assert!(status.is_none());
+        assert!(output.statuses[0].starts_with("cache miss, executing"));
+        assert!(!artifact.exists());
+
+        // The rebuilt outputs are cached again and can be restored
+        task_cache()
+            .save_outputs(Duration::from_millis(1), &telemetry)
+            .await
+            .unwrap();
+        run_cache.cache.wait().await.unwrap();
+        let status = task_cache()
+            .restore_outputs(&mut RecordingOutput::default(), &telemetry)
+            .await
+            .unwrap();
+        assert!(status.is_some());
+    }
+
+    #[test_case(&["dist/**"], &["apps/web/dist", "apps/web/dist/index.js", "apps/web/dist/index.js.map", "apps/web/dist/tmp", "apps/web/dist/tmp/chunk.js"] ; "no exclusions")]
+    #[test_case(&["dist/**", "!dist/tmp/**"], &["apps/web/dist", "apps/web/dist/index.js", "apps/web/dist/index.js.map"] ; "excludes subtree")]
+    #[test_case(&["dist/**", "!dist/**/*.map"], &["apps/web/dist", "apps/web/dist/index.js", "apps/web/dist/tmp", "apps/web/dist/tmp/chunk.js"] ; "excludes by extension")]
+    #[tokio::test]
+    async fn test_save_outputs_exclusions(outputs: &[&str], expected: &[&str]) {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let package_info = PackageInfo {
+            package_json_path: AnchoredSystemPathBuf::from_raw("apps/web/package.json").unwrap(),
+            ..Default::default()
+        };
+        for file in [
+            "apps/web/.turbo/turbo-build.log",
+            "apps/web/dist/index.js",
+            "apps/web/dist/index.js.map",
+            "apps/web/dist/tmp/chunk.js",
+        ] {
+            let file = repo_root.join_unix_path(RelativeUnixPath::new(file).unwrap());
+            file.ensure_dir().unwrap();
+            file.create_with_contents("contents").unwrap();
+        }
+
+        let run_cache = Arc::new(RunCache::new(
+            local_cache(&repo_root),
+            &repo_root,
+            &RunCacheOpts::default(),
+            ColorSelector::default(),
+            None,
+            ColorConfig::new(true),
+            false,
+        ));
+        let raw_outputs = outputs.iter().map(|output| output.to_string());
+        let task_definition = TaskDefinition {
+            outputs: TaskOutputs {
+                inclusions: raw_outputs
+                    .clone()
+                    .filter(|output| !output.starts_with('!'))
+                    .collect(),
+                exclusions: raw_outputs
+                    .filter_map(|output| output.strip_prefix('!').map(|glob| glob.to_string()))
+                    .collect(),
+            },
+            ..Default::default()
+        };
+        let mut task_cache = run_cache.task_cache(
+            &task_definition,
+            &package_info,
+            TaskId::new("web", "build"),
+            "0123456789abcdef",
+        );
+
+        task_cache
+            .save_outputs(
+                Duration::from_millis(1),
+                &PackageTaskEventBuilder::new("web", "build"),
+            )
+            .await
+            .unwrap();
+
+        let mut expected = expected
+            .iter()
+            .chain(std::iter::once(&"apps/web/.turbo/turbo-build.log"))
+            .map(|path| AnchoredSystemPathBuf::from_raw(path).unwrap())
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(task_cache.expanded_outputs(), expected.as_slice());
+    }
+}
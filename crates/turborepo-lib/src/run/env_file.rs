@@ -0,0 +1,122 @@
+use thiserror::Error;
+use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf};
+use turborepo_env::EnvironmentVariableMap;
+
+use crate::cli::EnvMode;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to read env file {path}: {source}")]
+    Read {
+        path: AbsoluteSystemPathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse env file {path}: {source}")]
+    Parse {
+        path: AbsoluteSystemPathBuf,
+        #[source]
+        source: turborepo_env::Error,
+    },
+}
+
+/// Loads the variables from each `--env-file` in order. Paths are resolved
+/// relative to the repository root and later files override earlier ones.
+pub fn load_env_files(
+    repo_root: &AbsoluteSystemPath,
+    env_files: &[String],
+) -> Result<EnvironmentVariableMap, Error> {
+    let mut env = EnvironmentVariableMap::default();
+    for env_file in env_files {
+        let path = AbsoluteSystemPathBuf::from_unknown(repo_root, env_file);
+        let contents = path.read_to_string().map_err(|source| Error::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let file_env = EnvironmentVariableMap::from_env_file_contents(&contents)
+            .map_err(|source| Error::Parse { path, source })?;
+        env.union(&file_env);
+    }
+
+    Ok(env)
+}
+
+/// Resolves the variables loaded from env files against the environment turbo
+/// was started with. In strict mode a variable that is already set in the
+/// environment takes precedence over the value loaded from a file, in loose
+/// mode the file wins.
+pub fn resolve_env_files(
+    mut file_env: EnvironmentVariableMap,
+    env_at_execution_start: &EnvironmentVariableMap,
+    env_mode: EnvMode,
+) -> EnvironmentVariableMap {
+    if env_mode == EnvMode::Loose {
+        return file_env;
+    }
+    for (key, value) in file_env.iter_mut() {
+        if let Some(process_value) = env_at_execution_start.get(key) {
+            value.clone_from(process_value);
+        }
+    }
+    file_env
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+    use turbopath::AbsoluteSystemPathBuf;
+    use turborepo_env::EnvironmentVariableMap;
+
+    use super::{load_env_files, resolve_env_files};
+    use crate::cli::EnvMode;
+
+    #[test]
+    fn test_later_env_file_overrides() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tempdir.path()).unwrap();
+        repo_root
+            .join_component(".env")
+            .create_with_contents("FOO=first\nBAR=bar\n")
+            .unwrap();
+        repo_root
+            .join_component(".env.local")
+            .create_with_contents("FOO=second\n")
+            .unwrap();
+
+        let env =
+            load_env_files(&repo_root, &[".env".to_string(), ".env.local".to_string()]).unwrap();
+
+        assert_eq!(env.get("FOO").map(String::as_str), Some("second"));
+        assert_eq!(env.get("BAR").map(String::as_str), Some("bar"));
+    }
+
+    #[test]
+    fn test_missing_env_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tempdir.path()).unwrap();
+
+        assert!(load_env_files(&repo_root, &[".env".to_string()]).is_err());
+    }
+
+    #[test_case(EnvMode::Strict, "process" ; "strict")]
+    #[test_case(EnvMode::Loose, "file" ; "loose")]
+    fn test_env_file_precedence(env_mode: EnvMode, expected: &str) {
+        let file_env = EnvironmentVariableMap::from(HashMap::from([
+            ("FOO".to_string(), "file".to_string()),
+            ("BAR".to_string(), "file".to_string()),
+        ]));
+        let process_env = EnvironmentVariableMap::from(HashMap::from([
+            ("FOO".to_string(), "process".to_string()),
+            ("UNRELATED".to_string(), "process".to_string()),
+        ]));
+
+        let env = resolve_env_files(file_env, &process_env, env_mode);
+
+        assert_eq!(env.get("FOO").map(String::as_str), Some(expected));
+        assert_eq!(env.get("BAR").map(String::as_str), Some("file"));
+        // Only variables named in a file are resolved
+        assert_eq!(env.get("UNRELATED"), None);
+    }
+}
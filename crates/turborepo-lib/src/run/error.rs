@@ -8,7 +8,7 @@ use crate::{
     config, daemon, engine,
     engine::ValidateError,
     opts,
-    run::{global_hash, scope},
+    run::{env_file, global_hash, scope},
     task_graph, task_hash,
 };
 
@@ -24,6 +24,8 @@ pub enum Error {
     #[error(transparent)]
     Env(#[from] turborepo_env::Error),
     #[error(transparent)]
+    EnvFile(#[from] env_file::Error),
+    #[error(transparent)]
     Opts(#[from] opts::Error),
     #[error(transparent)]
     #[diagnostic(transparent)]
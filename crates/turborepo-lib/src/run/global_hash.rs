@@ -8,7 +8,7 @@ use globwalk::{ValidatedGlob, WalkType};
 use itertools::Itertools;
 use thiserror::Error;
 use tracing::debug;
-use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, RelativeUnixPathBuf};
+use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, RelativeUnixPath, RelativeUnixPathBuf};
 use turborepo_env::{get_global_hashable_env_vars, DetailedMap, EnvironmentVariableMap};
 use turborepo_lockfiles::Lockfile;
 use turborepo_repository::{
@@ -38,6 +38,16 @@ pub enum Error {
     Scm(#[from] turborepo_scm::Error),
     #[error(transparent)]
     PackageManager(#[from] turborepo_repository::package_manager::Error),
+    #[error(transparent)]
+    Path(#[from] turbopath::PathError),
+    #[error("globalEnvFile {0} does not exist")]
+    MissingGlobalEnvFile(AbsoluteSystemPathBuf),
+    #[error("unable to read globalEnvFile {path}: {source}")]
+    ReadGlobalEnvFile {
+        path: AbsoluteSystemPathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 #[derive(Debug)]
@@ -67,6 +77,7 @@ pub fn get_global_hash_inputs<'a, L: ?Sized + Lockfile>(
     lockfile: Option<&L>,
     global_file_dependencies: &'a [String],
     env_at_execution_start: &'a EnvironmentVariableMap,
+    env_from_files: &EnvironmentVariableMap,
     global_env: &'a [String],
     global_pass_through_env: Option<&'a [String]>,
     env_mode: EnvMode,
@@ -75,8 +86,19 @@ pub fn get_global_hash_inputs<'a, L: ?Sized + Lockfile>(
 ) -> Result<GlobalHashableInputs<'a>, Error> {
     let engines = root_package.package_json.engines();
 
-    let global_hashable_env_vars =
+    let mut global_hashable_env_vars =
         get_global_hashable_env_vars(env_at_execution_start, global_env)?;
+    // Variables loaded with --env-file are always part of the global env, even
+    // if they aren't listed in globalEnv
+    global_hashable_env_vars.all.union(env_from_files);
+    global_hashable_env_vars
+        .by_source
+        .explicit
+        .union(env_from_files);
+    global_hashable_env_vars
+        .by_source
+        .matching
+        .difference(env_from_files);
 
     debug!(
         "global hash env vars {:?}",
@@ -121,6 +143,38 @@ pub fn get_global_hash_inputs<'a, L: ?Sized + Lockfile>(
     })
 }
 
+/// Combines `globalEnv` with the variable names listed in `globalEnvFile`,
+/// one per line. Blank lines and lines starting with `#` are skipped.
+pub fn resolve_global_env(
+    root_path: &AbsoluteSystemPath,
+    global_env: &[String],
+    global_env_file: Option<&str>,
+) -> Result<Vec<String>, Error> {
+    let Some(global_env_file) = global_env_file else {
+        return Ok(global_env.to_vec());
+    };
+    let path = root_path.join_unix_path(RelativeUnixPath::new(global_env_file)?);
+    let contents = path
+        .read_existing_to_string()
+        .map_err(|source| Error::ReadGlobalEnvFile {
+            path: path.clone(),
+            source,
+        })?
+        .ok_or_else(|| Error::MissingGlobalEnvFile(path.clone()))?;
+
+    let mut env = global_env.to_vec();
+    env.extend(
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_owned),
+    );
+    env.sort();
+    env.dedup();
+    Ok(env)
+}
+
 fn collect_global_deps(
     package_manager: &PackageManager,
     root_path: &AbsoluteSystemPath,
@@ -206,14 +260,20 @@ impl<'a> GlobalHashableInputs<'a> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
     use turbopath::AbsoluteSystemPathBuf;
     use turborepo_env::EnvironmentVariableMap;
     use turborepo_lockfiles::Lockfile;
     use turborepo_repository::{package_graph::PackageInfo, package_manager::PackageManager};
     use turborepo_scm::SCM;
 
-    use super::get_global_hash_inputs;
-    use crate::{cli::EnvMode, run::global_hash::collect_global_deps};
+    use super::{get_global_hash_inputs, resolve_global_env, Error};
+    use crate::{
+        cli::EnvMode,
+        run::{env_file::resolve_env_files, global_hash::collect_global_deps},
+    };
 
     #[test]
     fn test_absolute_path() {
@@ -246,6 +306,7 @@ mod tests {
             lockfile,
             &file_deps,
             &env_var_map,
+            &EnvironmentVariableMap::default(),
             &[],
             None,
             EnvMode::Strict,
@@ -255,6 +316,125 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_global_env_file_in_strict_mode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        root.join_component("package.json")
+            .create_with_contents("{}")
+            .unwrap();
+        root.join_component(".env.allowlist")
+            .create_with_contents("# shared allowlist\nFROM_FILE\n\nFROM_TURBO_JSON\n")
+            .unwrap();
+
+        let global_env = resolve_global_env(
+            &root,
+            &["FROM_TURBO_JSON".to_string()],
+            Some(".env.allowlist"),
+        )
+        .unwrap();
+        assert_eq!(global_env, ["FROM_FILE", "FROM_TURBO_JSON"]);
+
+        let env_at_execution_start = EnvironmentVariableMap::from(
+            [
+                ("FROM_FILE".to_string(), "1".to_string()),
+                ("FROM_TURBO_JSON".to_string(), "2".to_string()),
+                ("UNLISTED".to_string(), "3".to_string()),
+            ]
+            .into_iter()
+            .collect::<std::collections::HashMap<_, _>>(),
+        );
+        let package_info = PackageInfo::default();
+        let lockfile: Option<&dyn Lockfile> = None;
+        let inputs = get_global_hash_inputs(
+            None,
+            None,
+            &package_info,
+            &root,
+            &PackageManager::Pnpm,
+            lockfile,
+            &[],
+            &env_at_execution_start,
+            &EnvironmentVariableMap::default(),
+            &global_env,
+            None,
+            EnvMode::Strict,
+            false,
+            &SCM::new(&root),
+        )
+        .unwrap();
+        let names = inputs.resolved_env_vars.unwrap().all.names();
+        assert_eq!(names, ["FROM_FILE", "FROM_TURBO_JSON"]);
+    }
+
+    #[test_case(EnvMode::Loose ; "loose")]
+    #[test_case(EnvMode::Strict ; "strict")]
+    fn test_env_file_vars_are_hashed(env_mode: EnvMode) {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        root.join_component("package.json")
+            .create_with_contents("{}")
+            .unwrap();
+
+        // FROM_BOTH isn't in globalEnv, so strict mode would otherwise filter it out
+        let env_at_execution_start = EnvironmentVariableMap::from(HashMap::from([(
+            "FROM_BOTH".to_string(),
+            "process".to_string(),
+        )]));
+        let package_info = PackageInfo::default();
+        let lockfile: Option<&dyn Lockfile> = None;
+        let global_hash = |file_contents: &str| {
+            let file_env = EnvironmentVariableMap::from_env_file_contents(file_contents).unwrap();
+            let env_from_files = resolve_env_files(file_env, &env_at_execution_start, env_mode);
+            let inputs = get_global_hash_inputs(
+                None,
+                None,
+                &package_info,
+                &root,
+                &PackageManager::Pnpm,
+                lockfile,
+                &[],
+                &env_at_execution_start,
+                &env_from_files,
+                &[],
+                None,
+                env_mode,
+                false,
+                &SCM::new(&root),
+            )
+            .unwrap();
+            let all = inputs.resolved_env_vars.as_ref().unwrap().all.clone();
+            (inputs.calculate_global_hash(), all)
+        };
+
+        let (hash, env) = global_hash("FROM_BOTH=file\nFROM_FILE=one\n");
+        // The environment takes precedence over the file in strict mode only
+        let expected_from_both = match env_mode {
+            EnvMode::Strict => "process",
+            EnvMode::Loose => "file",
+        };
+        assert_eq!(
+            env.get("FROM_BOTH").map(String::as_str),
+            Some(expected_from_both)
+        );
+        assert_eq!(env.get("FROM_FILE").map(String::as_str), Some("one"));
+
+        let (changed_hash, _) = global_hash("FROM_BOTH=file\nFROM_FILE=two\n");
+        assert_ne!(hash, changed_hash);
+        // Changing a value that the environment overrides doesn't change the hash
+        let (overridden_hash, _) = global_hash("FROM_BOTH=other\nFROM_FILE=one\n");
+        assert_eq!(hash == overridden_hash, env_mode == EnvMode::Strict);
+    }
+
+    #[test]
+    fn test_missing_global_env_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+
+        let result = resolve_global_env(&root, &[], Some(".env.allowlist"));
+        assert!(matches!(result, Err(Error::MissingGlobalEnvFile(_))));
+    }
+
     /// get_global_hash_inputs should not yield any folders when walking since
     /// turbo does not consider changes to folders when evaluating hashes,
     /// only to files
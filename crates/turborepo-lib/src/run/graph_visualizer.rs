@@ -33,9 +33,18 @@ pub(crate) fn write_graph(
 ) -> Result<(), Error> {
     match graph_opts {
         GraphOpts::Stdout => render_dot_graph(std::io::stdout(), engine, single_package)?,
+        GraphOpts::Mermaid => engine
+            .mermaid_graph(std::io::stdout(), single_package)
+            .map_err(Error::GraphOutput)?,
+        GraphOpts::Json => {
+            engine
+                .json_graph(std::io::stdout(), single_package)
+                .map_err(Error::GraphOutput)?;
+            println!();
+        }
         GraphOpts::File(raw_filename) => {
             let (filename, extension) = filename_and_extension(cwd, raw_filename)?;
-            if extension == "mermaid" {
+            if extension == "mermaid" || extension == "mmd" {
                 render_mermaid_graph(&filename, engine, single_package)?;
             } else if extension == "html" {
                 render_html(&filename, engine, single_package)?;
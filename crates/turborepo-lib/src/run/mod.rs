@@ -2,6 +2,7 @@
 
 pub mod builder;
 mod cache;
+mod env_file;
 mod error;
 pub(crate) mod global_hash;
 mod graph_visualizer;
@@ -23,7 +24,7 @@ use std::{
 pub use cache::{CacheOutput, ConfigCache, Error as CacheError, RunCache, TaskCache};
 use chrono::{DateTime, Local};
 use rayon::iter::ParallelBridge;
-use tokio::{select, task::JoinHandle};
+use tokio::{select, sync::mpsc, task::JoinHandle};
 use tracing::{debug, instrument};
 use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf};
 use turborepo_api_client::{APIAuth, APIClient};
@@ -43,9 +44,13 @@ use crate::{
     engine::Engine,
     opts::Opts,
     process::ProcessManager,
-    run::{global_hash::get_global_hash_inputs, summary::RunTracker, task_access::TaskAccess},
+    run::{
+        global_hash::{get_global_hash_inputs, resolve_global_env},
+        summary::RunTracker,
+        task_access::TaskAccess,
+    },
     signal::SignalHandler,
-    task_graph::Visitor,
+    task_graph::{TaskTimingEvent, Visitor},
     task_hash::{get_external_deps_hash, get_internal_deps_hash, PackageInputsHashes},
     turbo_json::{TurboJson, UIMode},
     DaemonClient, DaemonConnector,
@@ -73,6 +78,7 @@ pub struct Run {
     task_access: TaskAccess,
     daemon: Option<DaemonClient<DaemonConnector>>,
     should_print_prelude: bool,
+    task_timing_sender: Option<mpsc::Sender<TaskTimingEvent>>,
 }
 
 type UIResult<T> = Result<Option<(T, JoinHandle<Result<(), turborepo_ui::Error>>)>, Error>;
@@ -226,7 +232,8 @@ impl Run {
             UIMode::Tui => self
                 .start_terminal_ui()
                 .map(|res| res.map(|(sender, handle)| (UISender::Tui(sender), handle))),
-            UIMode::Stream => Ok(None),
+            // Task events are written by the visitor, there's no UI to start
+            UIMode::Stream | UIMode::Json => Ok(None),
             UIMode::Web => self
                 .start_web_ui()
                 .map(|res| res.map(|(sender, handle)| (UISender::Wui(sender), handle))),
@@ -386,6 +393,23 @@ impl Run {
             })
             .transpose()?;
 
+        let global_env_names = resolve_global_env(
+            &self.repo_root,
+            &self.root_turbo_json.global_env,
+            self.root_turbo_json.global_env_file.as_deref(),
+        )?;
+        // Variables from --env-file are resolved up front so that they are part of
+        // the global hash and reach every task through the global env
+        let env_from_files =
+            env_file::load_env_files(&self.repo_root, &self.opts.run_opts.env_files).map(
+                |file_env| {
+                    env_file::resolve_env_files(
+                        file_env,
+                        &self.env_at_execution_start,
+                        self.opts.run_opts.env_mode,
+                    )
+                },
+            )?;
         let global_hash_inputs = {
             let env_mode = self.opts.run_opts.env_mode;
             let pass_through_env = match env_mode {
@@ -405,7 +429,8 @@ impl Run {
                 self.pkg_dep_graph.lockfile(),
                 &self.root_turbo_json.global_deps,
                 &self.env_at_execution_start,
-                &self.root_turbo_json.global_env,
+                &env_from_files,
+                &global_env_names,
                 pass_through_env,
                 env_mode,
                 self.opts.run_opts.framework_inference,
@@ -455,6 +480,7 @@ impl Run {
             global_env,
             ui_sender,
             is_watch,
+            self.task_timing_sender.clone(),
         )
         .await;
 
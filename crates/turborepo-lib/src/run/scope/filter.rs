@@ -162,10 +162,22 @@ impl<'a, T: GitChangeDetector> FilterResolver<'a, T> {
     pub(crate) fn resolve(
         &self,
         affected: &Option<(Option<String>, Option<String>)>,
+        only_changed: &Option<Option<String>>,
         patterns: &[String],
     ) -> Result<(HashMap<PackageName, PackageInclusionReason>, bool), ResolutionError> {
+        // Without git there's nothing to compare against, so rather than
+        // silently selecting every package we refuse to run
+        if affected.is_some() && self.scm.is_manual() {
+            return Err(ResolutionError::AffectedRequiresGit(
+                self.turbo_root.to_owned(),
+            ));
+        }
+
         // inference is None only if we are in the root
-        let is_all_packages = patterns.is_empty() && self.inference.is_none() && affected.is_none();
+        let is_all_packages = patterns.is_empty()
+            && self.inference.is_none()
+            && affected.is_none()
+            && only_changed.is_none();
 
         let filter_patterns = if is_all_packages {
             // return all packages in the workspace
@@ -182,7 +194,7 @@ impl<'a, T: GitChangeDetector> FilterResolver<'a, T> {
                 })
                 .collect()
         } else {
-            self.get_packages_from_patterns(affected, patterns)?
+            self.get_packages_from_patterns(affected, only_changed, patterns)?
         };
 
         Ok((filter_patterns, is_all_packages))
@@ -191,6 +203,7 @@ impl<'a, T: GitChangeDetector> FilterResolver<'a, T> {
     fn get_packages_from_patterns(
         &self,
         affected: &Option<(Option<String>, Option<String>)>,
+        only_changed: &Option<Option<String>>,
         patterns: &[String],
     ) -> Result<HashMap<PackageName, PackageInclusionReason>, ResolutionError> {
         let mut selectors = patterns
@@ -212,6 +225,25 @@ impl<'a, T: GitChangeDetector> FilterResolver<'a, T> {
             });
         }
 
+        match only_changed {
+            // Same as passing `--filter "[<ref>]"`
+            Some(Some(from_ref)) => {
+                selectors.push(TargetSelector::from_str(&format!("[{from_ref}]"))?)
+            }
+            // Without a ref we compare against the merge base with the default branch
+            Some(None) => selectors.push(TargetSelector {
+                git_range: Some(GitRange {
+                    from_ref: None,
+                    to_ref: None,
+                    include_uncommitted: true,
+                    allow_unknown_objects: true,
+                    merge_base: true,
+                }),
+                ..Default::default()
+            }),
+            None => (),
+        }
+
         self.get_filtered_packages(selectors)
     }
 
@@ -703,6 +735,11 @@ pub enum ResolutionError {
     DirectoryDoesNotExist(AbsoluteSystemPathBuf),
     #[error("failed to construct glob for globalDependencies")]
     GlobalDependenciesGlob(#[from] turborepo_repository::change_mapper::Error),
+    #[error(
+        "`--affected` requires a git repository, but {0} is not inside one. Use `--filter` to \
+         select packages instead."
+    )]
+    AffectedRequiresGit(AbsoluteSystemPathBuf),
 }
 
 #[cfg(test)]
@@ -1341,6 +1378,69 @@ mod test {
         );
     }
 
+    #[test_case("develop", None, &["package-3", "package-20"] ; "configured base")]
+    #[test_case("develop", Some("my-branch"), &["package-20"] ; "configured base and head")]
+    #[test_case("main", None, &["package-1"] ; "other base")]
+    fn affected_uses_scm_base(base: &str, head: Option<&str>, expected: &[&str]) {
+        let scm_resolver = TestChangeDetector::new(&[
+            ("develop", None, &["package-3"]),
+            ("develop", Some("my-branch"), &["package-20"]),
+            ("main", None, &["package-1"]),
+        ]);
+
+        let (tempdir, resolver) = make_project(
+            &[("package-20", "package-3")],
+            &["package-1", "package-2"],
+            None,
+            scm_resolver,
+        );
+        // `--affected` refuses to run outside of a git repository
+        init_git_repo(&tempdir);
+        let scm = turborepo_scm::SCM::new(resolver.turbo_root);
+        let resolver = FilterResolver::new_with_change_detector(
+            resolver.pkg_graph,
+            resolver.turbo_root,
+            None,
+            &scm,
+            resolver.change_detector,
+        );
+
+        let affected = Some((Some(base.to_string()), head.map(|h| h.to_string())));
+        let (packages, is_all_packages) = resolver.resolve(&affected, &None, &[]).unwrap();
+        assert!(!is_all_packages);
+        assert_eq!(
+            packages.into_keys().collect::<HashSet<_>>(),
+            expected.iter().map(|s| PackageName::from(*s)).collect()
+        );
+    }
+
+    #[test]
+    fn affected_requires_git() {
+        let (_tempdir, resolver) = make_project(
+            &[("package-20", "package-3")],
+            &["package-1", "package-2"],
+            None,
+            TestChangeDetector::new(&[]),
+        );
+
+        let affected = Some((Some("develop".to_string()), None));
+        let result = resolver.resolve(&affected, &None, &[]);
+        assert!(
+            matches!(result, Err(ResolutionError::AffectedRequiresGit(_))),
+            "expected an error for --affected outside of a git repository"
+        );
+    }
+
+    fn init_git_repo(dir: &TempDir) {
+        let status = std::process::Command::new("git")
+            .arg("init")
+            .arg("--quiet")
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
     struct TestChangeDetector<'a>(
         HashMap<(&'a str, Option<&'a str>), HashMap<PackageName, PackageInclusionReason>>,
     );
@@ -36,5 +36,9 @@ pub fn resolve_packages(
         scm,
         root_turbo_json,
     )?
-    .resolve(&opts.affected_range, &opts.get_filters())
+    .resolve(
+        &opts.affected_range,
+        &opts.only_changed,
+        &opts.get_filters(),
+    )
 }
@@ -1,10 +1,10 @@
-use std::fmt;
+use std::{fmt, io};
 
 use chrono::{DateTime, Local};
 use serde::Serialize;
 use tokio::sync::mpsc;
 use turbopath::{AbsoluteSystemPathBuf, AnchoredSystemPath};
-use turborepo_ui::{color, cprintln, ColorConfig, BOLD, BOLD_GREEN, BOLD_RED, MAGENTA, YELLOW};
+use turborepo_ui::{color, cwriteln, ColorConfig, BOLD, BOLD_GREEN, BOLD_RED, MAGENTA, YELLOW};
 
 use super::TurboDuration;
 use crate::run::{summary::task::TaskSummary, task_id::TaskId};
@@ -36,6 +36,12 @@ pub struct ExecutionSummary<'a> {
     failed: usize,
     // number of tasks that had a cache hit
     cached: usize,
+    // number of tasks that missed the cache when only restoring outputs
+    #[serde(skip)]
+    missing: usize,
+    // number of tasks that weren't run because one of their dependencies failed
+    #[serde(skip)]
+    skipped: usize,
     // number of tasks that started
     attempted: usize,
     pub(crate) start_time: i64,
@@ -60,6 +66,8 @@ impl<'a> ExecutionSummary<'a> {
             success: state.success,
             failed: state.failed,
             cached: state.cached,
+            missing: state.missing,
+            skipped: state.skipped,
             attempted: state.attempted,
             // We're either at some path in the repo, or at the root, which is an empty path
             repo_path: package_inference_root.unwrap_or_else(|| AnchoredSystemPath::empty()),
@@ -77,7 +85,8 @@ impl<'a> ExecutionSummary<'a> {
         ui: ColorConfig,
         path: AbsoluteSystemPathBuf,
         failed_tasks: Vec<&TaskSummary>,
-    ) {
+        mut writer: impl io::Write,
+    ) -> io::Result<()> {
         let maybe_full_turbo = if self.cached == self.attempted && self.attempted > 0 {
             match std::env::var("TERM_PROGRAM").as_deref() {
                 Ok("Apple_Terminal") => color!(ui, MAGENTA, ">>> FULL TURBO").to_string(),
@@ -105,15 +114,34 @@ impl<'a> ExecutionSummary<'a> {
                 )
                 .to_string(),
             ),
-            (
-                "Time",
+        ];
+
+        if self.missing > 0 {
+            line_data.push((
+                "Missing",
                 format!(
-                    "{} {}",
-                    color!(ui, BOLD, "{}", self.duration),
-                    maybe_full_turbo
+                    "{}, {} total",
+                    color!(ui, BOLD, "{} missing", self.missing),
+                    self.attempted
                 ),
+            ));
+        }
+
+        if self.skipped > 0 {
+            line_data.push((
+                "Skipped",
+                color!(ui, BOLD, "{} skipped", self.skipped).to_string(),
+            ));
+        }
+
+        line_data.push((
+            "Time",
+            format!(
+                "{} {}",
+                color!(ui, BOLD, "{}", self.duration),
+                maybe_full_turbo
             ),
-        ];
+        ));
 
         if path.exists() {
             line_data.push(("Summary", path.to_string()));
@@ -149,16 +177,21 @@ impl<'a> ExecutionSummary<'a> {
             .collect();
 
         if self.attempted == 0 {
-            println!();
-            cprintln!(ui, YELLOW, "No tasks were executed as part of this run.");
+            writeln!(writer)?;
+            cwriteln!(
+                writer,
+                ui,
+                YELLOW,
+                "No tasks were executed as part of this run."
+            )?;
         }
 
-        println!();
+        writeln!(writer)?;
         for line in lines {
-            println!("{}", line);
+            writeln!(writer, "{}", line)?;
         }
 
-        println!();
+        writeln!(writer)
     }
 
     fn successful(&self) -> usize {
@@ -172,6 +205,8 @@ pub struct SummaryState {
     pub attempted: usize,
     pub failed: usize,
     pub cached: usize,
+    pub missing: usize,
+    pub skipped: usize,
     pub success: usize,
     pub tasks: Vec<TaskState>,
 }
@@ -188,6 +223,8 @@ impl SummaryState {
             Event::Building => self.attempted += 1,
             Event::BuildFailed => self.failed += 1,
             Event::Cached => self.cached += 1,
+            Event::Missing => self.missing += 1,
+            Event::Skipped => self.skipped += 1,
             Event::Built => self.success += 1,
             Event::Canceled => (),
         }
@@ -214,6 +251,10 @@ enum Event {
     Building,
     BuildFailed,
     Cached,
+    // Not found in the cache and not executed
+    Missing,
+    // Not executed because one of its dependencies failed
+    Skipped,
     Built,
     // Canceled due to external signal or internal failure
     Canceled,
@@ -327,6 +368,24 @@ impl TaskTracker<()> {
             .await
             .expect("execution summary state thread finished")
     }
+
+    // Track that the task wasn't run because one of its dependencies failed
+    pub async fn skipped(self) {
+        let Self {
+            sender, task_id, ..
+        } = self;
+
+        sender
+            .send(TrackerMessage {
+                event: Event::Skipped,
+                state: Some(TaskState {
+                    task_id,
+                    execution: None,
+                }),
+            })
+            .await
+            .expect("execution summary state thread finished")
+    }
 }
 
 impl TaskTracker<chrono::DateTime<Local>> {
@@ -364,6 +423,24 @@ impl TaskTracker<chrono::DateTime<Local>> {
         execution
     }
 
+    // Track that the task wasn't in the cache and was skipped instead of executed
+    pub async fn missing(self) {
+        let Self {
+            sender, task_id, ..
+        } = self;
+
+        sender
+            .send(TrackerMessage {
+                event: Event::Missing,
+                state: Some(TaskState {
+                    task_id,
+                    execution: None,
+                }),
+            })
+            .await
+            .expect("summary state thread finished")
+    }
+
     pub async fn build_succeeded(self, exit_code: i32) -> TaskExecutionSummary {
         let Self {
             sender,
@@ -442,6 +519,8 @@ mod test {
         let bar = TaskId::new("bar", "build");
         let baz = TaskId::new("baz", "build");
         let boo = TaskId::new("boo", "build");
+        let qux = TaskId::new("qux", "build");
+        let skipped = TaskId::new("skipped", "build");
         let mut tasks = Vec::new();
         {
             let tracker = summary.task_tracker(foo.clone());
@@ -471,13 +550,28 @@ mod test {
                 tracker.cancel();
             }));
         }
+        {
+            let tracker = summary.task_tracker(qux.clone());
+            tasks.push(tokio::spawn(async move {
+                let tracker = tracker.start().await;
+                tracker.missing().await;
+            }));
+        }
+        {
+            let tracker = summary.task_tracker(skipped.clone());
+            tasks.push(tokio::spawn(async move {
+                tracker.skipped().await;
+            }));
+        }
         for task in tasks {
             task.await.unwrap();
         }
 
         let state = summary.finish().await.unwrap();
-        assert_eq!(state.attempted, 4);
+        assert_eq!(state.attempted, 5);
         assert_eq!(state.cached, 1);
+        assert_eq!(state.missing, 1);
+        assert_eq!(state.skipped, 1);
         assert_eq!(state.failed, 1);
         assert_eq!(state.success, 1);
         let foo_state = state.tasks.iter().find(|task| task.task_id == foo).unwrap();
@@ -491,6 +585,20 @@ mod test {
             boo_state.is_none(),
             "canceling doesn't produce execution data"
         );
+        let qux_state = state.tasks.iter().find(|task| task.task_id == qux).unwrap();
+        assert!(
+            qux_state.execution.is_none(),
+            "missing tasks weren't executed"
+        );
+        let skipped_state = state
+            .tasks
+            .iter()
+            .find(|task| task.task_id == skipped)
+            .unwrap();
+        assert!(
+            skipped_state.execution.is_none(),
+            "skipped tasks weren't executed"
+        );
     }
 
     #[tokio::test]
@@ -11,6 +11,8 @@ mod scm;
 mod spaces;
 mod task;
 mod task_factory;
+mod trace;
+mod why_hash;
 use std::{collections::HashSet, io, io::Write};
 
 use chrono::{DateTime, Local};
@@ -22,6 +24,7 @@ use serde::Serialize;
 pub use spaces::{SpacesTaskClient, SpacesTaskInformation};
 use svix_ksuid::{Ksuid, KsuidLike};
 use tabwriter::TabWriter;
+pub use task::TaskSummaryTaskDefinition;
 use thiserror::Error;
 use tracing::{error, log::warn};
 use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPath};
@@ -30,6 +33,7 @@ use turborepo_env::EnvironmentVariableMap;
 use turborepo_repository::package_graph::{PackageGraph, PackageName};
 use turborepo_scm::SCM;
 use turborepo_ui::{color, cprintln, cwriteln, ColorConfig, BOLD, BOLD_CYAN, GREY};
+pub use why_hash::print_why_hash;
 
 use self::{
     execution::TaskState, task::SinglePackageTaskSummary, task_factory::TaskSummaryFactory,
@@ -39,7 +43,7 @@ use crate::{
     cli,
     cli::{DryRunMode, EnvMode},
     engine::Engine,
-    opts::RunOpts,
+    opts::{ResolvedLogOrder, RunOpts},
     run::summary::{
         execution::{ExecutionSummary, ExecutionTracker},
         scm::SCMState,
@@ -81,6 +85,7 @@ enum RunType {
     Real,
     DryText,
     DryJson,
+    DryNdjson,
 }
 
 #[derive(Debug, Serialize)]
@@ -98,6 +103,8 @@ pub struct RunSummary<'a> {
     env_mode: EnvMode,
     framework_inference: bool,
     tasks: Vec<TaskSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<TaskWarningSummary>,
     user: String,
     scm: SCMState,
     #[serde(skip)]
@@ -108,6 +115,23 @@ pub struct RunSummary<'a> {
     run_type: RunType,
     #[serde(skip)]
     spaces_client_handle: Option<SpacesClientHandle>,
+    #[serde(skip)]
+    task_trace: Option<AbsoluteSystemPathBuf>,
+    #[serde(skip)]
+    summary_path: Option<AbsoluteSystemPathBuf>,
+    // When tasks write JSON events to stdout, everything else is written to
+    // stderr so stdout stays parseable
+    #[serde(skip)]
+    stdout_is_events: bool,
+}
+
+/// A warning collected while running a task. These are included in the run
+/// summary so that CI can gate on them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskWarningSummary {
+    pub task_id: String,
+    pub missing_platform_env: Vec<String>,
 }
 
 /// We use this to track the run, so it's constructed before the run.
@@ -174,6 +198,7 @@ impl RunTracker {
         packages,
         global_hash_summary,
         task_factory,
+        warnings,
     ))]
     pub async fn to_summary<'a>(
         self,
@@ -186,6 +211,7 @@ impl RunTracker {
         global_hash_summary: GlobalHashSummary<'a>,
         global_env_mode: EnvMode,
         task_factory: TaskSummaryFactory<'a>,
+        warnings: Vec<TaskWarningSummary>,
     ) -> Result<RunSummary<'a>, Error> {
         let single_package = run_opts.single_package;
         let should_save = run_opts.summarize;
@@ -194,6 +220,7 @@ impl RunTracker {
             None => RunType::Real,
             Some(DryRunMode::Json) => RunType::DryJson,
             Some(DryRunMode::Text) => RunType::DryText,
+            Some(DryRunMode::Ndjson) => RunType::DryNdjson,
         };
 
         let summary_state = self.execution_tracker.finish().await?;
@@ -222,6 +249,7 @@ impl RunTracker {
             env_mode: global_env_mode,
             framework_inference: run_opts.framework_inference,
             tasks,
+            warnings,
             global_hash_summary,
             scm: self.scm,
             user: self.user,
@@ -230,6 +258,15 @@ impl RunTracker {
             should_save,
             run_type,
             spaces_client_handle: self.spaces_client_handle,
+            task_trace: run_opts
+                .task_trace
+                .as_deref()
+                .map(|file| AbsoluteSystemPathBuf::from_unknown(repo_root, file)),
+            summary_path: run_opts
+                .summary_path
+                .as_deref()
+                .map(|file| AbsoluteSystemPathBuf::from_unknown(repo_root, file)),
+            stdout_is_events: run_opts.log_order == ResolvedLogOrder::Json,
         })
     }
 
@@ -241,7 +278,8 @@ impl RunTracker {
         global_hash_summary,
         engine,
         hash_tracker,
-        env_at_execution_start
+        env_at_execution_start,
+        warnings
     ))]
     #[allow(clippy::too_many_arguments)]
     pub async fn finish<'a>(
@@ -258,6 +296,7 @@ impl RunTracker {
         engine: &'a Engine,
         hash_tracker: TaskHashTracker,
         env_at_execution_start: &'a EnvironmentVariableMap,
+        warnings: Vec<TaskWarningSummary>,
         is_watch: bool,
     ) -> Result<(), Error> {
         let end_time = Local::now();
@@ -282,6 +321,7 @@ impl RunTracker {
                 global_hash_summary,
                 global_env_mode,
                 task_factory,
+                warnings,
             )
             .await?;
 
@@ -305,6 +345,50 @@ impl RunTracker {
     }
 }
 
+/// Renders task summaries as single lines of JSON so that `--dry-run=ndjson`
+/// can write out each task as soon as it's planned instead of buffering the
+/// whole run summary.
+pub struct NdjsonTaskSummaries<'a> {
+    task_factory: TaskSummaryFactory<'a>,
+    single_package: bool,
+}
+
+impl<'a> NdjsonTaskSummaries<'a> {
+    pub fn new(
+        pkg_dep_graph: &'a PackageGraph,
+        engine: &'a Engine,
+        hash_tracker: TaskHashTracker,
+        env_at_execution_start: &'a EnvironmentVariableMap,
+        run_opts: &'a RunOpts,
+        global_env_mode: EnvMode,
+    ) -> Self {
+        let task_factory = TaskSummaryFactory::new(
+            pkg_dep_graph,
+            engine,
+            hash_tracker,
+            env_at_execution_start,
+            run_opts,
+            global_env_mode,
+        );
+        Self {
+            task_factory,
+            single_package: run_opts.single_package,
+        }
+    }
+
+    pub fn format_task(&self, task_id: TaskId<'static>) -> Result<String, Error> {
+        let mut task = self.task_factory.task_summary(task_id, None)?;
+        task.shared.dependencies.sort();
+        task.shared.dependents.sort();
+
+        Ok(if self.single_package {
+            serde_json::to_string(&SinglePackageTaskSummary::from(task))
+        } else {
+            serde_json::to_string(&task)
+        }?)
+    }
+}
+
 // This is an exact copy of RunSummary, but the JSON tags are structured
 // for rendering a single-package run of turbo. Notably, we want to always omit
 // packages since there is no concept of packages in a single-workspace repo.
@@ -324,6 +408,8 @@ struct SinglePackageRunSummary<'a> {
     env_mode: EnvMode,
     framework_inference: bool,
     tasks: Vec<SinglePackageTaskSummary>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    warnings: &'a [TaskWarningSummary],
     user: &'a str,
     pub scm: &'a SCMState,
 }
@@ -347,6 +433,7 @@ impl<'a> From<&'a RunSummary<'a>> for SinglePackageRunSummary<'a> {
             env_mode: run_summary.env_mode,
             framework_inference: run_summary.framework_inference,
             tasks,
+            warnings: &run_summary.warnings,
             user: &run_summary.user,
             scm: &run_summary.scm,
         }
@@ -363,7 +450,10 @@ impl<'a> RunSummary<'a> {
         ui: ColorConfig,
         is_watch: bool,
     ) -> Result<(), Error> {
-        if matches!(self.run_type, RunType::DryJson | RunType::DryText) {
+        if matches!(
+            self.run_type,
+            RunType::DryJson | RunType::DryText | RunType::DryNdjson
+        ) {
             return self.close_dry_run(pkg_dep_graph, ui);
         }
 
@@ -373,11 +463,23 @@ impl<'a> RunSummary<'a> {
             }
         }
 
+        if let Some(task_trace) = &self.task_trace {
+            let tasks = self.tasks.iter().filter_map(|task| {
+                let execution = task.shared.execution.as_ref()?;
+                Some((&task.task_id, execution))
+            });
+            if let Err(err) = trace::write_task_trace(task_trace, tasks) {
+                warn!("Error writing task trace: {}", err)
+            }
+        }
+
         if !is_watch {
             if let Some(execution) = &self.execution {
                 let path = self.get_path();
                 let failed_tasks = self.get_failed_tasks();
-                execution.print(ui, path, failed_tasks);
+                if let Err(err) = execution.print(ui, path, failed_tasks, self.output()) {
+                    warn!("Error printing run summary: {}", err)
+                }
             }
         }
 
@@ -422,11 +524,19 @@ impl<'a> RunSummary<'a> {
             Self::print_errors(&result.errors);
 
             if let Some(run) = result.run {
-                println!("Run: {}\n", run.url);
+                writeln!(self.output(), "Run: {}\n", run.url).ok();
             }
         }
     }
 
+    fn output(&self) -> Box<dyn Write> {
+        if self.stdout_is_events {
+            Box::new(io::stderr())
+        } else {
+            Box::new(io::stdout())
+        }
+    }
+
     fn print_errors(errors: &[Error]) {
         if errors.is_empty() {
             return;
@@ -449,6 +559,11 @@ impl<'a> RunSummary<'a> {
             return Ok(());
         }
 
+        // Tasks were already written out by the visitor as they were planned
+        if matches!(self.run_type, RunType::DryNdjson) {
+            return Ok(());
+        }
+
         self.format_and_print_text(pkg_dep_graph, ui)
     }
 
@@ -774,6 +889,10 @@ impl<'a> RunSummary<'a> {
     }
 
     fn get_path(&self) -> AbsoluteSystemPathBuf {
+        if let Some(summary_path) = &self.summary_path {
+            return summary_path.clone();
+        }
+
         let filename = format!("{}.json", self.id);
 
         self.repo_root
@@ -801,3 +920,78 @@ impl<'a> RunSummary<'a> {
         Ok(summary_path.create_with_contents(json)?)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+    use test_case::test_case;
+
+    use super::*;
+    use crate::run::summary::global_hash::{GlobalEnvConfiguration, GlobalEnvVarSummary};
+
+    #[test_case(
+        vec![TaskWarningSummary {
+            task_id: "web#build".into(),
+            missing_platform_env: vec!["VERCEL_URL".into()],
+        }],
+        Some(json!([{ "taskId": "web#build", "missingPlatformEnv": ["VERCEL_URL"] }]))
+        ; "warnings"
+    )]
+    #[test_case(Vec::new(), None ; "no warnings")]
+    fn test_run_summary_warnings(
+        warnings: Vec<TaskWarningSummary>,
+        expected: Option<serde_json::Value>,
+    ) {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPath::from_std_path(tmp.path()).unwrap();
+        let packages = [PackageName::from("web")];
+        let run_summary = RunSummary {
+            id: Ksuid::new(None, None),
+            version: RUN_SUMMARY_SCHEMA_VERSION.to_string(),
+            turbo_version: "2.0.0",
+            monorepo: true,
+            global_hash_summary: GlobalHashSummary {
+                root_key: "root key",
+                files: BTreeMap::new(),
+                hash_of_external_dependencies: "",
+                hash_of_internal_dependencies: "",
+                environment_variables: GlobalEnvVarSummary {
+                    specified: GlobalEnvConfiguration {
+                        env: &[],
+                        pass_through_env: None,
+                    },
+                    configured: None,
+                    inferred: None,
+                    pass_through: None,
+                },
+                engines: None,
+            },
+            execution: None,
+            packages: packages.iter().collect(),
+            env_mode: EnvMode::Strict,
+            framework_inference: true,
+            tasks: Vec::new(),
+            warnings,
+            user: "user".into(),
+            scm: SCMState::get(
+                &EnvironmentVariableMap::default(),
+                &SCM::new(repo_root),
+                repo_root,
+            ),
+            repo_root,
+            should_save: false,
+            run_type: RunType::Real,
+            spaces_client_handle: None,
+            task_trace: None,
+            summary_path: None,
+            stdout_is_events: false,
+        };
+
+        let summary = serde_json::to_value(&run_summary).unwrap();
+        // Warnings are a top level key of the summary, next to `tasks`
+        assert_eq!(summary.get("warnings"), expected.as_ref());
+        assert!(summary.get("tasks").is_some());
+    }
+}
@@ -9,7 +9,8 @@ use super::{execution::TaskExecutionSummary, EnvMode};
 use crate::{
     cli::OutputLogsMode,
     run::task_id::TaskId,
-    task_graph::{TaskDefinition, TaskOutputs},
+    task_graph::{ReadyProbe, TaskDefinition, TaskOutputs},
+    task_hash::TaskHashInputs,
 };
 
 #[derive(Debug, Serialize, Clone)]
@@ -83,6 +84,9 @@ pub(crate) struct SharedTaskSummary<T> {
     pub environment_variables: TaskEnvVarSummary,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub execution: Option<TaskExecutionSummary>,
+    // Named `hashInputs` because `inputs` already lists the task's input files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash_inputs: Option<TaskHashInputs>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -103,9 +107,21 @@ pub struct TaskSummaryTaskDefinition {
     persistent: bool,
     interruptible: bool,
     env: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    env_values: BTreeMap<String, String>,
     pass_through_env: Option<Vec<String>>,
     interactive: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    confirm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ready: Option<ReadyProbe>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_streams: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_replay: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     env_mode: Option<EnvMode>,
 }
 
@@ -234,6 +250,7 @@ impl From<SharedTaskSummary<TaskId<'static>>> for SharedTaskSummary<String> {
             execution,
             env_mode,
             environment_variables,
+            hash_inputs,
             ..
         } = value;
         Self {
@@ -261,6 +278,7 @@ impl From<SharedTaskSummary<TaskId<'static>>> for SharedTaskSummary<String> {
             execution,
             env_mode,
             environment_variables,
+            hash_inputs,
         }
     }
 }
@@ -275,6 +293,7 @@ impl From<TaskDefinition> for TaskSummaryTaskDefinition {
                 },
             cache,
             mut env,
+            env_values,
             pass_through_env,
             topological_dependencies,
             task_dependencies,
@@ -283,6 +302,11 @@ impl From<TaskDefinition> for TaskSummaryTaskDefinition {
             persistent,
             interruptible,
             interactive,
+            confirm,
+            ready,
+            merge_streams,
+            log_replay,
+            timeout,
             env_mode,
         } = value;
 
@@ -317,7 +341,13 @@ impl From<TaskDefinition> for TaskSummaryTaskDefinition {
             persistent,
             interruptible,
             interactive,
+            confirm,
+            ready,
+            merge_streams,
+            log_replay,
+            timeout: timeout.map(|timeout| humantime::format_duration(timeout).to_string()),
             env,
+            env_values,
             pass_through_env,
             env_mode,
         }
@@ -382,6 +412,36 @@ mod test {
         })
         ; "resolved task definition"
     )]
+    #[test_case(
+        TaskHashInputs {
+            global_hash: "global".into(),
+            hash_of_files: "files".into(),
+            external_deps_hash: Some("external".into()),
+            task_dependency_hashes: vec!["dep".into()],
+            outputs: TaskOutputs {
+                inclusions: vec!["dist/**".into()],
+                exclusions: vec![],
+            },
+            pass_through_args: vec![],
+            env: vec!["API_*".into()],
+            resolved_env_vars: vec!["API_URL=hashed".into()],
+            pass_through_env: vec![],
+            env_mode: EnvMode::Strict,
+        },
+        json!({
+            "globalHash": "global",
+            "hashOfFiles": "files",
+            "externalDepsHash": "external",
+            "taskDependencyHashes": ["dep"],
+            "outputs": { "inclusions": ["dist/**"], "exclusions": [] },
+            "passThroughArgs": [],
+            "env": ["API_*"],
+            "resolvedEnvVars": ["API_URL=hashed"],
+            "passThroughEnv": [],
+            "envMode": "strict",
+        })
+        ; "hash inputs"
+    )]
     fn test_serialization(value: impl serde::Serialize, expected: serde_json::Value) {
         assert_eq!(serde_json::to_value(value).unwrap(), expected);
     }
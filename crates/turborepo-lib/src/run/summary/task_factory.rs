@@ -184,6 +184,7 @@ impl<'a> TaskSummaryFactory<'a> {
             )
             .expect("invalid glob in task definition should have been caught earlier"),
             execution,
+            hash_inputs: self.hash_tracker.hash_inputs(task_id),
         })
     }
 
@@ -0,0 +1,141 @@
+//! Writes task executions as a Chrome trace so that a run's timeline can be
+//! loaded in chrome://tracing or https://ui.perfetto.dev
+use serde::Serialize;
+use turbopath::AbsoluteSystemPath;
+
+use super::{execution::TaskExecutionSummary, Error};
+use crate::run::task_id::TaskId;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskTrace {
+    trace_events: Vec<TraceEvent>,
+    display_time_unit: &'static str,
+}
+
+/// A Chrome trace "complete" event, timestamps are in microseconds
+#[derive(Debug, Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,
+    ts: i64,
+    dur: i64,
+    pid: u32,
+    tid: usize,
+    args: TraceEventArgs,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceEventArgs {
+    exit_code: Option<i32>,
+}
+
+pub fn write_task_trace<'a>(
+    path: &AbsoluteSystemPath,
+    tasks: impl IntoIterator<Item = (&'a TaskId<'static>, &'a TaskExecutionSummary)>,
+) -> Result<(), Error> {
+    let trace = serde_json::to_string(&task_trace(tasks))?;
+    path.ensure_dir()?;
+    path.create_with_contents(trace)?;
+    Ok(())
+}
+
+fn task_trace<'a>(
+    tasks: impl IntoIterator<Item = (&'a TaskId<'static>, &'a TaskExecutionSummary)>,
+) -> TaskTrace {
+    let mut tasks = tasks.into_iter().collect::<Vec<_>>();
+    tasks.sort_by_key(|(task_id, execution)| (execution.start_time, task_id.to_string()));
+
+    // Tasks that overlap are placed on separate lanes so that the trace viewer
+    // doesn't try to nest them. Each lane holds the end time of its last task.
+    let mut lanes: Vec<i64> = Vec::new();
+    let trace_events = tasks
+        .into_iter()
+        .map(|(task_id, execution)| {
+            let tid = match lanes
+                .iter()
+                .position(|lane_end| *lane_end <= execution.start_time)
+            {
+                Some(lane) => lane,
+                None => {
+                    lanes.push(execution.end_time);
+                    lanes.len() - 1
+                }
+            };
+            lanes[tid] = execution.end_time;
+
+            TraceEvent {
+                name: task_id.to_string(),
+                cat: "task",
+                ph: "X",
+                ts: execution.start_time * 1000,
+                dur: (execution.end_time - execution.start_time) * 1000,
+                pid: 1,
+                tid,
+                args: TraceEventArgs {
+                    exit_code: execution.exit_code,
+                },
+            }
+        })
+        .collect();
+
+    TaskTrace {
+        trace_events,
+        display_time_unit: "ms",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn execution(start_time: i64, end_time: i64, exit_code: i32) -> TaskExecutionSummary {
+        TaskExecutionSummary {
+            start_time,
+            end_time,
+            error: None,
+            exit_code: Some(exit_code),
+        }
+    }
+
+    #[test]
+    fn test_task_trace() {
+        let lib = TaskId::new("lib", "build");
+        let web = TaskId::new("web", "build");
+        let docs = TaskId::new("docs", "build");
+        let lib_execution = execution(1000, 1500, 0);
+        let docs_execution = execution(1100, 1300, 1);
+        let web_execution = execution(1500, 2250, 0);
+
+        let trace = task_trace([
+            (&web, &web_execution),
+            (&lib, &lib_execution),
+            (&docs, &docs_execution),
+        ]);
+
+        assert_eq!(
+            serde_json::to_value(trace).unwrap(),
+            json!({
+                "traceEvents": [
+                    {
+                        "name": "lib#build", "cat": "task", "ph": "X", "ts": 1_000_000,
+                        "dur": 500_000, "pid": 1, "tid": 0, "args": { "exitCode": 0 }
+                    },
+                    {
+                        "name": "docs#build", "cat": "task", "ph": "X", "ts": 1_100_000,
+                        "dur": 200_000, "pid": 1, "tid": 1, "args": { "exitCode": 1 }
+                    },
+                    {
+                        "name": "web#build", "cat": "task", "ph": "X", "ts": 1_500_000,
+                        "dur": 750_000, "pid": 1, "tid": 0, "args": { "exitCode": 0 }
+                    },
+                ],
+                "displayTimeUnit": "ms",
+            })
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Output for `--why-hash`, which prints the values that went into a task's
+//! hash and compares them with the last run summary that recorded them.
+use std::{io, io::Write};
+
+use serde::Deserialize;
+use tabwriter::TabWriter;
+use tracing::debug;
+use turbopath::AbsoluteSystemPath;
+use turborepo_ui::{cprintln, cwriteln, ColorConfig, BOLD, BOLD_CYAN, GREY, YELLOW};
+
+use super::Error;
+use crate::{
+    engine::TaskNode,
+    opts::RunOpts,
+    run::task_id::TaskId,
+    task_hash::{TaskHashInputs, TaskHashTracker},
+};
+
+// Only the parts of a saved run summary that are needed for the comparison
+#[derive(Debug, Deserialize)]
+struct SavedRunSummary {
+    id: String,
+    tasks: Vec<SavedTaskSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedTaskSummary {
+    task_id: String,
+    hash: String,
+    hash_inputs: Option<TaskHashInputs>,
+}
+
+#[derive(Debug)]
+struct PreviousHash {
+    run_id: String,
+    hash: String,
+    hash_inputs: TaskHashInputs,
+}
+
+pub fn print_why_hash<'a>(
+    repo_root: &AbsoluteSystemPath,
+    run_opts: &RunOpts,
+    tasks: impl Iterator<Item = &'a TaskNode>,
+    task_hash_tracker: &TaskHashTracker,
+    ui: ColorConfig,
+) -> Result<(), Error> {
+    let mut task_ids = tasks
+        .filter_map(|task| match task {
+            TaskNode::Task(task_id) if run_opts.explains_hash_of(task_id) => Some(task_id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    task_ids.sort();
+
+    let why_hash = run_opts.why_hash.as_deref().unwrap_or_default();
+    if task_ids.is_empty() {
+        cprintln!(ui, YELLOW, "\n{why_hash} is not part of this run");
+        return Ok(());
+    }
+
+    for task_id in task_ids {
+        let (Some(hash), Some(hash_inputs)) = (
+            task_hash_tracker.hash(task_id),
+            task_hash_tracker.hash_inputs(task_id),
+        ) else {
+            continue;
+        };
+
+        println!();
+        cprintln!(ui, BOLD_CYAN, "Hash of {task_id}");
+        let mut tab_writer = TabWriter::new(io::stdout()).minwidth(0).padding(1);
+        cwriteln!(tab_writer, ui, GREY, "  Hash\t=\t{hash}")?;
+        for (label, value) in hash_inputs.components() {
+            cwriteln!(tab_writer, ui, GREY, "  {label}\t=\t{value}")?;
+        }
+        tab_writer.flush()?;
+
+        let Some(previous) = find_previous_hash(repo_root, task_id) else {
+            cprintln!(
+                ui,
+                YELLOW,
+                "No previous run summary recorded the hash inputs of {task_id}. Run with \
+                 --why-hash and --summarize to record them"
+            );
+            continue;
+        };
+        if previous.hash == hash {
+            cprintln!(ui, BOLD, "Unchanged since run {}", previous.run_id);
+            continue;
+        }
+
+        cprintln!(
+            ui,
+            BOLD,
+            "Changed since run {} (hash was {})",
+            previous.run_id,
+            previous.hash
+        );
+        let mut tab_writer = TabWriter::new(io::stdout()).minwidth(0).padding(1);
+        for (label, before, after) in changed_components(&previous.hash_inputs, &hash_inputs) {
+            cwriteln!(tab_writer, ui, GREY, "  {label}\t=\t{before} -> {after}")?;
+        }
+        tab_writer.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Components whose value differs between two sets of hash inputs
+fn changed_components(
+    before: &TaskHashInputs,
+    after: &TaskHashInputs,
+) -> Vec<(&'static str, String, String)> {
+    before
+        .components()
+        .into_iter()
+        .zip(after.components())
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((label, before), (_, after))| (label, before, after))
+        .collect()
+}
+
+/// Finds the most recent run summary in `.turbo/runs` that recorded the hash
+/// inputs of the given task
+fn find_previous_hash(repo_root: &AbsoluteSystemPath, task_id: &TaskId) -> Option<PreviousHash> {
+    let runs_dir = repo_root.join_components(&[".turbo", "runs"]);
+    let mut summaries = std::fs::read_dir(runs_dir.as_std_path())
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .collect::<Vec<_>>();
+    // Run ids are KSUIDs which sort by creation time
+    summaries.sort();
+
+    // Single package summaries only contain the task name
+    let full_task_id = task_id.to_string();
+    summaries.into_iter().rev().find_map(|path| {
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let summary: SavedRunSummary = serde_json::from_str(&contents)
+            .map_err(|err| debug!("unable to parse run summary {}: {err}", path.display()))
+            .ok()?;
+        let task = summary
+            .tasks
+            .into_iter()
+            .find(|task| task.task_id == full_task_id || task.task_id == task_id.task())?;
+        Some(PreviousHash {
+            run_id: summary.id,
+            hash: task.hash,
+            hash_inputs: task.hash_inputs?,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use turbopath::AbsoluteSystemPathBuf;
+
+    use super::*;
+    use crate::{cli::EnvMode, task_graph::TaskOutputs};
+
+    fn hash_inputs(hash_of_files: &str) -> TaskHashInputs {
+        TaskHashInputs {
+            global_hash: "global".to_string(),
+            hash_of_files: hash_of_files.to_string(),
+            external_deps_hash: Some("deps".to_string()),
+            task_dependency_hashes: vec![],
+            outputs: TaskOutputs::default(),
+            pass_through_args: vec![],
+            env: vec![],
+            resolved_env_vars: vec![],
+            pass_through_env: vec![],
+            env_mode: EnvMode::Strict,
+        }
+    }
+
+    #[test]
+    fn test_changed_components() {
+        let changed = changed_components(&hash_inputs("old"), &hash_inputs("new"));
+        assert_eq!(
+            changed,
+            vec![("Hash of Files", "old".to_string(), "new".to_string())]
+        );
+        assert!(changed_components(&hash_inputs("same"), &hash_inputs("same")).is_empty());
+    }
+
+    #[test]
+    fn test_find_previous_hash() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo_root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let runs_dir = repo_root.join_components(&[".turbo", "runs"]);
+        runs_dir.create_dir_all().unwrap();
+
+        let summary = |id: &str, hash_inputs: Option<TaskHashInputs>| {
+            json!({
+                "id": id,
+                "tasks": [{ "taskId": "web#build", "hash": id, "hashInputs": hash_inputs }],
+            })
+            .to_string()
+        };
+        runs_dir
+            .join_component("2aaa.json")
+            .create_with_contents(summary("2aaa", Some(hash_inputs("older"))))
+            .unwrap();
+        runs_dir
+            .join_component("2bbb.json")
+            .create_with_contents(summary("2bbb", Some(hash_inputs("newer"))))
+            .unwrap();
+        // The latest run didn't record hash inputs so it is skipped
+        runs_dir
+            .join_component("2ccc.json")
+            .create_with_contents(summary("2ccc", None))
+            .unwrap();
+
+        let previous = find_previous_hash(&repo_root, &TaskId::new("web", "build")).unwrap();
+        assert_eq!(previous.run_id, "2bbb");
+        assert_eq!(previous.hash, "2bbb");
+        assert_eq!(previous.hash_inputs.hash_of_files, "newer");
+
+        assert!(find_previous_hash(&repo_root, &TaskId::new("docs", "build")).is_none());
+    }
+}
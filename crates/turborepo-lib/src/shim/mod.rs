@@ -20,7 +20,7 @@ use turborepo_repository::inference::{RepoMode, RepoState};
 use turborepo_ui::ColorConfig;
 use which::which;
 
-use crate::{cli, get_version, spawn_child, tracing::TurboSubscriber};
+use crate::{cli, config, get_version, spawn_child, tracing::TurboSubscriber};
 
 const TURBO_GLOBAL_WARNING_DISABLED: &str = "TURBO_GLOBAL_WARNING_DISABLED";
 
@@ -252,7 +252,7 @@ fn is_turbo_binary_path_set() -> bool {
 }
 
 fn try_check_for_updates(args: &ShimArgs, current_version: &str) {
-    if args.should_check_for_update() {
+    if args.should_check_for_update() && (args.force_update_check || update_notifier_enabled()) {
         // custom footer for update message
         let footer = format!(
             "Follow {username} for updates: {url}",
@@ -280,6 +280,13 @@ fn try_check_for_updates(args: &ShimArgs, current_version: &str) {
     }
 }
 
+/// Checks whether `turbo config set updateNotifier false` has been used to
+/// turn off update checks. A config that can't be read shouldn't stop the
+/// check, any error will be reported once the command loads its config.
+fn update_notifier_enabled() -> bool {
+    config::global_config().map_or(true, |config| config.update_notifier())
+}
+
 pub fn run() -> Result<i32, Error> {
     let args = ShimArgs::parse()?;
     let color_config = args.color_config();
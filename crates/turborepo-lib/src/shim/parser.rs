@@ -472,4 +472,21 @@ mod test {
         let actual = ShimArgs::parse_from_iter(cwd, args.iter().map(|s| s.to_string())).unwrap();
         assert_eq!(expected, actual);
     }
+
+    // Flags are resolved without consulting FORCE_COLOR or NO_COLOR
+    #[test_case(&["turbo", "--no-color"], true ; "no color flag")]
+    #[test_case(&["turbo", "--color"], false ; "color flag")]
+    fn test_color_flags_take_precedence(args: &[&str], should_strip_ansi: bool) {
+        let cwd = AbsoluteSystemPathBuf::new(if cfg!(windows) {
+            "Z:\\some\\dir"
+        } else {
+            "/some/dir"
+        })
+        .unwrap();
+        let shim_args = ShimArgs::parse_from_iter(cwd, args.iter().map(|s| s.to_string())).unwrap();
+        assert_eq!(
+            shim_args.color_config().should_strip_ansi,
+            should_strip_ansi
+        );
+    }
 }
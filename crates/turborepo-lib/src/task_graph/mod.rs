@@ -1,9 +1,13 @@
+mod ready;
+mod task_events;
 mod visitor;
 
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr, time::Duration};
 
 use globwalk::{GlobError, ValidatedGlob};
+pub use ready::ReadyProbe;
 use serde::{Deserialize, Serialize};
+pub use task_events::{TaskEventCacheStatus, TaskTimingEvent};
 use turbopath::{AnchoredSystemPath, AnchoredSystemPathBuf, RelativeUnixPathBuf};
 use turborepo_errors::Spanned;
 pub use visitor::{Error as VisitorError, Visitor};
@@ -54,6 +58,10 @@ pub struct TaskDefinition {
     // This field is custom-marshalled from `env` and `depends_on``
     pub(crate) env: Vec<String>,
 
+    // EnvValues are fixed environment variables set for the task. They are
+    // applied on top of the task's environment and are part of the task hash.
+    pub(crate) env_values: BTreeMap<String, String>,
+
     pub(crate) pass_through_env: Option<Vec<String>>,
 
     // TopologicalDependencies are tasks from package dependencies.
@@ -88,6 +96,26 @@ pub struct TaskDefinition {
     // input.
     pub interactive: bool,
 
+    // Confirm is a prompt shown before the task runs. Only the task is skipped if
+    // the user declines, its dependents still run.
+    pub confirm: Option<String>,
+
+    // Ready is a probe for persistent tasks that lets tasks depending on them
+    // start once it passes.
+    pub ready: Option<ReadyProbe>,
+
+    // MergeStreams overrides whether the task's stderr is written to stdout. If
+    // unset the run-wide behavior is used.
+    pub merge_streams: Option<bool>,
+
+    // LogReplay overrides whether the task's logs are replayed when it hits the
+    // cache. If unset the run-wide behavior is used.
+    pub log_replay: Option<bool>,
+
+    // Timeout is how long the task may run before it is stopped and marked as
+    // failed.
+    pub timeout: Option<Duration>,
+
     // Override for global env mode setting
     pub env_mode: Option<EnvMode>,
 }
@@ -98,6 +126,7 @@ impl Default for TaskDefinition {
             cache: true,
             outputs: Default::default(),
             env: Default::default(),
+            env_values: Default::default(),
             pass_through_env: Default::default(),
             topological_dependencies: Default::default(),
             task_dependencies: Default::default(),
@@ -106,6 +135,11 @@ impl Default for TaskDefinition {
             persistent: Default::default(),
             interruptible: Default::default(),
             interactive: Default::default(),
+            confirm: Default::default(),
+            ready: Default::default(),
+            merge_streams: Default::default(),
+            log_replay: Default::default(),
+            timeout: Default::default(),
             env_mode: Default::default(),
         }
     }
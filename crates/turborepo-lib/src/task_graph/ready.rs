@@ -0,0 +1,194 @@
+use std::{
+    future::Future,
+    io::{self, Write},
+    time::Duration,
+};
+
+use regex::Regex;
+use serde::Serialize;
+use tokio::{net::TcpStream, sync::oneshot};
+
+const PORT_PROBE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Determines when a persistent task is ready, at which point tasks that
+/// depend on it are allowed to start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReadyProbe {
+    /// Ready once a TCP connection can be opened to the port on localhost
+    Port(u16),
+    /// Ready once a line of the task's output matches the pattern
+    Log(String),
+}
+
+/// Pattern to look for in a task's output along with the channel used to
+/// signal that it was found.
+pub struct LogProbe {
+    pattern: Regex,
+    ready: oneshot::Sender<()>,
+}
+
+/// Starts a probe for a task. Returns the log probe that should be fed the
+/// task's output, if any, and a future that resolves once the task is ready.
+/// The future never resolves if there is no probe or the task never becomes
+/// ready.
+pub fn start(probe: Option<ReadyProbe>) -> (Option<LogProbe>, impl Future<Output = ()>) {
+    let (log_probe, log_ready, port) = match probe {
+        Some(ReadyProbe::Port(port)) => (None, None, Some(port)),
+        Some(ReadyProbe::Log(pattern)) => {
+            let (ready, log_ready) = oneshot::channel();
+            let pattern = Regex::new(&pattern).expect("ready pattern validated in turbo.json");
+            (Some(LogProbe { pattern, ready }), Some(log_ready), None)
+        }
+        None => (None, None, None),
+    };
+
+    let ready = async move {
+        if let Some(port) = port {
+            wait_for_port(port).await;
+            return;
+        }
+        // An error means the task finished without the pattern ever showing up
+        if let Some(log_ready) = log_ready {
+            if log_ready.await.is_ok() {
+                return;
+            }
+        }
+        std::future::pending::<()>().await
+    };
+
+    (log_probe, ready)
+}
+
+/// Waits until a TCP connection can be opened to `port` on localhost
+pub async fn wait_for_port(port: u16) {
+    while TcpStream::connect(("localhost", port)).await.is_err() {
+        tokio::time::sleep(PORT_PROBE_INTERVAL).await;
+    }
+}
+
+/// Writer that passes output through while checking each line against a log
+/// probe. Once a line matches the probe is signalled and output is no longer
+/// inspected.
+pub struct LogProbeWriter<W> {
+    writer: W,
+    probe: Option<LogProbe>,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> LogProbeWriter<W> {
+    pub fn new(writer: W, probe: Option<LogProbe>) -> Self {
+        Self {
+            writer,
+            probe,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn check_lines(&mut self) {
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line = self.buffer.drain(..=newline).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let line = console::strip_ansi_codes(line.trim_end());
+            if self
+                .probe
+                .as_ref()
+                .map_or(false, |probe| probe.pattern.is_match(&line))
+            {
+                if let Some(probe) = self.probe.take() {
+                    probe.ready.send(()).ok();
+                }
+                self.buffer = Vec::new();
+                return;
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for LogProbeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        if self.probe.is_some() {
+            self.buffer.extend_from_slice(&buf[..written]);
+            self.check_lines();
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Write, time::Duration};
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_log_probe() {
+        let (log_probe, ready) = start(Some(ReadyProbe::Log("listening on \\d+".into())));
+        let mut output = Vec::new();
+        {
+            let mut writer = LogProbeWriter::new(&mut output, log_probe);
+            writer.write_all(b"starting\nlistening ").unwrap();
+            writer.write_all(b"on 3000\nready\n").unwrap();
+        }
+        tokio::time::timeout(Duration::from_secs(1), ready)
+            .await
+            .expect("log probe should be ready");
+        assert_eq!(output, b"starting\nlistening on 3000\nready\n");
+    }
+
+    #[tokio::test]
+    async fn test_log_probe_never_matches() {
+        let (log_probe, ready) = start(Some(ReadyProbe::Log("listening".into())));
+        let mut writer = LogProbeWriter::new(Vec::new(), log_probe);
+        writer.write_all(b"starting\nlisten").unwrap();
+        drop(writer);
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), ready)
+                .await
+                .is_err(),
+            "probe shouldn't be ready without a matching line"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_port_probe_waits_for_listener() {
+        let port = {
+            let listener = TcpListener::bind(("localhost", 0)).await.unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let (_, ready) = start(Some(ReadyProbe::Port(port)));
+        tokio::pin!(ready);
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(300), &mut ready)
+                .await
+                .is_err(),
+            "probe shouldn't be ready before the port is open"
+        );
+
+        let _listener = TcpListener::bind(("localhost", port)).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), ready)
+            .await
+            .expect("port probe should be ready once the port is open");
+    }
+
+    #[tokio::test]
+    async fn test_port_probe() {
+        let listener = TcpListener::bind(("localhost", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (log_probe, ready) = start(Some(ReadyProbe::Port(port)));
+        assert!(log_probe.is_none());
+
+        tokio::time::timeout(Duration::from_secs(1), ready)
+            .await
+            .expect("port probe should be ready");
+    }
+}
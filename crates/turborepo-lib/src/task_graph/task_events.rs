@@ -0,0 +1,318 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::run::{summary::TaskExecutionSummary, task_id::TaskId};
+
+/// Task lifecycle events emitted when `--log-order=json` is used. Each event
+/// is written as a single line of JSON.
+#[derive(Debug, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum TaskEvent<'a> {
+    TaskStarted {
+        task: &'a str,
+    },
+    TaskOutput {
+        task: &'a str,
+        stream: OutputStream,
+        line: &'a str,
+    },
+    TaskFinished {
+        task: &'a str,
+        exit_code: Option<i32>,
+        cache_status: TaskEventCacheStatus,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TaskEventCacheStatus {
+    Hit,
+    Miss,
+}
+
+impl TaskEvent<'_> {
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut line = serde_json::to_vec(self)?;
+        line.push(b'\n');
+        writer.write_all(&line)
+    }
+
+    /// Writes the event to `output`
+    pub fn emit(&self, output: &TaskEventOutput) {
+        if let Err(e) = self.write_to(output.clone()) {
+            tracing::error!("unable to write task event: {e}");
+        }
+    }
+}
+
+/// Where task events are written, stdout by default. Clones share the same
+/// writer and each event is written while holding its lock so events from
+/// different tasks are never interleaved.
+#[derive(Clone)]
+pub struct TaskEventOutput(Arc<Mutex<dyn Write + Send>>);
+
+impl TaskEventOutput {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(writer)))
+    }
+}
+
+impl Default for TaskEventOutput {
+    fn default() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl Write for TaskEventOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .expect("event output lock poisoned")
+            .write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0
+            .lock()
+            .expect("event output lock poisoned")
+            .write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().expect("event output lock poisoned").flush()
+    }
+}
+
+/// Timing information sent to an external collector as each task finishes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskTimingEvent {
+    pub task_id: TaskId<'static>,
+    pub duration: Duration,
+    pub cache_status: TaskEventCacheStatus,
+    pub exit_code: Option<i32>,
+}
+
+impl TaskTimingEvent {
+    pub fn new(
+        task_id: TaskId<'static>,
+        execution: &TaskExecutionSummary,
+        cache_status: TaskEventCacheStatus,
+    ) -> Self {
+        let duration = (execution.end_time - execution.start_time).max(0);
+        Self {
+            task_id,
+            duration: Duration::from_millis(duration as u64),
+            cache_status,
+            exit_code: execution.exit_code,
+        }
+    }
+
+    /// Sends the event without waiting for the collector. If the channel is
+    /// full the event is dropped so a slow collector never holds up tasks.
+    pub fn send(self, sender: &mpsc::Sender<TaskTimingEvent>) {
+        if let Err(mpsc::error::TrySendError::Full(event)) = sender.try_send(self) {
+            tracing::debug!("dropping timing event for {}", event.task_id);
+        }
+    }
+}
+
+/// Writer that turns each line of a task's output into a `taskOutput` event
+pub struct TaskEventWriter<W> {
+    task: String,
+    stream: OutputStream,
+    buffer: Vec<u8>,
+    writer: W,
+}
+
+impl<W: Write> TaskEventWriter<W> {
+    pub fn new(task: String, stream: OutputStream, writer: W) -> Self {
+        Self {
+            task,
+            stream,
+            buffer: Vec::new(),
+            writer,
+        }
+    }
+
+    fn write_event(&mut self, line: &[u8]) -> io::Result<()> {
+        let line = String::from_utf8_lossy(line);
+        TaskEvent::TaskOutput {
+            task: &self.task,
+            stream: self.stream,
+            line: line.trim_end_matches('\r'),
+        }
+        .write_to(&mut self.writer)
+    }
+}
+
+impl<W: Write> Write for TaskEventWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line = self.buffer.drain(..=newline).collect::<Vec<_>>();
+            self.write_event(&line[..newline])?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Any trailing output without a newline still gets its own event
+        if !self.buffer.is_empty() {
+            let line = std::mem::take(&mut self.buffer);
+            self.write_event(&line)?;
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn execution(start_time: i64, end_time: i64, exit_code: i32) -> TaskExecutionSummary {
+        TaskExecutionSummary {
+            start_time,
+            end_time,
+            error: None,
+            exit_code: Some(exit_code),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_timing_events() {
+        let (sender, mut receiver) = mpsc::channel(8);
+        // Execution summaries hold millisecond timestamps, so fixed values stand in
+        // for the clock
+        TaskTimingEvent::new(
+            TaskId::new("lib", "build").into_owned(),
+            &execution(1_000, 1_250, 0),
+            TaskEventCacheStatus::Miss,
+        )
+        .send(&sender);
+        TaskTimingEvent::new(
+            TaskId::new("web", "build").into_owned(),
+            &execution(1_250, 1_260, 1),
+            TaskEventCacheStatus::Hit,
+        )
+        .send(&sender);
+        // A clock that went backwards doesn't produce a bogus duration
+        TaskTimingEvent::new(
+            TaskId::new("docs", "build").into_owned(),
+            &execution(2_000, 1_900, 0),
+            TaskEventCacheStatus::Miss,
+        )
+        .send(&sender);
+        drop(sender);
+
+        let mut events = Vec::new();
+        while let Some(event) = receiver.recv().await {
+            events.push(event);
+        }
+
+        assert_eq!(
+            events,
+            vec![
+                TaskTimingEvent {
+                    task_id: TaskId::new("lib", "build").into_owned(),
+                    duration: Duration::from_millis(250),
+                    cache_status: TaskEventCacheStatus::Miss,
+                    exit_code: Some(0),
+                },
+                TaskTimingEvent {
+                    task_id: TaskId::new("web", "build").into_owned(),
+                    duration: Duration::from_millis(10),
+                    cache_status: TaskEventCacheStatus::Hit,
+                    exit_code: Some(1),
+                },
+                TaskTimingEvent {
+                    task_id: TaskId::new("docs", "build").into_owned(),
+                    duration: Duration::ZERO,
+                    cache_status: TaskEventCacheStatus::Miss,
+                    exit_code: Some(0),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_task_timing_events_dropped_when_full() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        for task in ["lib", "web"] {
+            TaskTimingEvent::new(
+                TaskId::new(task, "build").into_owned(),
+                &execution(0, 10, 0),
+                TaskEventCacheStatus::Miss,
+            )
+            .send(&sender);
+        }
+        drop(sender);
+
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.task_id, TaskId::new("lib", "build"));
+        assert!(
+            receiver.recv().await.is_none(),
+            "second event should be dropped"
+        );
+    }
+
+    #[test]
+    fn test_task_events_are_json_lines() {
+        let mut output = Vec::new();
+        TaskEvent::TaskStarted { task: "web#build" }
+            .write_to(&mut output)
+            .unwrap();
+        {
+            let mut writer =
+                TaskEventWriter::new("web#build".into(), OutputStream::Stdout, &mut output);
+            writer.write_all(b"compiling\r\ndone").unwrap();
+            writer.write_all(b"!\n\"quoted\"").unwrap();
+            writer.flush().unwrap();
+        }
+        TaskEvent::TaskFinished {
+            task: "web#build",
+            exit_code: Some(0),
+            cache_status: TaskEventCacheStatus::Miss,
+        }
+        .write_to(&mut output)
+        .unwrap();
+
+        let events = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                json!({"type": "taskStarted", "task": "web#build"}),
+                json!({"type": "taskOutput", "task": "web#build", "stream": "stdout", "line": "compiling"}),
+                json!({"type": "taskOutput", "task": "web#build", "stream": "stdout", "line": "done!"}),
+                json!({"type": "taskOutput", "task": "web#build", "stream": "stdout", "line": "\"quoted\""}),
+                json!({"type": "taskFinished", "task": "web#build", "exitCode": 0, "cacheStatus": "MISS"}),
+            ]
+        );
+    }
+}
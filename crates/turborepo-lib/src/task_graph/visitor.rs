@@ -1,22 +1,30 @@
 use std::{
     borrow::Cow,
-    collections::HashSet,
-    io::Write,
+    collections::{BTreeMap, HashSet},
+    io::{IsTerminal, Write},
     sync::{Arc, Mutex, OnceLock},
     time::{Duration, Instant},
 };
 
 use console::{Style, StyledObject};
+use dialoguer::Confirm;
 use either::Either;
-use futures::{stream::FuturesUnordered, StreamExt};
+use futures::{
+    stream::{FuturesOrdered, FuturesUnordered},
+    FutureExt, StreamExt,
+};
 use itertools::Itertools;
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use regex::Regex;
-use tokio::sync::{mpsc, oneshot};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::{JoinError, JoinHandle},
+};
 use tracing::{debug, error, warn, Instrument, Span};
 use turbopath::{AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPath};
 use turborepo_ci::{Vendor, VendorBehavior};
 use turborepo_env::{platform::PlatformEnv, EnvironmentVariableMap};
+use turborepo_errors::Spanned;
 use turborepo_repository::{
     package_graph::{PackageGraph, PackageName, ROOT_PKG_NAME},
     package_manager::PackageManager,
@@ -32,21 +40,29 @@ use turborepo_ui::{
 use which::which;
 
 use crate::{
-    cli::EnvMode,
+    cli::{ContinueMode, DryRunMode, EnvMode, OutputLogsMode},
     config::UIMode,
-    engine::{Engine, ExecutionOptions, StopExecution},
-    opts::RunOpts,
-    process::{ChildExit, Command, ProcessManager},
+    engine::{Engine, ExecutionOptions, StopExecution, TaskNode},
+    opts::{ResolvedLogOrder, RunOpts},
+    process::{Child, ChildExit, Command, ProcessManager, SpawnRetry},
     run::{
         global_hash::GlobalHashableInputs,
         summary::{
             self, GlobalHashSummary, RunTracker, SpacesTaskClient, SpacesTaskInformation,
-            TaskExecutionSummary, TaskTracker,
+            TaskExecutionSummary, TaskTracker, TaskWarningSummary,
         },
         task_access::TaskAccess,
         task_id::TaskId,
         CacheOutput, RunCache, TaskCache,
     },
+    task_graph::{
+        ready::{self, LogProbe, LogProbeWriter},
+        task_events::{
+            OutputStream, TaskEvent, TaskEventCacheStatus, TaskEventOutput, TaskEventWriter,
+            TaskTimingEvent,
+        },
+        ReadyProbe,
+    },
     task_hash::{self, PackageInputsHashes, TaskHashTracker, TaskHashTrackerState, TaskHasher},
 };
 
@@ -56,6 +72,7 @@ pub struct Visitor<'a> {
     dry: bool,
     global_env: EnvironmentVariableMap,
     global_env_mode: EnvMode,
+    env_at_execution_start: &'a EnvironmentVariableMap,
     manager: ProcessManager,
     run_opts: &'a RunOpts,
     package_graph: Arc<PackageGraph>,
@@ -69,6 +86,8 @@ pub struct Visitor<'a> {
     is_watch: bool,
     ui_sender: Option<UISender>,
     warnings: Arc<Mutex<Vec<TaskWarning>>>,
+    timing_sender: Option<mpsc::Sender<TaskTimingEvent>>,
+    task_event_output: TaskEventOutput,
 }
 
 #[derive(Debug, thiserror::Error, Diagnostic)]
@@ -99,6 +118,11 @@ pub enum Error {
     RunSummary(#[from] summary::Error),
     #[error("internal errors encountered: {0}")]
     InternalErrors(String),
+    #[error(
+        "{task_id} needs to be confirmed before it runs, but the terminal UI doesn't support \
+         prompts. Rerun with `--ui=stream` to confirm it."
+    )]
+    ConfirmWithTui { task_id: String },
 }
 
 impl<'a> Visitor<'a> {
@@ -122,6 +146,7 @@ impl<'a> Visitor<'a> {
         global_env: EnvironmentVariableMap,
         ui_sender: Option<UISender>,
         is_watch: bool,
+        timing_sender: Option<mpsc::Sender<TaskTimingEvent>>,
     ) -> Self {
         let task_hasher = TaskHasher::new(
             package_inputs_hashes,
@@ -130,7 +155,7 @@ impl<'a> Visitor<'a> {
             global_hash,
         );
 
-        let sink = Self::sink(run_opts);
+        let sink = Self::sink();
         let color_cache = ColorSelector::default();
         // Set up correct size for underlying pty
 
@@ -144,6 +169,7 @@ impl<'a> Visitor<'a> {
             color_cache,
             dry: false,
             global_env_mode,
+            env_at_execution_start,
             manager,
             run_opts,
             package_graph,
@@ -158,6 +184,8 @@ impl<'a> Visitor<'a> {
             ui_sender,
             is_watch,
             warnings: Default::default(),
+            timing_sender,
+            task_event_output: TaskEventOutput::default(),
         }
     }
 
@@ -170,6 +198,17 @@ impl<'a> Visitor<'a> {
         for task in engine.tasks().sorted() {
             self.color_cache.color_for_key(&task.to_string());
         }
+        let task_names = engine
+            .tasks()
+            .filter_map(|task| match task {
+                TaskNode::Root => None,
+                TaskNode::Task(task_id) => Some(task_id.task()),
+            })
+            .collect::<HashSet<_>>();
+
+        // Confirmations are all collected before any task starts so a prompt
+        // doesn't hold up scheduling or get mixed in with task output.
+        let declined_tasks = self.declined_tasks(&engine).await?;
 
         let concurrency = self.run_opts.concurrency as usize;
         let (node_sender, mut node_stream) = mpsc::channel(concurrency);
@@ -183,6 +222,17 @@ impl<'a> Visitor<'a> {
         let span = Span::current();
 
         let factory = ExecContextFactory::new(self, errors.clone(), self.manager.clone(), &engine);
+        let mut ndjson_tasks =
+            matches!(self.run_opts.dry_run, Some(DryRunMode::Ndjson)).then(|| {
+                NdjsonSequencer::new(summary::NdjsonTaskSummaries::new(
+                    &self.package_graph,
+                    &engine,
+                    self.task_hasher.task_hash_tracker(),
+                    self.env_at_execution_start,
+                    self.run_opts,
+                    self.global_env_mode,
+                ))
+            });
 
         while let Some(message) = node_stream.recv().await {
             let span = tracing::debug_span!(parent: &span, "queue_task", task = %message.info);
@@ -206,18 +256,31 @@ impl<'a> Visitor<'a> {
                 .get(info.task())
                 .cloned();
 
-            match command {
-                Some(cmd) if info.package() == ROOT_PKG_NAME && turbo_regex().is_match(&cmd) => {
-                    package_task_event.track_error(TrackedErrors::RecursiveError);
-                    let (span, text) = cmd.span_and_text("package.json");
-                    return Err(Error::RecursiveTurbo {
-                        task_name: info.to_string(),
-                        command: cmd.to_string(),
-                        span,
-                        text,
-                    });
+            if let Some(cmd) = &command {
+                match check_recursive_turbo(info.package(), cmd, &task_names) {
+                    Some(RecursiveTurbo::Error) => {
+                        package_task_event.track_error(TrackedErrors::RecursiveError);
+                        let (span, text) = cmd.span_and_text("package.json");
+                        let span = turbo_span(cmd).or(span);
+                        return Err(Error::RecursiveTurbo {
+                            task_name: info.to_string(),
+                            command: cmd.to_string(),
+                            span,
+                            text,
+                        });
+                    }
+                    Some(RecursiveTurbo::Warning) => {
+                        self.warnings
+                            .lock()
+                            .expect("warnings lock poisoned")
+                            .push(TaskWarning {
+                                task_id: self.display_task_id(&info),
+                                missing_platform_env: Vec::new(),
+                                recursive_turbo_command: Some(cmd.to_string()),
+                            });
+                    }
+                    None => (),
                 }
-                _ => (),
             }
 
             let task_definition = engine
@@ -246,6 +309,22 @@ impl<'a> Visitor<'a> {
             let execution_env =
                 self.task_hasher
                     .env(&info, task_env_mode, task_definition, &self.global_env)?;
+            if tracing::enabled!(tracing::Level::DEBUG) {
+                let env_diff = execution_env.diff(self.env_at_execution_start);
+                debug!(
+                    "task {} env ({:?} mode) drops {:?} and adds {:?}",
+                    info, task_env_mode, env_diff.dropped, env_diff.added
+                );
+            }
+            // Loose mode tasks get every env var, so to find the platform env vars a task
+            // doesn't declare we check against the env it would get in strict mode.
+            let strict_execution_env = (self.run_opts.warn_undeclared_env
+                && task_env_mode == EnvMode::Loose)
+                .then(|| {
+                    self.task_hasher
+                        .env(&info, EnvMode::Strict, task_definition, &self.global_env)
+                })
+                .transpose()?;
 
             let task_cache = self.run_cache.task_cache(
                 task_definition,
@@ -262,10 +341,15 @@ impl<'a> Visitor<'a> {
                 true => {
                     let dry_run_exec_context =
                         factory.dry_run_exec_context(info.clone(), task_cache);
-                    let tracker = self.run_tracker.track_task(info.into_owned());
-                    tasks.push(tokio::spawn(async move {
-                        dry_run_exec_context.execute_dry_run(tracker).await
-                    }));
+                    let tracker = self.run_tracker.track_task(info.clone().into_owned());
+                    if let Some(ndjson_tasks) = &mut ndjson_tasks {
+                        ndjson_tasks.push(info.into_owned(), dry_run_exec_context, tracker);
+                        ndjson_tasks.write_finished()?;
+                    } else {
+                        tasks.push(tokio::spawn(async move {
+                            dry_run_exec_context.execute_dry_run(tracker).await
+                        }));
+                    }
                 }
                 false => {
                     // TODO(gsoltis): if/when we fix https://github.com/vercel/turborepo/issues/937
@@ -277,7 +361,15 @@ impl<'a> Visitor<'a> {
                         continue;
                     }
 
+                    // Dropping the callback lets the engine carry on with the tasks that
+                    // depend on this one
+                    if declined_tasks.contains(&info) {
+                        warn!("skipping {info} since it was not confirmed");
+                        continue;
+                    }
+
                     let workspace_directory = self.repo_root.resolve(workspace_info.package_path());
+                    let output_logs = task_cache.output_logs();
 
                     let takes_input = task_definition.interactive || task_definition.persistent;
                     let mut exec_context = factory.exec_context(
@@ -286,6 +378,7 @@ impl<'a> Visitor<'a> {
                         task_cache,
                         workspace_directory,
                         execution_env,
+                        strict_execution_env,
                         takes_input,
                         self.task_access.clone(),
                     );
@@ -296,7 +389,12 @@ impl<'a> Visitor<'a> {
                     let output_client = if let Some(handle) = &self.ui_sender {
                         TaskOutput::UI(handle.task(info.to_string()))
                     } else {
-                        TaskOutput::Direct(self.output_client(&info, vendor_behavior))
+                        TaskOutput::Direct(self.output_client(
+                            &info,
+                            output_logs,
+                            task_definition.merge_streams,
+                            vendor_behavior,
+                        ))
                     };
 
                     let tracker = self.run_tracker.track_task(info.clone().into_owned());
@@ -321,7 +419,14 @@ impl<'a> Visitor<'a> {
         }
 
         // Wait for the engine task to finish and for all of our tasks to finish
-        engine_handle.await.expect("engine execution panicked")?;
+        let skipped_tasks = engine_handle.await.expect("engine execution panicked")?;
+        for task_id in skipped_tasks.into_iter().sorted() {
+            warn!("skipping {task_id} since one of its dependencies failed");
+            self.run_tracker.track_task(task_id).skipped().await;
+        }
+        if let Some(ndjson_tasks) = ndjson_tasks {
+            ndjson_tasks.finish().await?;
+        }
         // This will poll the futures until they are all completed
         let mut internal_errors = Vec::new();
         while let Some(result) = tasks.next().await {
@@ -386,6 +491,11 @@ impl<'a> Visitor<'a> {
         } = self;
 
         let global_hash_summary = GlobalHashSummary::try_from(global_hash_inputs)?;
+        let summary_warnings: Vec<_> = self
+            .warnings
+            .lock()
+            .map(|warnings| warnings.iter().filter_map(TaskWarning::summary).collect())
+            .unwrap_or_default();
 
         // output any warnings that we collected while running tasks
         if let Ok(warnings) = self.warnings.lock() {
@@ -413,9 +523,29 @@ impl<'a> Visitor<'a> {
                         }
                     }
                 }
+
+                for warning in warnings.iter() {
+                    if let Some(command) = &warning.recursive_turbo_command {
+                        warn!(
+                            "{} ({command}) runs turbo on a task that is part of this run and \
+                             might cause a loop",
+                            warning.task_id
+                        );
+                    }
+                }
             }
         }
 
+        if run_opts.why_hash.is_some() {
+            summary::print_why_hash(
+                repo_root,
+                run_opts,
+                engine.tasks(),
+                &task_hasher.task_hash_tracker(),
+                ui,
+            )?;
+        }
+
         Ok(self
             .run_tracker
             .finish(
@@ -431,36 +561,110 @@ impl<'a> Visitor<'a> {
                 engine,
                 task_hasher.task_hash_tracker(),
                 env_at_execution_start,
+                summary_warnings,
                 is_watch,
             )
             .await?)
     }
 
-    fn sink(run_opts: &RunOpts) -> OutputSink<StdWriter> {
-        let (out, err) = if run_opts.should_redirect_stderr_to_stdout() {
-            (std::io::stdout().into(), std::io::stdout().into())
-        } else {
-            (std::io::stdout().into(), std::io::stderr().into())
-        };
-        OutputSink::new(out, err)
+    /// Prompts for every task in the engine that requires confirmation and
+    /// returns the tasks that were declined
+    async fn declined_tasks(&self, engine: &Engine) -> Result<HashSet<TaskId<'static>>, Error> {
+        let mut declined = HashSet::new();
+        if self.dry {
+            return Ok(declined);
+        }
+        for task_id in engine.tasks().sorted().filter_map(|task| match task {
+            TaskNode::Root => None,
+            TaskNode::Task(task_id) => Some(task_id),
+        }) {
+            let Some(prompt) = engine
+                .task_definition(task_id)
+                .and_then(|definition| definition.confirm.as_deref())
+            else {
+                continue;
+            };
+            // Tasks without a script never run so there's nothing to confirm
+            let has_command = self
+                .package_graph
+                .package_info(&PackageName::from(task_id.package()))
+                .and_then(|info| info.package_json.scripts.get(task_id.task()))
+                .map_or(false, |cmd| !cmd.is_empty());
+            if has_command && !self.confirm_task(task_id, prompt).await? {
+                declined.insert(task_id.clone());
+            }
+        }
+        Ok(declined)
+    }
+
+    /// Asks the user to confirm a task before it is scheduled.
+    async fn confirm_task(&self, task_id: &TaskId<'static>, prompt: &str) -> Result<bool, Error> {
+        let is_interactive = !self.is_watch
+            && Vendor::infer().is_none()
+            && std::io::stdin().is_terminal()
+            && std::io::stdout().is_terminal();
+        let uses_tui = matches!(self.ui_sender, Some(UISender::Tui(_)));
+        if !should_prompt(task_id, is_interactive, uses_tui)? {
+            debug!("not prompting to confirm {task_id} in a non-interactive context");
+            return Ok(true);
+        }
+
+        let prompt = format!("{}: {prompt}", self.display_task_id(task_id));
+        let confirmation =
+            tokio::task::spawn_blocking(move || Confirm::new().with_prompt(prompt).interact())
+                .await
+                .expect("confirmation prompt panicked");
+        match confirmation {
+            Ok(confirmed) => Ok(confirmed),
+            Err(e) => {
+                warn!("unable to confirm {task_id}: {e}");
+                Ok(false)
+            }
+        }
+    }
+
+    fn sink() -> OutputSink<StdWriter> {
+        OutputSink::new(std::io::stdout().into(), std::io::stderr().into())
     }
 
     fn output_client(
         &self,
         task_id: &TaskId,
+        output_logs: OutputLogsMode,
+        merge_streams: Option<bool>,
         vendor_behavior: Option<&VendorBehavior>,
     ) -> OutputClient<impl std::io::Write> {
         let behavior = match self.run_opts.log_order {
-            crate::opts::ResolvedLogOrder::Stream if self.run_tracker.spaces_enabled() => {
-                turborepo_ui::OutputClientBehavior::InMemoryBuffer
+            ResolvedLogOrder::Json => {
+                // Each task gets its own sink so that every line of output can be tagged
+                // with the task it came from. Both streams end up in the same event
+                // output.
+                let task = task_id.to_string();
+                let sink = OutputSink::new(
+                    StdWriter::from(TaskEventWriter::new(
+                        task.clone(),
+                        OutputStream::Stdout,
+                        self.task_event_output.clone(),
+                    )),
+                    StdWriter::from(TaskEventWriter::new(
+                        task,
+                        OutputStream::Stderr,
+                        self.task_event_output.clone(),
+                    )),
+                );
+                return sink.logger(turborepo_ui::OutputClientBehavior::Passthrough);
             }
-            crate::opts::ResolvedLogOrder::Stream => {
-                turborepo_ui::OutputClientBehavior::Passthrough
+            log_order => {
+                output_client_behavior(log_order, output_logs, self.run_tracker.spaces_enabled())
             }
-            crate::opts::ResolvedLogOrder::Grouped => turborepo_ui::OutputClientBehavior::Grouped,
         };
 
         let mut logger = self.sink.logger(behavior);
+        // Streams are merged by the client rather than the sink so every task shares
+        // the same sink and lock regardless of its setting
+        if merge_streams.unwrap_or_else(|| self.run_opts.should_redirect_stderr_to_stdout()) {
+            logger.with_merged_streams();
+        }
         if let Some(vendor_behavior) = vendor_behavior {
             let group_name = if self.run_opts.single_package {
                 task_id.task().to_string()
@@ -488,13 +692,19 @@ impl<'a> Visitor<'a> {
 
     fn prefix<'b>(&self, task_id: &'b TaskId) -> Cow<'b, str> {
         match self.run_opts.log_prefix {
-            crate::opts::ResolvedLogPrefix::Task if self.run_opts.single_package => {
+            crate::opts::ResolvedLogPrefix::Task
+            | crate::opts::ResolvedLogPrefix::TaskTimestamp
+                if self.run_opts.single_package =>
+            {
                 task_id.task().into()
             }
-            crate::opts::ResolvedLogPrefix::Task => {
+            crate::opts::ResolvedLogPrefix::Task
+            | crate::opts::ResolvedLogPrefix::TaskTimestamp => {
                 format!("{}:{}", task_id.package(), task_id.task()).into()
             }
-            crate::opts::ResolvedLogPrefix::None => "".into(),
+            crate::opts::ResolvedLogPrefix::None | crate::opts::ResolvedLogPrefix::Timestamp => {
+                "".into()
+            }
         }
     }
 
@@ -509,11 +719,13 @@ impl<'a> Visitor<'a> {
     fn prefixed_ui<W: Write>(
         color_config: ColorConfig,
         is_github_actions: bool,
+        timestamps: bool,
         stdout: W,
         stderr: W,
         prefix: StyledObject<String>,
     ) -> PrefixedUI<W> {
         let mut prefixed_ui = PrefixedUI::new(color_config, stdout, stderr)
+            .with_timestamps(timestamps)
             .with_output_prefix(prefix.clone())
             // TODO: we can probably come up with a more ergonomic way to achieve this
             .with_error_prefix(
@@ -547,6 +759,29 @@ enum StdWriter {
     Out(std::io::Stdout),
     Err(std::io::Stderr),
     Null(std::io::Sink),
+    Json(TaskEventWriter<TaskEventOutput>),
+}
+
+/// Picks how a task's output client forwards lines to the terminal. Tasks that
+/// only show errors with a summary are grouped so that a failing task's logs
+/// aren't interleaved with the summary lines of other tasks.
+fn output_client_behavior(
+    log_order: ResolvedLogOrder,
+    output_logs: OutputLogsMode,
+    spaces_enabled: bool,
+) -> turborepo_ui::OutputClientBehavior {
+    match log_order {
+        ResolvedLogOrder::Stream if spaces_enabled => {
+            turborepo_ui::OutputClientBehavior::InMemoryBuffer
+        }
+        ResolvedLogOrder::Stream if output_logs == OutputLogsMode::ErrorsOnlyWithSummary => {
+            turborepo_ui::OutputClientBehavior::Grouped
+        }
+        ResolvedLogOrder::Stream | ResolvedLogOrder::Json => {
+            turborepo_ui::OutputClientBehavior::Passthrough
+        }
+        ResolvedLogOrder::Grouped => turborepo_ui::OutputClientBehavior::Grouped,
+    }
 }
 
 impl StdWriter {
@@ -555,6 +790,7 @@ impl StdWriter {
             StdWriter::Out(out) => out,
             StdWriter::Err(err) => err,
             StdWriter::Null(null) => null,
+            StdWriter::Json(json) => json,
         }
     }
 }
@@ -577,6 +813,12 @@ impl From<std::io::Sink> for StdWriter {
     }
 }
 
+impl From<TaskEventWriter<TaskEventOutput>> for StdWriter {
+    fn from(value: TaskEventWriter<TaskEventOutput>) -> Self {
+        Self::Json(value)
+    }
+}
+
 impl std::io::Write for StdWriter {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         self.writer().write(buf)
@@ -599,11 +841,125 @@ fn turbo_regex() -> &'static Regex {
     RE.get_or_init(|| Regex::new(r"(?:^|\s)turbo(?:$|\s)").unwrap())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecursiveTurbo {
+    Error,
+    Warning,
+}
+
+/// Checks if a task's command invokes turbo in a way that might loop forever.
+/// Any invocation from a root task is an error. A package task is only
+/// warned about if it runs a task that is already part of the current run.
+fn check_recursive_turbo(
+    package: &str,
+    command: &str,
+    task_names: &HashSet<&str>,
+) -> Option<RecursiveTurbo> {
+    if !turbo_regex().is_match(command) {
+        return None;
+    }
+    if package == ROOT_PKG_NAME {
+        return Some(RecursiveTurbo::Error);
+    }
+
+    invoked_turbo_tasks(command)
+        .any(|task| {
+            // Tasks can be invoked with a package prefix e.g. `turbo run web#build`
+            let task = task.rsplit_once('#').map_or(task, |(_, task)| task);
+            task_names.contains(task)
+        })
+        .then_some(RecursiveTurbo::Warning)
+}
+
+/// Decides whether a task that requires confirmation should prompt. There is
+/// nobody to answer the prompt in watch mode, in CI, or without a TTY so the
+/// task runs without one. The terminal UI owns stdin, so a prompt can't be
+/// shown while it is in use.
+fn should_prompt(task_id: &TaskId, is_interactive: bool, uses_tui: bool) -> Result<bool, Error> {
+    if !is_interactive {
+        return Ok(false);
+    }
+    if uses_tui {
+        return Err(Error::ConfirmWithTui {
+            task_id: task_id.to_string(),
+        });
+    }
+    Ok(true)
+}
+
+/// Waits for a process to exit while piping its output to `writer`. Returns
+/// `None` if the timeout elapses first, in which case the process is left
+/// running.
+async fn wait_with_timeout(
+    process: &mut Child,
+    writer: impl Write,
+    timeout: Option<Duration>,
+) -> Option<std::io::Result<Option<ChildExit>>> {
+    let wait = process.wait_with_piped_outputs(writer);
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, wait).await.ok(),
+        None => Some(wait.await),
+    }
+}
+
+/// Narrows the span of a script down to the first `turbo` call in it. The
+/// script's span starts at the opening quote of the JSON string, so the raw
+/// text is searched instead of the unescaped command.
+fn turbo_span<T>(script: &Spanned<T>) -> Option<SourceSpan> {
+    let range = script.range.as_ref()?;
+    let text = script.text.as_deref()?;
+    let value_start = range.start + 1;
+    let raw_command = text.get(value_start..range.end.saturating_sub(1))?;
+    let invocation = turbo_regex().find(raw_command)?;
+    let offset = invocation.start() + invocation.as_str().find("turbo")?;
+    Some((value_start + offset, "turbo".len()).into())
+}
+
+/// Returns the tasks passed to each turbo invocation in a command. Only the
+/// leading arguments are considered as tasks, parsing stops at the first flag.
+fn invoked_turbo_tasks(command: &str) -> impl Iterator<Item = &str> {
+    command.split(['&', '|', ';']).flat_map(|invocation| {
+        let mut args = invocation
+            .split_whitespace()
+            .skip_while(|arg| *arg != "turbo")
+            .skip(1)
+            .peekable();
+        args.next_if_eq(&"run");
+        args.take_while(|arg| !arg.starts_with('-'))
+    })
+}
+
 // Warning that comes from the execution of the task
 #[derive(Debug, Clone)]
 pub struct TaskWarning {
     task_id: String,
     missing_platform_env: Vec<String>,
+    recursive_turbo_command: Option<String>,
+}
+
+impl TaskWarning {
+    /// The part of the warning that is included in the run summary
+    fn summary(&self) -> Option<TaskWarningSummary> {
+        (!self.missing_platform_env.is_empty()).then(|| TaskWarningSummary {
+            task_id: self.task_id.clone(),
+            missing_platform_env: self.missing_platform_env.clone(),
+        })
+    }
+}
+
+/// Warns about the platform env vars that are missing from a task's env.
+/// Nothing is checked if platform env validation is turned off.
+fn platform_env_warning(
+    platform_env: Option<&PlatformEnv>,
+    task_id: &str,
+    execution_env: &EnvironmentVariableMap,
+) -> Option<TaskWarning> {
+    let missing_platform_env = platform_env?.validate(execution_env);
+    (!missing_platform_env.is_empty()).then(|| TaskWarning {
+        task_id: task_id.to_string(),
+        missing_platform_env,
+        recursive_turbo_command: None,
+    })
 }
 
 // Error that comes from the execution of the task
@@ -621,6 +977,8 @@ enum TaskErrorCause {
     Spawn { msg: String },
     #[error("command {command} exited ({exit_code})")]
     Exit { command: String, exit_code: i32 },
+    #[error("command {command} timed out after {}", humantime::format_duration(*timeout))]
+    Timeout { command: String, timeout: Duration },
     #[error("turbo has internal error processing task")]
     Internal,
 }
@@ -706,17 +1064,25 @@ impl<'a> ExecContextFactory<'a> {
         task_cache: TaskCache,
         workspace_directory: AbsoluteSystemPathBuf,
         execution_env: EnvironmentVariableMap,
+        strict_execution_env: Option<EnvironmentVariableMap>,
         takes_input: bool,
         task_access: TaskAccess,
     ) -> ExecContext {
         let task_id_for_display = self.visitor.display_task_id(&task_id);
         let pass_through_args = self.visitor.run_opts.args_for_task(&task_id);
         let task_id_string = &task_id.to_string();
+        let task_definition = self.engine.task_definition(&task_id);
+        let ready = task_definition.and_then(|task_definition| task_definition.ready.clone());
+        let timeout = task_definition.and_then(|task_definition| task_definition.timeout);
+        let env_values = task_definition
+            .map(|task_definition| task_definition.env_values.clone())
+            .unwrap_or_default();
         ExecContext {
             engine: self.engine.clone(),
             ui_mode: self.visitor.run_opts.ui_mode,
             color_config: self.visitor.color_config,
             is_github_actions: self.visitor.run_opts.is_github_actions,
+            log_timestamps: self.visitor.run_opts.log_prefix.timestamps(),
             pretty_prefix: self
                 .visitor
                 .color_cache
@@ -730,13 +1096,25 @@ impl<'a> ExecContextFactory<'a> {
             manager: self.manager.clone(),
             task_hash,
             execution_env,
+            strict_execution_env,
+            env_values,
             continue_on_error: self.visitor.run_opts.continue_on_error,
+            kill_timeout: self.visitor.run_opts.kill_timeout,
             pass_through_args,
             errors: self.errors.clone(),
             warnings: self.visitor.warnings.clone(),
             takes_input,
             task_access,
-            platform_env: PlatformEnv::new(),
+            platform_env: self
+                .visitor
+                .run_opts
+                .platform_env_validation
+                .then(PlatformEnv::new),
+            task_event_output: (self.visitor.run_opts.log_order == ResolvedLogOrder::Json)
+                .then(|| self.visitor.task_event_output.clone()),
+            timing_sender: self.visitor.timing_sender.clone(),
+            ready,
+            timeout,
         }
     }
 
@@ -758,6 +1136,7 @@ struct ExecContext {
     color_config: ColorConfig,
     ui_mode: UIMode,
     is_github_actions: bool,
+    log_timestamps: bool,
     pretty_prefix: StyledObject<String>,
     task_id: TaskId<'static>,
     task_id_for_display: String,
@@ -768,13 +1147,24 @@ struct ExecContext {
     manager: ProcessManager,
     task_hash: String,
     execution_env: EnvironmentVariableMap,
-    continue_on_error: bool,
+    // Env the task would get in strict mode, used to warn about undeclared platform env vars
+    strict_execution_env: Option<EnvironmentVariableMap>,
+    // Fixed env vars declared for the task in turbo.json
+    env_values: BTreeMap<String, String>,
+    continue_on_error: ContinueMode,
+    kill_timeout: Duration,
     pass_through_args: Option<Vec<String>>,
     errors: Arc<Mutex<Vec<TaskError>>>,
     warnings: Arc<Mutex<Vec<TaskWarning>>>,
     takes_input: bool,
     task_access: TaskAccess,
-    platform_env: PlatformEnv,
+    // Unset when platform env validation is turned off
+    platform_env: Option<PlatformEnv>,
+    // Set when task events are written for `--log-order=json`
+    task_event_output: Option<TaskEventOutput>,
+    timing_sender: Option<mpsc::Sender<TaskTimingEvent>>,
+    ready: Option<ReadyProbe>,
+    timeout: Option<Duration>,
 }
 
 enum ExecOutcome {
@@ -792,6 +1182,8 @@ enum ExecOutcome {
 enum SuccessOutcome {
     CacheHit,
     Run,
+    // Cache miss while only restoring outputs, the task wasn't executed
+    Missing,
 }
 
 impl ExecContext {
@@ -813,12 +1205,35 @@ impl ExecContext {
         telemetry: &PackageTaskEventBuilder,
     ) -> Result<(), InternalError> {
         let tracker = tracker.start().await;
+        if let Some(output) = &self.task_event_output {
+            TaskEvent::TaskStarted {
+                task: &self.task_id.to_string(),
+            }
+            .emit(output);
+        }
         let span = tracing::debug_span!("execute_task", task = %self.task_id.task());
         span.follows_from(parent_span_id);
-        let mut result = self
-            .execute_inner(&output_client, telemetry)
-            .instrument(span)
-            .await;
+        let mut callback = Some(callback);
+        let mut send_callback = |result| {
+            if let Some(callback) = callback.take() {
+                callback.send(result).ok();
+            }
+        };
+        let (log_probe, ready) = ready::start(self.ready.clone());
+        let mut result = {
+            let inner = self
+                .execute_inner(&output_client, telemetry, log_probe)
+                .instrument(span);
+            tokio::pin!(inner);
+            tokio::select! {
+                result = &mut inner => result,
+                _ = ready => {
+                    // Tasks that depend on this one can start now that it's ready
+                    send_callback(Ok(()));
+                    inner.await
+                }
+            }
+        };
 
         // If the task resulted in an error, do not group in order to better highlight
         // the error.
@@ -834,13 +1249,25 @@ impl ExecContext {
             }
         };
 
+        if let Some(output) = &self.task_event_output {
+            self.emit_finished_event(output, &result);
+        }
+
         match result {
             Ok(ExecOutcome::Success(outcome)) => {
-                let task_summary = match outcome {
-                    SuccessOutcome::CacheHit => tracker.cached().await,
-                    SuccessOutcome::Run => tracker.build_succeeded(0).await,
+                let (task_summary, cache_status) = match outcome {
+                    SuccessOutcome::CacheHit => (tracker.cached().await, TaskEventCacheStatus::Hit),
+                    SuccessOutcome::Run => {
+                        (tracker.build_succeeded(0).await, TaskEventCacheStatus::Miss)
+                    }
+                    SuccessOutcome::Missing => {
+                        tracker.missing().await;
+                        send_callback(Ok(()));
+                        return Ok(());
+                    }
                 };
-                callback.send(Ok(())).ok();
+                self.send_timing_event(&task_summary, cache_status);
+                send_callback(Ok(()));
                 if let Some(client) = spaces_client {
                     let logs = logs.expect("spaces enabled logs should be collected");
                     let info = self.spaces_task_info(self.task_id.clone(), task_summary, logs);
@@ -849,18 +1276,18 @@ impl ExecContext {
             }
             Ok(ExecOutcome::Task { exit_code, message }) => {
                 let task_summary = tracker.build_failed(exit_code, message).await;
-                callback
-                    .send(match self.continue_on_error {
-                        true => Ok(()),
-                        false => Err(StopExecution),
-                    })
-                    .ok();
-
-                match (spaces_client, self.continue_on_error) {
+                self.send_timing_event(&task_summary, TaskEventCacheStatus::Miss);
+                send_callback(match self.continue_on_error {
+                    ContinueMode::Always => Ok(()),
+                    ContinueMode::DependenciesSuccessful => Err(StopExecution::DependentTasks),
+                    ContinueMode::Never => Err(StopExecution::AllTasks),
+                });
+
+                match (spaces_client, self.continue_on_error != ContinueMode::Never) {
                     // Nothing to do
                     (None, true) => (),
                     // Shut down manager
-                    (None, false) => self.manager.stop().await,
+                    (None, false) => self.manager.stop_with_grace(self.kill_timeout).await,
                     // Send task
                     (Some(client), true) => {
                         let logs = logs.expect("spaced enabled logs should be collected");
@@ -873,22 +1300,24 @@ impl ExecContext {
                         let info = self.spaces_task_info(self.task_id.clone(), task_summary, logs);
                         // Ignore spaces result as that indicates handler is shut down and we are
                         // unable to send information to spaces
-                        let (_spaces_result, _) =
-                            tokio::join!(client.finish_task(info), self.manager.stop());
+                        let (_spaces_result, _) = tokio::join!(
+                            client.finish_task(info),
+                            self.manager.stop_with_grace(self.kill_timeout)
+                        );
                     }
                 }
             }
             Ok(ExecOutcome::Shutdown) => {
                 tracker.cancel();
-                callback.send(Err(StopExecution)).ok();
+                send_callback(Err(StopExecution::AllTasks));
                 // Probably overkill here, but we should make sure the process manager is
                 // stopped if we think we're shutting down.
-                self.manager.stop().await;
+                self.manager.stop_with_grace(self.kill_timeout).await;
             }
             Err(e) => {
                 tracker.cancel();
-                callback.send(Err(StopExecution)).ok();
-                self.manager.stop().await;
+                send_callback(Err(StopExecution::AllTasks));
+                self.manager.stop_with_grace(self.kill_timeout).await;
                 return Err(e);
             }
         }
@@ -896,6 +1325,39 @@ impl ExecContext {
         Ok(())
     }
 
+    fn emit_finished_event(
+        &self,
+        output: &TaskEventOutput,
+        result: &Result<ExecOutcome, InternalError>,
+    ) {
+        let (exit_code, cache_status) = match result {
+            Ok(ExecOutcome::Success(SuccessOutcome::CacheHit)) => {
+                (Some(0), TaskEventCacheStatus::Hit)
+            }
+            Ok(ExecOutcome::Success(SuccessOutcome::Run)) => (Some(0), TaskEventCacheStatus::Miss),
+            Ok(ExecOutcome::Success(SuccessOutcome::Missing)) => (None, TaskEventCacheStatus::Miss),
+            Ok(ExecOutcome::Task { exit_code, .. }) => (*exit_code, TaskEventCacheStatus::Miss),
+            // The task didn't finish so there's nothing to report
+            Ok(ExecOutcome::Shutdown) | Err(_) => return,
+        };
+        TaskEvent::TaskFinished {
+            task: &self.task_id.to_string(),
+            exit_code,
+            cache_status,
+        }
+        .emit(output);
+    }
+
+    fn send_timing_event(
+        &self,
+        task_summary: &TaskExecutionSummary,
+        cache_status: TaskEventCacheStatus,
+    ) {
+        if let Some(sender) = &self.timing_sender {
+            TaskTimingEvent::new(self.task_id.clone(), task_summary, cache_status).send(sender);
+        }
+    }
+
     fn prefixed_ui<'a, W: Write>(
         &self,
         output_client: &'a TaskOutput<W>,
@@ -904,6 +1366,7 @@ impl ExecContext {
             TaskOutput::Direct(client) => TaskCacheOutput::Direct(Visitor::prefixed_ui(
                 self.color_config,
                 self.is_github_actions,
+                self.log_timestamps,
                 client.stdout(),
                 client.stderr(),
                 self.pretty_prefix.clone(),
@@ -916,6 +1379,7 @@ impl ExecContext {
         &mut self,
         output_client: &TaskOutput<impl Write>,
         telemetry: &PackageTaskEventBuilder,
+        log_probe: Option<LogProbe>,
     ) -> Result<ExecOutcome, InternalError> {
         let task_start = Instant::now();
         let mut prefixed_ui = self.prefixed_ui(output_client);
@@ -928,15 +1392,19 @@ impl ExecContext {
         }
 
         if !self.task_cache.is_caching_disabled() {
-            let missing_platform_env = self.platform_env.validate(&self.execution_env);
-            if !missing_platform_env.is_empty() {
+            let execution_env = self
+                .strict_execution_env
+                .as_ref()
+                .unwrap_or(&self.execution_env);
+            if let Some(warning) = platform_env_warning(
+                self.platform_env.as_ref(),
+                &self.task_id_for_display,
+                execution_env,
+            ) {
                 self.warnings
                     .lock()
                     .expect("warnings lock poisoned")
-                    .push(TaskWarning {
-                        task_id: self.task_id_for_display.clone(),
-                        missing_platform_env,
-                    });
+                    .push(warning);
             }
         }
 
@@ -962,6 +1430,10 @@ impl ExecContext {
             }
         }
 
+        if self.task_cache.only_restore() {
+            return Ok(ExecOutcome::Success(SuccessOutcome::Missing));
+        }
+
         let package_manager_binary = which(self.package_manager.command())?;
 
         let mut cmd = Command::new(package_manager_binary);
@@ -980,6 +1452,7 @@ impl ExecContext {
         // We clear the env before populating it with variables we expect
         cmd.env_clear();
         cmd.envs(self.execution_env.iter());
+        cmd.envs(self.env_values.iter());
         // Always last to make sure it overwrites any user configured env var.
         cmd.env("TURBO_HASH", &self.task_hash);
 
@@ -999,7 +1472,11 @@ impl ExecContext {
 
         cmd.open_stdin();
 
-        let mut process = match self.manager.spawn(cmd, Duration::from_millis(500)) {
+        let mut process = match self
+            .manager
+            .spawn_with_retry(cmd, Duration::from_millis(500), SpawnRetry::default())
+            .await
+        {
             Some(Ok(child)) => child,
             // Turbo was unable to spawn a process
             Some(Err(e)) => {
@@ -1036,14 +1513,29 @@ impl ExecContext {
             process.stdin();
         }
 
-        let mut stdout_writer = self
-            .task_cache
-            .output_writer(prefixed_ui.task_writer())
-            .inspect_err(|_| {
-                telemetry.track_error(TrackedErrors::FailedToCaptureOutputs);
-            })?;
+        // The probe wraps the log writer rather than the terminal writer so it sees
+        // all output regardless of the output logs mode
+        let mut stdout_writer = LogProbeWriter::new(
+            self.task_cache
+                .output_writer(prefixed_ui.task_writer())
+                .inspect_err(|_| {
+                    telemetry.track_error(TrackedErrors::FailedToCaptureOutputs);
+                })?,
+            log_probe,
+        );
 
-        let exit_status = match process.wait_with_piped_outputs(&mut stdout_writer).await {
+        let Some(wait_result) =
+            wait_with_timeout(&mut process, &mut stdout_writer, self.timeout).await
+        else {
+            process.stop_with_grace(self.kill_timeout).await;
+            // Flush whatever output the task produced before it was stopped
+            if let Err(e) = stdout_writer.flush() {
+                error!("error flushing logs: {e}");
+            }
+            return Ok(self.on_timeout(process.label(), &mut prefixed_ui));
+        };
+
+        let exit_status = match wait_result {
             Ok(Some(exit_status)) => exit_status,
             Err(e) => {
                 telemetry.track_error(TrackedErrors::FailedToPipeOutputs);
@@ -1082,6 +1574,8 @@ impl ExecContext {
                     }
                 }
 
+                self.task_cache.on_success(&mut prefixed_ui);
+
                 // Return success outcome
                 Ok(ExecOutcome::Success(SuccessOutcome::Run))
             }
@@ -1095,7 +1589,7 @@ impl ExecContext {
                 }
                 let error = TaskErrorCause::from_execution(process.label().to_string(), code);
                 let message = error.to_string();
-                if self.continue_on_error {
+                if self.continue_on_error != ContinueMode::Never {
                     prefixed_ui.warn("command finished with error, but continuing...");
                 } else {
                     prefixed_ui.error(&format!("command finished with error: {error}"));
@@ -1119,6 +1613,38 @@ impl ExecContext {
         }
     }
 
+    /// Records a task that ran past its timeout as failed
+    fn on_timeout<W: Write>(
+        &mut self,
+        command: &str,
+        prefixed_ui: &mut TaskCacheOutput<W>,
+    ) -> ExecOutcome {
+        let timeout = self
+            .timeout
+            .expect("only called when the task has a timeout");
+        if let Err(e) = self.task_cache.on_error(prefixed_ui) {
+            error!("error reading logs: {e}");
+        }
+        let error = TaskErrorCause::Timeout {
+            command: command.to_string(),
+            timeout,
+        };
+        let message = error.to_string();
+        if self.continue_on_error != ContinueMode::Never {
+            prefixed_ui.warn("command timed out, but continuing...");
+        } else {
+            prefixed_ui.error(&format!("command finished with error: {error}"));
+        }
+        self.errors.lock().expect("lock poisoned").push(TaskError {
+            task_id: self.task_id_for_display.clone(),
+            cause: error,
+        });
+        ExecOutcome::Task {
+            exit_code: None,
+            message,
+        }
+    }
+
     fn spaces_task_info(
         &self,
         task_id: TaskId<'static>,
@@ -1158,6 +1684,61 @@ impl DryRunExecContext {
     }
 }
 
+/// Writes out a task's ndjson summary once its dry run has checked the cache.
+/// The checks run concurrently, but summaries are written in the order the
+/// tasks were queued.
+struct NdjsonSequencer<'a> {
+    summaries: summary::NdjsonTaskSummaries<'a>,
+    pending: FuturesOrdered<JoinHandle<(TaskId<'static>, Result<(), InternalError>)>>,
+}
+
+impl<'a> NdjsonSequencer<'a> {
+    fn new(summaries: summary::NdjsonTaskSummaries<'a>) -> Self {
+        Self {
+            summaries,
+            pending: FuturesOrdered::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        task_id: TaskId<'static>,
+        dry_run_exec_context: DryRunExecContext,
+        tracker: TaskTracker<()>,
+    ) {
+        self.pending.push_back(tokio::spawn(async move {
+            let result = dry_run_exec_context.execute_dry_run(tracker).await;
+            (task_id, result)
+        }));
+    }
+
+    /// Writes out every task at the front of the queue that has finished
+    /// without waiting on the rest
+    fn write_finished(&mut self) -> Result<(), Error> {
+        while let Some(Some(finished)) = self.pending.next().now_or_never() {
+            self.write(finished)?;
+        }
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<(), Error> {
+        while let Some(finished) = self.pending.next().await {
+            self.write(finished)?;
+        }
+        Ok(())
+    }
+
+    fn write(
+        &self,
+        finished: Result<(TaskId<'static>, Result<(), InternalError>), JoinError>,
+    ) -> Result<(), Error> {
+        let (task_id, result) = finished.unwrap_or_else(|e| panic!("task executor panicked: {e}"));
+        result.map_err(|e| Error::InternalErrors(e.to_string()))?;
+        println!("{}", self.summaries.format_task(task_id)?);
+        Ok(())
+    }
+}
+
 /// Struct for displaying information about task's cache
 enum TaskCacheOutput<W> {
     Direct(PrefixedUI<W>),
@@ -1241,3 +1822,160 @@ impl<W: Write> TaskOutput<W> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{HashMap, HashSet},
+        time::{Duration, Instant},
+    };
+
+    use miette::SourceSpan;
+    use test_case::test_case;
+    use turborepo_env::{platform::PlatformEnv, EnvironmentVariableMap};
+    use turborepo_errors::Spanned;
+
+    use super::{
+        check_recursive_turbo, output_client_behavior, platform_env_warning, should_prompt,
+        turbo_span, wait_with_timeout, Error, RecursiveTurbo,
+    };
+    use crate::{
+        cli::OutputLogsMode,
+        opts::ResolvedLogOrder,
+        process::{ChildExit, Command, ProcessManager},
+        run::task_id::TaskId,
+    };
+
+    #[test_case(false, false, Some(false) ; "non-interactive")]
+    #[test_case(false, true, Some(false) ; "non-interactive tui")]
+    #[test_case(true, false, Some(true) ; "interactive")]
+    #[test_case(true, true, None ; "interactive tui")]
+    fn test_should_prompt(is_interactive: bool, uses_tui: bool, expected: Option<bool>) {
+        let task_id = TaskId::new("web", "deploy");
+        match should_prompt(&task_id, is_interactive, uses_tui) {
+            Ok(prompt) => assert_eq!(Some(prompt), expected),
+            Err(Error::ConfirmWithTui { task_id }) => {
+                assert_eq!(expected, None);
+                assert_eq!(task_id, "web#deploy");
+            }
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test_case("//", "turbo run build", Some(RecursiveTurbo::Error) ; "root")]
+    #[test_case("//", "turbo run lint", Some(RecursiveTurbo::Error) ; "root other task")]
+    #[test_case("//", "tsc && turbo", Some(RecursiveTurbo::Error) ; "root bare turbo")]
+    #[test_case("web", "turbo run build", Some(RecursiveTurbo::Warning) ; "package")]
+    #[test_case("web", "turbo build --filter=docs", Some(RecursiveTurbo::Warning) ; "package without run")]
+    #[test_case("web", "tsc && turbo run lint build", Some(RecursiveTurbo::Warning) ; "package chained")]
+    #[test_case("web", "turbo run docs#build", Some(RecursiveTurbo::Warning) ; "package qualified task")]
+    #[test_case("web", "turbo run lint", None ; "package other task")]
+    #[test_case("web", "turbo run lint --filter build", None ; "package flag value")]
+    #[test_case("web", "turbo-ignore && next build", None ; "package not turbo")]
+    fn test_check_recursive_turbo(package: &str, command: &str, expected: Option<RecursiveTurbo>) {
+        let task_names = HashSet::from(["build", "test"]);
+        assert_eq!(
+            check_recursive_turbo(package, command, &task_names),
+            expected
+        );
+    }
+
+    #[test_case("turbo run build", 0 ; "bare")]
+    #[test_case("tsc && turbo run build", 7 ; "chained")]
+    #[test_case("echo \\\"turbo\\\" && turbo build", 18 ; "escaped quotes")]
+    fn test_turbo_span(script: &str, expected_offset: usize) {
+        let text = format!(r#"{{ "scripts": {{ "build": "{script}" }} }}"#);
+        let start = text.find(script).unwrap() - 1;
+        let end = start + script.len() + 2;
+        let cmd = Spanned::new(())
+            .with_range(start..end)
+            .with_text(text.clone());
+
+        let span = turbo_span(&cmd).expect("span should be found");
+        assert_eq!(span, SourceSpan::from((start + 1 + expected_offset, 5)));
+        assert_eq!(&text[span.offset()..span.offset() + span.len()], "turbo");
+    }
+
+    #[test_case(true, Some(vec!["VERCEL_URL"]) ; "enabled")]
+    #[test_case(false, None ; "disabled")]
+    fn test_platform_env_warning(validate: bool, expected: Option<Vec<&str>>) {
+        let platform_env = PlatformEnv::from_keys(vec!["VERCEL_URL".into(), "CI".into()]);
+        let execution_env =
+            EnvironmentVariableMap::from(HashMap::from([("CI".to_string(), "1".to_string())]));
+
+        let warning = platform_env_warning(
+            validate.then_some(&platform_env),
+            "web#build",
+            &execution_env,
+        );
+        assert_eq!(
+            warning.map(|warning| warning.missing_platform_env),
+            expected.map(|missing| missing.into_iter().map(String::from).collect())
+        );
+    }
+
+    #[test]
+    fn test_platform_env_warning_summary() {
+        let platform_env = PlatformEnv::from_keys(vec!["VERCEL_URL".into()]);
+        let warning = platform_env_warning(
+            Some(&platform_env),
+            "web#build",
+            &EnvironmentVariableMap::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(warning.summary()).unwrap(),
+            serde_json::json!({ "taskId": "web#build", "missingPlatformEnv": ["VERCEL_URL"] })
+        );
+
+        let recursive_turbo = TaskWarning {
+            task_id: "web#build".into(),
+            missing_platform_env: Vec::new(),
+            recursive_turbo_command: Some("turbo run build".into()),
+        };
+        assert_eq!(recursive_turbo.summary(), None);
+    }
+
+    #[test_case(ResolvedLogOrder::Stream, OutputLogsMode::Full, false, "Passthrough" ; "stream")]
+    #[test_case(ResolvedLogOrder::Stream, OutputLogsMode::ErrorsOnly, false, "Passthrough" ; "stream errors only")]
+    #[test_case(ResolvedLogOrder::Stream, OutputLogsMode::ErrorsOnlyWithSummary, false, "Grouped" ; "stream errors only with summary")]
+    #[test_case(ResolvedLogOrder::Stream, OutputLogsMode::ErrorsOnlyWithSummary, true, "InMemoryBuffer" ; "stream with spaces")]
+    #[test_case(ResolvedLogOrder::Grouped, OutputLogsMode::Full, false, "Grouped" ; "grouped")]
+    #[test_case(ResolvedLogOrder::Grouped, OutputLogsMode::ErrorsOnlyWithSummary, false, "Grouped" ; "grouped errors only with summary")]
+    fn test_output_client_behavior(
+        log_order: ResolvedLogOrder,
+        output_logs: OutputLogsMode,
+        spaces_enabled: bool,
+        expected: &str,
+    ) {
+        let behavior = output_client_behavior(log_order, output_logs, spaces_enabled);
+        assert_eq!(format!("{behavior:?}"), expected);
+    }
+
+    #[test_case("sleep_5_interruptable.js", Some(Duration::from_millis(100)), None ; "timed out")]
+    #[test_case("hello_world.js", Some(Duration::from_secs(5)), Some(ChildExit::Finished(Some(0))) ; "finished in time")]
+    #[test_case("hello_world.js", None, Some(ChildExit::Finished(Some(0))) ; "no timeout")]
+    #[tokio::test]
+    async fn test_wait_with_timeout(
+        script: &str,
+        timeout: Option<Duration>,
+        expected: Option<ChildExit>,
+    ) {
+        let manager = ProcessManager::new(false);
+        let mut cmd = Command::new("node");
+        cmd.args([format!("./test/scripts/{script}")]);
+        let start = Instant::now();
+        let mut process = manager.spawn(cmd, Duration::from_secs(1)).unwrap().unwrap();
+
+        let mut output = Vec::new();
+        let exit = wait_with_timeout(&mut process, &mut output, timeout)
+            .await
+            .map(|exit| exit.unwrap().expect("child should have exited"));
+        assert_eq!(exit, expected);
+        if expected.is_none() {
+            assert!(start.elapsed() < Duration::from_secs(2));
+            assert_eq!(process.stop().await, Some(ChildExit::Killed));
+        }
+    }
+}
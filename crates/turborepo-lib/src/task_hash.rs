@@ -4,7 +4,7 @@ use std::{
 };
 
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::{debug, Span};
 use turbopath::{AbsoluteSystemPath, AnchoredSystemPath, AnchoredSystemPathBuf};
@@ -23,7 +23,7 @@ use crate::{
     hash::{FileHashes, LockFilePackages, TaskHashable, TurboHash},
     opts::RunOpts,
     run::task_id::TaskId,
-    task_graph::TaskDefinition,
+    task_graph::{TaskDefinition, TaskOutputs},
     DaemonClient, DaemonConnector,
 };
 
@@ -235,6 +235,60 @@ pub struct TaskHashTrackerState {
     package_task_cache: HashMap<TaskId<'static>, CacheHitMetadata>,
     #[serde(skip)]
     package_task_inputs_expanded_hashes: HashMap<TaskId<'static>, FileHashes>,
+    #[serde(skip)]
+    package_task_hash_inputs: HashMap<TaskId<'static>, TaskHashInputs>,
+}
+
+/// Breakdown of the values that went into a task's hash. Only collected when
+/// `--verbose-hashes` or `--why-hash` is passed as it's used to debug
+/// unexpected cache misses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHashInputs {
+    pub global_hash: String,
+    pub hash_of_files: String,
+    pub external_deps_hash: Option<String>,
+    pub task_dependency_hashes: Vec<String>,
+    pub outputs: TaskOutputs,
+    pub pass_through_args: Vec<String>,
+    pub env: Vec<String>,
+    // Values are hashed so secrets don't end up in the summary
+    pub resolved_env_vars: Vec<String>,
+    pub pass_through_env: Vec<String>,
+    pub env_mode: EnvMode,
+}
+
+impl TaskHashInputs {
+    /// The inputs as labeled values, in the order they are fed into the hash
+    pub fn components(&self) -> Vec<(&'static str, String)> {
+        let outputs = self
+            .outputs
+            .inclusions
+            .iter()
+            .cloned()
+            .chain(
+                self.outputs
+                    .exclusions
+                    .iter()
+                    .map(|exclusion| format!("!{exclusion}")),
+            )
+            .collect::<Vec<_>>();
+        vec![
+            ("Global Hash", self.global_hash.clone()),
+            ("Dependency Hashes", self.task_dependency_hashes.join(", ")),
+            ("Hash of Files", self.hash_of_files.clone()),
+            (
+                "External Dependencies Hash",
+                self.external_deps_hash.clone().unwrap_or_default(),
+            ),
+            ("Outputs", outputs.join(", ")),
+            ("Passed Through Args", self.pass_through_args.join(", ")),
+            ("Env Vars", self.env.join(", ")),
+            ("Env Vars Values", self.resolved_env_vars.join(", ")),
+            ("Passed Through Env Vars", self.pass_through_env.join(", ")),
+            ("Env Mode", self.env_mode.to_string()),
+        ]
+    }
 }
 
 /// Caches package-inputs hashes, and package-task hashes.
@@ -366,12 +420,7 @@ impl<'a> TaskHasher<'a> {
             is_monorepo.then(|| get_external_deps_hash(&workspace.transitive_dependencies));
 
         if !hashable_env_pairs.is_empty() {
-            debug!(
-                "task hash env vars for {}:{}\n vars: {:?}",
-                task_id.package(),
-                task_id.task(),
-                hashable_env_pairs
-            );
+            debug!("{}", hashed_env_vars_message(task_id, &env_vars.all));
         }
 
         let package_dir = workspace.package_path().to_unix();
@@ -396,10 +445,36 @@ impl<'a> TaskHasher<'a> {
                 .as_deref()
                 .unwrap_or_default(),
             env_mode: task_env_mode,
+            env_values: &task_definition.env_values,
         };
 
+        // Hashing consumes the hashable so the breakdown is captured beforehand
+        let record_hash_inputs =
+            self.run_opts.verbose_hashes || self.run_opts.explains_hash_of(task_id);
+        let hash_inputs = record_hash_inputs.then(|| TaskHashInputs {
+            global_hash: task_hashable.global_hash.to_owned(),
+            hash_of_files: task_hashable.hash_of_files.to_owned(),
+            external_deps_hash: task_hashable.external_deps_hash.clone(),
+            task_dependency_hashes: task_hashable.task_dependency_hashes.clone(),
+            outputs: task_hashable.outputs.clone(),
+            pass_through_args: task_hashable.pass_through_args.to_vec(),
+            env: task_hashable.env.to_vec(),
+            resolved_env_vars: env_vars.all.to_secret_hashable(),
+            // Pass through env is only part of the hash in strict mode
+            pass_through_env: match task_env_mode {
+                EnvMode::Strict => task_hashable.pass_through_env.to_vec(),
+                EnvMode::Loose => Vec::new(),
+            },
+            env_mode: task_env_mode,
+        });
+
         let task_hash = task_hashable.calculate_task_hash();
 
+        if let Some(hash_inputs) = hash_inputs {
+            self.task_hash_tracker
+                .insert_hash_inputs(task_id.clone(), hash_inputs);
+        }
+
         self.task_hash_tracker.insert_hash(
             task_id.clone(),
             env_vars,
@@ -532,7 +607,14 @@ impl<'a> TaskHasher<'a> {
 
                 Ok(pass_through_env)
             }
-            EnvMode::Loose => Ok(self.env_at_execution_start.clone()),
+            EnvMode::Loose => {
+                // The global env can contain variables loaded from an env file. Those
+                // have already been resolved so that the environment takes precedence,
+                // which means this only adds variables that aren't set in it.
+                let mut env = self.env_at_execution_start.clone();
+                env.union(global_env);
+                Ok(env)
+            }
         }
     }
 }
@@ -651,6 +733,16 @@ impl TaskHashTracker {
         state.package_task_cache.insert(task_id, cache_status);
     }
 
+    pub fn hash_inputs(&self, task_id: &TaskId) -> Option<TaskHashInputs> {
+        let state = self.state.lock().expect("hash tracker mutex poisoned");
+        state.package_task_hash_inputs.get(task_id).cloned()
+    }
+
+    fn insert_hash_inputs(&self, task_id: TaskId<'static>, hash_inputs: TaskHashInputs) {
+        let mut state = self.state.lock().expect("hash tracker mutex poisoned");
+        state.package_task_hash_inputs.insert(task_id, hash_inputs);
+    }
+
     pub fn get_expanded_inputs(&self, task_id: &TaskId) -> Option<FileHashes> {
         let state = self.state.lock().expect("hash tracker mutex poisoned");
         state
@@ -660,6 +752,17 @@ impl TaskHashTracker {
     }
 }
 
+/// Describes the environment variables that contributed to a task's hash.
+/// Only the variable names are included so values never end up in logs.
+fn hashed_env_vars_message(task_id: &TaskId, env_vars: &EnvironmentVariableMap) -> String {
+    format!(
+        "task hash env vars for {}:{}\n vars: {:?}",
+        task_id.package(),
+        task_id.task(),
+        env_vars.names()
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -673,4 +776,65 @@ mod test {
         assert_send::<TaskHashTracker>();
         assert_sync::<TaskHashTracker>();
     }
+
+    #[test]
+    fn test_hashed_env_vars_message() {
+        let task_id = TaskId::new("web", "build");
+        let env_vars = EnvironmentVariableMap::from(HashMap::from([
+            ("NODE_ENV".to_string(), "production".to_string()),
+            ("API_KEY".to_string(), "super-secret".to_string()),
+        ]));
+
+        let message = hashed_env_vars_message(&task_id, &env_vars);
+        assert_eq!(
+            message,
+            format!(
+                "task hash env vars for web:build\n vars: {:?}",
+                env_vars.names()
+            )
+        );
+        assert!(message.contains(r#"["API_KEY", "NODE_ENV"]"#));
+        assert!(!message.contains("production"));
+        assert!(!message.contains("super-secret"));
+    }
+
+    #[test]
+    fn test_hash_inputs_components() {
+        let hash_inputs = TaskHashInputs {
+            global_hash: "global".to_string(),
+            hash_of_files: "files".to_string(),
+            external_deps_hash: None,
+            task_dependency_hashes: vec!["aaa".to_string(), "bbb".to_string()],
+            outputs: TaskOutputs {
+                inclusions: vec!["dist/**".to_string()],
+                exclusions: vec!["dist/tmp/**".to_string()],
+            },
+            pass_through_args: vec![],
+            env: vec!["NODE_ENV".to_string()],
+            resolved_env_vars: vec!["NODE_ENV=abc123".to_string()],
+            pass_through_env: vec![],
+            env_mode: EnvMode::Strict,
+        };
+
+        let components = hash_inputs.components();
+        assert_eq!(
+            components,
+            vec![
+                ("Global Hash", "global".to_string()),
+                ("Dependency Hashes", "aaa, bbb".to_string()),
+                ("Hash of Files", "files".to_string()),
+                ("External Dependencies Hash", "".to_string()),
+                ("Outputs", "dist/**, !dist/tmp/**".to_string()),
+                ("Passed Through Args", "".to_string()),
+                ("Env Vars", "NODE_ENV".to_string()),
+                ("Env Vars Values", "NODE_ENV=abc123".to_string()),
+                ("Passed Through Env Vars", "".to_string()),
+                ("Env Mode", "strict".to_string()),
+            ]
+        );
+        // A round trip through a run summary keeps the breakdown stable
+        let round_tripped: TaskHashInputs =
+            serde_json::from_str(&serde_json::to_string(&hash_inputs).unwrap()).unwrap();
+        assert_eq!(round_tripped.components(), components);
+    }
 }
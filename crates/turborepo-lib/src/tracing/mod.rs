@@ -1,3 +1,5 @@
+mod perfetto;
+
 use std::{io::Stderr, marker::PhantomData, path::Path, sync::Mutex};
 
 use chrono::Local;
@@ -8,7 +10,6 @@ use owo_colors::{
 use tracing::{field::Visit, metadata::LevelFilter, trace, Event, Level, Subscriber};
 use tracing_appender::{non_blocking::NonBlocking, rolling::RollingFileAppender};
 use tracing_chrome::ChromeLayer;
-pub use tracing_subscriber::reload::Error;
 use tracing_subscriber::{
     filter::Filtered,
     fmt::{
@@ -24,6 +25,17 @@ use tracing_subscriber::{
 };
 use turborepo_ui::ColorConfig;
 
+use self::perfetto::PerfettoLayer;
+use crate::cli::ProfileFormat;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Reload(#[from] reload::Error),
+    #[error("unable to create profile: {0}")]
+    Profile(#[from] std::io::Error),
+}
+
 // a lot of types to make sure we record the right relationships
 
 /// Note that we cannot express the type of `std::io::stderr` directly, so
@@ -70,6 +82,11 @@ type ChromeReload = reload::Layer<Option<ChromeLog>, DaemonLogLayered>;
 /// `ChromeLogLayered`, which forms the base for the next layer.
 type ChromeLogLayered = layer::Layered<ChromeReload, DaemonLogLayered>;
 
+/// A logger that writes spans and events as a Perfetto protobuf trace. It is
+/// applied on top of the `ChromeLogLayered` layer, and only one of the two is
+/// enabled at a time.
+type PerfettoReload = reload::Layer<Option<PerfettoLayer>, ChromeLogLayered>;
+
 pub struct TurboSubscriber {
     daemon_update: Handle<Option<DaemonLog>, StdErrLogLayered>,
 
@@ -80,6 +97,9 @@ pub struct TurboSubscriber {
     chrome_update: Handle<Option<ChromeLog>, DaemonLogLayered>,
     chrome_guard: Mutex<Option<tracing_chrome::FlushGuard>>,
 
+    perfetto_update: Handle<Option<PerfettoLayer>, ChromeLogLayered>,
+    perfetto_guard: Mutex<Option<perfetto::FlushGuard>>,
+
     #[cfg(feature = "pprof")]
     pprof_guard: pprof::ProfilerGuard<'static>,
 }
@@ -101,7 +121,7 @@ impl TurboSubscriber {
     /// - `set_daemon_logger` enables logging to a file, using the standard
     ///   formatter.
     /// - `enable_chrome_tracing` enables logging to a file, using the chrome
-    ///   tracing formatter.
+    ///   tracing or Perfetto formats.
     pub fn new_with_verbosity(verbosity: usize, color_config: &ColorConfig) -> Self {
         let level_override = match verbosity {
             0 => None,
@@ -138,11 +158,14 @@ impl TurboSubscriber {
         let logrotate: DaemonLogFiltered = logrotate.with_filter(env_filter(LevelFilter::INFO));
 
         let (chrome, chrome_update) = reload::Layer::new(Option::<ChromeLog>::None);
+        let (perfetto, perfetto_update): (PerfettoReload, _) =
+            reload::Layer::new(Option::<PerfettoLayer>::None);
 
         let registry = Registry::default()
             .with(stderr)
             .with(logrotate)
-            .with(chrome);
+            .with(chrome)
+            .with(perfetto);
 
         #[cfg(feature = "pprof")]
         let pprof_guard = pprof::ProfilerGuardBuilder::default()
@@ -158,6 +181,8 @@ impl TurboSubscriber {
             daemon_guard: Mutex::new(None),
             chrome_update,
             chrome_guard: Mutex::new(None),
+            perfetto_update,
+            perfetto_guard: Mutex::new(None),
             #[cfg(feature = "pprof")]
             pprof_guard,
         }
@@ -184,25 +209,39 @@ impl TurboSubscriber {
         Ok(())
     }
 
-    /// Enables chrome tracing.
+    /// Enables chrome tracing, writing the trace in the given format.
     #[tracing::instrument(skip(self, to_file))]
     pub fn enable_chrome_tracing<P: AsRef<Path>>(
         &self,
         to_file: P,
         include_args: bool,
+        format: ProfileFormat,
     ) -> Result<(), Error> {
-        let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
-            .file(to_file)
-            .include_args(include_args)
-            .include_locations(true)
-            .trace_style(tracing_chrome::TraceStyle::Async)
-            .build();
-
-        self.chrome_update.reload(Some(layer))?;
-        self.chrome_guard
-            .lock()
-            .expect("not poisoned")
-            .replace(guard);
+        match format {
+            ProfileFormat::Chrome => {
+                let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+                    .file(to_file)
+                    .include_args(include_args)
+                    .include_locations(true)
+                    .trace_style(tracing_chrome::TraceStyle::Async)
+                    .build();
+
+                self.chrome_update.reload(Some(layer))?;
+                self.chrome_guard
+                    .lock()
+                    .expect("not poisoned")
+                    .replace(guard);
+            }
+            ProfileFormat::Perfetto => {
+                let (layer, guard) = PerfettoLayer::new(to_file, include_args)?;
+
+                self.perfetto_update.reload(Some(layer))?;
+                self.perfetto_guard
+                    .lock()
+                    .expect("not poisoned")
+                    .replace(guard);
+            }
+        }
 
         Ok(())
     }
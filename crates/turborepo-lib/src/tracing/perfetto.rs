@@ -0,0 +1,395 @@
+//! A tracing layer that writes spans and events as a Perfetto protobuf trace
+//! that can be loaded in https://ui.perfetto.dev
+//!
+//! Only the handful of `TracePacket` fields that we emit are modelled here.
+//! Every span gets its own track, nested under the track of its parent span,
+//! so that concurrent async spans don't need to be strictly nested.
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use prost::Message;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id},
+    Event, Metadata, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+// All packets are written from a single sequence
+const SEQUENCE_ID: u32 = 1;
+// The track that every span track is nested under
+const PROCESS_TRACK_UUID: u64 = 1;
+
+#[derive(Clone, PartialEq, Message)]
+struct Trace {
+    #[prost(message, repeated, tag = "1")]
+    packet: Vec<TracePacket>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct TracePacket {
+    #[prost(uint64, optional, tag = "8")]
+    timestamp: Option<u64>,
+    #[prost(uint32, optional, tag = "10")]
+    trusted_packet_sequence_id: Option<u32>,
+    #[prost(oneof = "PacketData", tags = "11, 60")]
+    data: Option<PacketData>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum PacketData {
+    #[prost(message, tag = "11")]
+    TrackEvent(TrackEvent),
+    #[prost(message, tag = "60")]
+    TrackDescriptor(TrackDescriptor),
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct TrackDescriptor {
+    #[prost(uint64, optional, tag = "1")]
+    uuid: Option<u64>,
+    #[prost(string, optional, tag = "2")]
+    name: Option<String>,
+    #[prost(message, optional, tag = "3")]
+    process: Option<ProcessDescriptor>,
+    #[prost(uint64, optional, tag = "5")]
+    parent_uuid: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProcessDescriptor {
+    #[prost(int32, optional, tag = "1")]
+    pid: Option<i32>,
+    #[prost(string, optional, tag = "6")]
+    process_name: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct TrackEvent {
+    #[prost(message, repeated, tag = "4")]
+    debug_annotations: Vec<DebugAnnotation>,
+    #[prost(enumeration = "TrackEventType", optional, tag = "9")]
+    r#type: Option<i32>,
+    #[prost(uint64, optional, tag = "11")]
+    track_uuid: Option<u64>,
+    #[prost(string, repeated, tag = "22")]
+    categories: Vec<String>,
+    #[prost(string, optional, tag = "23")]
+    name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+enum TrackEventType {
+    SliceBegin = 1,
+    SliceEnd = 2,
+    Instant = 3,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DebugAnnotation {
+    #[prost(string, optional, tag = "10")]
+    name: Option<String>,
+    #[prost(oneof = "AnnotationValue", tags = "2, 3, 4, 5, 6")]
+    value: Option<AnnotationValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum AnnotationValue {
+    #[prost(bool, tag = "2")]
+    Bool(bool),
+    #[prost(uint64, tag = "3")]
+    Uint(u64),
+    #[prost(int64, tag = "4")]
+    Int(i64),
+    #[prost(double, tag = "5")]
+    Double(f64),
+    #[prost(string, tag = "6")]
+    String(String),
+}
+
+/// The track a span's slice is drawn on, stored in the span's extensions
+struct TrackUuid(u64);
+
+pub struct PerfettoLayer {
+    writer: Arc<Mutex<BufWriter<File>>>,
+    start: Instant,
+    include_args: bool,
+    next_track_uuid: AtomicU64,
+}
+
+/// Flushes the trace file when dropped
+pub struct FlushGuard {
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl PerfettoLayer {
+    pub fn new(to_file: impl AsRef<Path>, include_args: bool) -> io::Result<(Self, FlushGuard)> {
+        let writer = Arc::new(Mutex::new(BufWriter::new(File::create(to_file)?)));
+        let layer = Self {
+            writer: writer.clone(),
+            start: Instant::now(),
+            include_args,
+            next_track_uuid: AtomicU64::new(PROCESS_TRACK_UUID + 1),
+        };
+
+        layer.write_packet(
+            None,
+            PacketData::TrackDescriptor(TrackDescriptor {
+                uuid: Some(PROCESS_TRACK_UUID),
+                process: Some(ProcessDescriptor {
+                    pid: Some(std::process::id() as i32),
+                    process_name: Some("turbo".to_string()),
+                }),
+                ..Default::default()
+            }),
+        );
+
+        Ok((layer, FlushGuard { writer }))
+    }
+
+    // Each packet is written as its own single entry `Trace`. Concatenated
+    // protobuf messages merge their repeated fields, so the file is a valid
+    // trace no matter when it stops being written to.
+    fn write_packet(&self, timestamp: Option<u64>, data: PacketData) {
+        let trace = Trace {
+            packet: vec![TracePacket {
+                timestamp,
+                trusted_packet_sequence_id: Some(SEQUENCE_ID),
+                data: Some(data),
+            }],
+        };
+        // A failure to write the profile shouldn't affect the run
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.write_all(&trace.encode_to_vec());
+        }
+    }
+
+    fn write_event(
+        &self,
+        track_uuid: u64,
+        event_type: TrackEventType,
+        metadata: Option<&Metadata<'_>>,
+        debug_annotations: Vec<DebugAnnotation>,
+    ) {
+        let timestamp = self.start.elapsed().as_nanos() as u64;
+        self.write_packet(
+            Some(timestamp),
+            PacketData::TrackEvent(TrackEvent {
+                debug_annotations,
+                r#type: Some(event_type as i32),
+                track_uuid: Some(track_uuid),
+                categories: metadata
+                    .map(|metadata| vec![metadata.target().to_string()])
+                    .unwrap_or_default(),
+                name: metadata.map(|metadata| metadata.name().to_string()),
+            }),
+        );
+    }
+
+    fn annotations(&self, metadata: &Metadata<'_>) -> Vec<DebugAnnotation> {
+        let mut annotations = Vec::new();
+        if let (Some(file), Some(line)) = (metadata.file(), metadata.line()) {
+            annotations.push(DebugAnnotation {
+                name: Some("location".to_string()),
+                value: Some(AnnotationValue::String(format!("{file}:{line}"))),
+            });
+        }
+        annotations
+    }
+}
+
+impl<S> Layer<S> for PerfettoLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        // Spans created before the layer was enabled won't have a track
+        let parent_uuid = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<TrackUuid>().map(|track| track.0))
+            .unwrap_or(PROCESS_TRACK_UUID);
+        let uuid = self.next_track_uuid.fetch_add(1, Ordering::Relaxed);
+        span.extensions_mut().insert(TrackUuid(uuid));
+
+        self.write_packet(
+            None,
+            PacketData::TrackDescriptor(TrackDescriptor {
+                uuid: Some(uuid),
+                name: Some(attrs.metadata().name().to_string()),
+                parent_uuid: Some(parent_uuid),
+                ..Default::default()
+            }),
+        );
+
+        let mut annotations = self.annotations(attrs.metadata());
+        if self.include_args {
+            attrs.record(&mut AnnotationVisitor(&mut annotations));
+        }
+        self.write_event(
+            uuid,
+            TrackEventType::SliceBegin,
+            Some(attrs.metadata()),
+            annotations,
+        );
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let track_uuid = ctx
+            .event_span(event)
+            .and_then(|span| span.extensions().get::<TrackUuid>().map(|track| track.0))
+            .unwrap_or(PROCESS_TRACK_UUID);
+
+        let mut annotations = self.annotations(event.metadata());
+        if self.include_args {
+            event.record(&mut AnnotationVisitor(&mut annotations));
+        }
+        self.write_event(
+            track_uuid,
+            TrackEventType::Instant,
+            Some(event.metadata()),
+            annotations,
+        );
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(track_uuid) = ctx
+            .span(&id)
+            .and_then(|span| span.extensions().get::<TrackUuid>().map(|track| track.0))
+        else {
+            return;
+        };
+        self.write_event(track_uuid, TrackEventType::SliceEnd, None, Vec::new());
+    }
+}
+
+/// Records the fields of a span or event as debug annotations
+struct AnnotationVisitor<'a>(&'a mut Vec<DebugAnnotation>);
+
+impl<'a> AnnotationVisitor<'a> {
+    fn push(&mut self, field: &Field, value: AnnotationValue) {
+        self.0.push(DebugAnnotation {
+            name: Some(field.name().to_string()),
+            value: Some(value),
+        });
+    }
+}
+
+impl<'a> Visit for AnnotationVisitor<'a> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, AnnotationValue::Double(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, AnnotationValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(field, AnnotationValue::Uint(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, AnnotationValue::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, AnnotationValue::String(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.push(field, AnnotationValue::String(format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tracing::info_span;
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    fn write_trace(include_args: bool) -> Vec<u8> {
+        let tmp = tempfile::tempdir().unwrap();
+        let trace_path = tmp.path().join("profile.pftrace");
+        let (layer, guard) = PerfettoLayer::new(&trace_path, include_args).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let run = info_span!("run", task = "web#build").entered();
+            info_span!("hash_task").in_scope(|| tracing::info!(files = 3, "hashed"));
+            drop(run);
+        });
+        drop(guard);
+
+        std::fs::read(trace_path).unwrap()
+    }
+
+    #[test]
+    fn test_writes_perfetto_trace() {
+        let contents = write_trace(true);
+        // Every Perfetto protobuf trace starts with the tag of the first
+        // `Trace.packet` entry: field 1, length delimited
+        assert!(!contents.is_empty());
+        assert_eq!(contents[0], 0x0A);
+
+        let trace = Trace::decode(contents.as_slice()).unwrap();
+        let events = trace
+            .packet
+            .iter()
+            .filter_map(|packet| match &packet.data {
+                Some(PacketData::TrackEvent(event)) => Some(event),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let names = events
+            .iter()
+            .filter(|event| event.r#type == Some(TrackEventType::SliceBegin as i32))
+            .map(|event| event.name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["run", "hash_task"]);
+        // Every slice that was started was also ended
+        let ends = events
+            .iter()
+            .filter(|event| event.r#type == Some(TrackEventType::SliceEnd as i32))
+            .count();
+        assert_eq!(ends, 2);
+
+        let run_start = events[0];
+        assert!(run_start
+            .debug_annotations
+            .iter()
+            .any(|annotation| annotation.name.as_deref() == Some("task")
+                && annotation.value == Some(AnnotationValue::String("web#build".to_string()))));
+    }
+
+    #[test]
+    fn test_anonymous_perfetto_trace_omits_args() {
+        let trace = Trace::decode(write_trace(false).as_slice()).unwrap();
+        for packet in trace.packet {
+            if let Some(PacketData::TrackEvent(event)) = packet.data {
+                assert!(event
+                    .debug_annotations
+                    .iter()
+                    .all(|annotation| annotation.name.as_deref() == Some("location")));
+            }
+        }
+    }
+}
@@ -34,6 +34,8 @@ enum Strategy {
     Workspace {
         // Map of package names to their package specific turbo.json
         packages: HashMap<PackageName, AbsoluteSystemPathBuf>,
+        // Synthesize a turbo.json from a package's scripts if it doesn't have one
+        synthesize_missing: bool,
     },
     WorkspaceNoTurboJson {
         // Map of package names to their scripts
@@ -57,10 +59,27 @@ impl TurboJsonLoader {
         Self {
             repo_root,
             cache: HashMap::new(),
-            strategy: Strategy::Workspace { packages },
+            strategy: Strategy::Workspace {
+                packages,
+                synthesize_missing: false,
+            },
         }
     }
 
+    /// Synthesize a turbo.json with uncached tasks from a package's
+    /// `package.json` scripts if the package doesn't have a turbo.json.
+    /// Only has an effect on workspace loaders.
+    #[allow(dead_code)]
+    pub fn with_synthesized_package_tasks(mut self) -> Self {
+        if let Strategy::Workspace {
+            synthesize_missing, ..
+        } = &mut self.strategy
+        {
+            *synthesize_missing = true;
+        }
+        self
+    }
+
     /// Create a loader that will construct turbo.json structures based on
     /// workspace `package.json`s.
     pub fn workspace_no_turbo_json<'a>(
@@ -147,9 +166,19 @@ impl TurboJsonLoader {
                     load_from_root_package_json(&self.repo_root, root_turbo_json, package_json)
                 }
             }
-            Strategy::Workspace { packages } => {
+            Strategy::Workspace {
+                packages,
+                synthesize_missing,
+            } => {
                 let path = packages.get(package).ok_or_else(|| Error::NoTurboJSON)?;
-                load_from_file(&self.repo_root, path)
+                match load_from_file(&self.repo_root, path) {
+                    Err(Error::NoTurboJSON)
+                        if *synthesize_missing && !matches!(package, PackageName::Root) =>
+                    {
+                        load_from_package_json(path)
+                    }
+                    result => result,
+                }
             }
             Strategy::WorkspaceNoTurboJson { packages } => {
                 let script_names = packages.get(package).ok_or(Error::NoTurboJSON)?;
@@ -230,6 +259,18 @@ fn load_from_file(
     }
 }
 
+/// Synthesizes a package turbo.json from the scripts in the `package.json`
+/// next to where the package's turbo.json would be
+fn load_from_package_json(turbo_json_path: &AbsoluteSystemPath) -> Result<TurboJson, Error> {
+    let package_json_path = turbo_json_path
+        .parent()
+        .expect("turbo.json path has a parent")
+        .join_component("package.json");
+    let package_json = PackageJson::load(&package_json_path)?;
+    let scripts = package_json.scripts.into_keys().collect::<Vec<_>>();
+    workspace_turbo_json_from_scripts(&scripts)
+}
+
 fn load_from_root_package_json(
     repo_root: &AbsoluteSystemPath,
     turbo_json_path: &AbsoluteSystemPath,
@@ -353,7 +394,10 @@ mod test {
     use test_case::test_case;
 
     use super::*;
-    use crate::{task_graph::TaskDefinition, turbo_json::CONFIG_FILE};
+    use crate::{
+        task_graph::TaskDefinition,
+        turbo_json::{validate_extends, CONFIG_FILE},
+    };
 
     #[test_case(r"{}", TurboJson::default() ; "empty")]
     #[test_case(r#"{ "globalDependencies": ["tsconfig.json", "jest.config.js"] }"#,
@@ -362,6 +406,12 @@ mod test {
             ..TurboJson::default()
         }
     ; "global dependencies (sorted)")]
+    #[test_case(r#"{ "globalEnvFile": ".env.allowlist" }"#,
+        TurboJson {
+            global_env_file: Some(".env.allowlist".to_string()),
+            ..TurboJson::default()
+        }
+    ; "global env file")]
     #[test_case(r#"{ "globalPassThroughEnv": ["GITHUB_TOKEN", "AWS_SECRET_KEY"] }"#,
         TurboJson {
             global_pass_through_env: Some(vec!["AWS_SECRET_KEY".to_string(), "GITHUB_TOKEN".to_string()]),
@@ -385,6 +435,7 @@ mod test {
                 packages: vec![(PackageName::Root, root_turbo_json)]
                     .into_iter()
                     .collect(),
+                synthesize_missing: false,
             },
         };
 
@@ -581,7 +632,10 @@ mod test {
         let mut loader = TurboJsonLoader {
             repo_root: repo_root.to_owned(),
             cache: HashMap::new(),
-            strategy: Strategy::Workspace { packages },
+            strategy: Strategy::Workspace {
+                packages,
+                synthesize_missing: false,
+            },
         };
         let result = loader.load(&PackageName::from("a"));
         assert!(
@@ -597,6 +651,63 @@ mod test {
         assert_eq!(turbo_json.tasks.len(), 1);
     }
 
+    #[test]
+    fn test_workspace_custom_root_turbo_json() {
+        let root_dir = tempdir().unwrap();
+        let repo_root = AbsoluteSystemPath::from_std_path(root_dir.path()).unwrap();
+        repo_root
+            .join_component(CONFIG_FILE)
+            .create_with_contents(r#"{"tasks": {"build": {}}}"#)
+            .unwrap();
+        let ci_turbo_json = repo_root.join_component("turbo.ci.json");
+        ci_turbo_json
+            .create_with_contents(r#"{"tasks": {"lint": {}, "test": {}}}"#)
+            .unwrap();
+
+        let mut loader =
+            TurboJsonLoader::workspace(repo_root.to_owned(), ci_turbo_json, std::iter::empty());
+        let turbo_json = loader.load(&PackageName::Root).unwrap();
+        let tasks = turbo_json
+            .tasks
+            .keys()
+            .map(|task| task.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(tasks, vec!["lint", "test"]);
+    }
+
+    #[test]
+    fn test_env_values_loading() {
+        let root_dir = tempdir().unwrap();
+        let repo_root = AbsoluteSystemPath::from_std_path(root_dir.path()).unwrap();
+        let root_turbo_json = repo_root.join_component(CONFIG_FILE);
+        root_turbo_json
+            .create_with_contents(
+                r#"{"tasks": {"build": {"envValues": {"NODE_OPTIONS": "--max-old-space-size=4096", "FORCE_COLOR": "1"}}}}"#,
+            )
+            .unwrap();
+
+        let mut loader =
+            TurboJsonLoader::workspace(repo_root.to_owned(), root_turbo_json, std::iter::empty());
+        let turbo_json = loader.load(&PackageName::Root).unwrap();
+        let build = turbo_json
+            .tasks
+            .get(&TaskName::from("build"))
+            .expect("build task should be loaded")
+            .as_inner();
+
+        let task_definition = TaskDefinition::try_from(build.clone()).unwrap();
+        assert_eq!(
+            task_definition.env_values,
+            BTreeMap::from([
+                ("FORCE_COLOR".to_string(), "1".to_string()),
+                (
+                    "NODE_OPTIONS".to_string(),
+                    "--max-old-space-size=4096".to_string()
+                ),
+            ])
+        );
+    }
+
     #[test]
     fn test_turbo_json_caching() {
         let root_dir = tempdir().unwrap();
@@ -610,7 +721,10 @@ mod test {
         let mut loader = TurboJsonLoader {
             repo_root: repo_root.to_owned(),
             cache: HashMap::new(),
-            strategy: Strategy::Workspace { packages },
+            strategy: Strategy::Workspace {
+                packages,
+                synthesize_missing: false,
+            },
         };
         a_turbo_json
             .create_with_contents(r#"{"tasks": {"build": {}}}"#)
@@ -622,6 +736,55 @@ mod test {
         assert!(loader.load(&PackageName::from("a")).is_ok());
     }
 
+    #[test]
+    fn test_workspace_synthesizes_missing_turbo_json() {
+        let root_dir = tempdir().unwrap();
+        let repo_root = AbsoluteSystemPath::from_std_path(root_dir.path()).unwrap();
+        let root_turbo_json = repo_root.join_component(CONFIG_FILE);
+        root_turbo_json
+            .create_with_contents(r#"{"tasks": {"build": {}}}"#)
+            .unwrap();
+        let a_package_json = repo_root.join_components(&["packages", "a", "package.json"]);
+        a_package_json.ensure_dir().unwrap();
+        a_package_json
+            .create_with_contents(r#"{"scripts": {"build": "tsc", "dev": "tsc --watch"}}"#)
+            .unwrap();
+        let packages = HashMap::from([(
+            PackageName::from("a"),
+            PackageInfo {
+                package_json_path: repo_root.anchor(&a_package_json).unwrap(),
+                ..Default::default()
+            },
+        )]);
+
+        let mut strict_loader = TurboJsonLoader::workspace(
+            repo_root.to_owned(),
+            root_turbo_json.clone(),
+            packages.iter(),
+        );
+        assert!(matches!(
+            strict_loader.load(&PackageName::from("a")).unwrap_err(),
+            Error::NoTurboJSON
+        ));
+
+        let mut loader =
+            TurboJsonLoader::workspace(repo_root.to_owned(), root_turbo_json, packages.iter())
+                .with_synthesized_package_tasks();
+        let turbo_json = loader.load(&PackageName::from("a")).unwrap();
+        assert_eq!(turbo_json.extends.as_inner(), &["//".to_owned()]);
+        assert_eq!(turbo_json.tasks.len(), 2);
+        for task_name in ["build", "dev"] {
+            let def = &turbo_json.tasks[&TaskName::from(task_name)];
+            assert_eq!(
+                def.cache.as_ref().map(|cache| *cache.as_inner()),
+                Some(false)
+            );
+        }
+        // The root turbo.json is never synthesized
+        let root_turbo_json = loader.load(&PackageName::Root).unwrap();
+        assert_eq!(root_turbo_json.tasks.len(), 1);
+    }
+
     #[test]
     fn test_no_turbo_json() {
         let root_dir = tempdir().unwrap();
@@ -676,4 +839,113 @@ mod test {
         let goose_err = loader.load(&PackageName::from("goose")).unwrap_err();
         assert!(matches!(goose_err, Error::NoTurboJSON));
     }
+
+    /// Writes a turbo.json for each package and returns a workspace loader for
+    /// them. Packages other than the root are placed under `packages/`.
+    fn workspace_loader(
+        repo_root: &AbsoluteSystemPath,
+        turbo_jsons: &[(PackageName, &str)],
+    ) -> Result<TurboJsonLoader> {
+        let mut packages = HashMap::new();
+        for (package, contents) in turbo_jsons {
+            let turbo_json_path = match package {
+                PackageName::Root => repo_root.join_component(CONFIG_FILE),
+                PackageName::Other(name) => {
+                    repo_root.join_components(&["packages", name.as_str(), CONFIG_FILE])
+                }
+            };
+            turbo_json_path.ensure_dir()?;
+            turbo_json_path.create_with_contents(contents)?;
+            packages.insert(package.clone(), turbo_json_path);
+        }
+
+        Ok(TurboJsonLoader {
+            repo_root: repo_root.to_owned(),
+            cache: HashMap::new(),
+            strategy: Strategy::Workspace {
+                packages,
+                synthesize_missing: false,
+            },
+        })
+    }
+
+    #[test_case(r#"{ "taks": {} }"#, "taks" ; "top level")]
+    #[test_case(r#"{ "remoteCache": { "enbled": true } }"#, "enbled" ; "remote cache")]
+    #[test_case(r#"{ "tasks": { "build": { "outptus": [] } } }"#, "outptus" ; "task")]
+    fn test_unknown_keys(turbo_json_content: &str, unknown_key: &str) -> Result<()> {
+        let root_dir = tempdir()?;
+        let repo_root = AbsoluteSystemPath::from_std_path(root_dir.path())?;
+        let mut loader = workspace_loader(repo_root, &[(PackageName::Root, turbo_json_content)])?;
+        let Err(Error::TurboJsonParseError(err)) = loader.load(&PackageName::Root) else {
+            panic!("expected unknown key {unknown_key} to fail parsing");
+        };
+
+        let diagnostic = miette::Diagnostic::related(&err)
+            .and_then(|mut related| related.next())
+            .expect("expected a diagnostic for the unknown key");
+        assert!(
+            diagnostic.to_string().contains(unknown_key),
+            "expected '{diagnostic}' to name {unknown_key}"
+        );
+        let label = diagnostic
+            .labels()
+            .and_then(|mut labels| labels.next())
+            .expect("diagnostic should point at the unknown key");
+        assert!(
+            turbo_json_content[label.offset()..label.offset() + label.len()].contains(unknown_key)
+        );
+
+        Ok(())
+    }
+
+    #[test_case(r#"{ "tasks": { "web#build#lint": {} } }"#, "\"web#build#lint\"" ; "double delimiter")]
+    #[test_case(r#"{ "tasks": { "#build": {} } }"#, "\"#build\"" ; "empty package")]
+    #[test_case(r#"{ "tasks": { "build": {}, "web#": {} } }"#, "\"web#\"" ; "empty task")]
+    fn test_invalid_task_name(turbo_json_content: &str, task_key: &str) -> Result<()> {
+        let root_dir = tempdir()?;
+        let repo_root = AbsoluteSystemPath::from_std_path(root_dir.path())?;
+        let mut loader = workspace_loader(repo_root, &[(PackageName::Root, turbo_json_content)])?;
+        let Err(Error::TurboJsonParseError(err)) = loader.load(&PackageName::Root) else {
+            panic!("expected {task_key} to fail parsing");
+        };
+
+        let diagnostic = miette::Diagnostic::related(&err)
+            .and_then(|mut related| related.next())
+            .expect("expected a diagnostic for the task name");
+        assert!(diagnostic.to_string().starts_with("Invalid task name"));
+        let label = diagnostic
+            .labels()
+            .and_then(|mut labels| labels.next())
+            .expect("diagnostic should point at the task name");
+        assert_eq!(
+            &turbo_json_content[label.offset()..label.offset() + label.len()],
+            task_key
+        );
+
+        Ok(())
+    }
+
+    #[test_case(r#"{ "extends": ["//"], "tasks": {} }"#, None ; "root")]
+    #[test_case(r#"{ "extends": ["web"], "tasks": {} }"#, Some("You can only extend from the root workspace") ; "other package")]
+    #[test_case(r#"{ "extends": ["//", "web"], "tasks": {} }"#, Some("You can only extend from the root workspace") ; "multiple")]
+    #[test_case(r#"{ "tasks": {} }"#, Some("No \"extends\" key found") ; "missing")]
+    fn test_workspace_extends(
+        turbo_json_content: &str,
+        expected_error: Option<&str>,
+    ) -> Result<()> {
+        let root_dir = tempdir()?;
+        let repo_root = AbsoluteSystemPath::from_std_path(root_dir.path())?;
+        let mut loader =
+            workspace_loader(repo_root, &[(PackageName::from("a"), turbo_json_content)])?;
+        let errors = loader
+            .load(&PackageName::from("a"))
+            .unwrap()
+            .validate(&[validate_extends]);
+        assert_eq!(
+            errors.first().map(|error| error.to_string()).as_deref(),
+            expected_error
+        );
+
+        Ok(())
+    }
 }
@@ -8,6 +8,7 @@ use biome_deserialize_macros::Deserializable;
 use camino::Utf8Path;
 use clap::ValueEnum;
 use miette::{NamedSource, SourceSpan};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use struct_iterable::Iterable;
 use turbopath::AbsoluteSystemPath;
@@ -16,13 +17,13 @@ use turborepo_repository::package_graph::ROOT_PKG_NAME;
 use turborepo_unescape::UnescapedString;
 
 use crate::{
-    cli::{EnvMode, OutputLogsMode},
+    cli::{CacheCompression, EnvMode, OutputLogsMode},
     config::{ConfigurationOptions, Error, InvalidEnvPrefixError},
     run::{
         task_access::TaskAccessTraceFile,
         task_id::{TaskId, TaskName},
     },
-    task_graph::{TaskDefinition, TaskOutputs},
+    task_graph::{ReadyProbe, TaskDefinition, TaskOutputs},
 };
 
 mod loader;
@@ -53,6 +54,8 @@ pub struct TurboJson {
     pub(crate) extends: Spanned<Vec<String>>,
     pub(crate) global_deps: Vec<String>,
     pub(crate) global_env: Vec<String>,
+    // Repo relative path to a file listing additional global env var names
+    pub(crate) global_env_file: Option<String>,
     pub(crate) global_pass_through_env: Option<Vec<String>>,
     pub(crate) tasks: Pipeline,
 }
@@ -60,6 +63,7 @@ pub struct TurboJson {
 // Iterable is required to enumerate allowed keys
 #[derive(Clone, Debug, Default, Iterable, Serialize, Deserializable)]
 #[serde(rename_all = "camelCase")]
+#[deserializable(unknown_fields = "deny")]
 pub(crate) struct RawRemoteCacheOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     api_url: Option<String>,
@@ -97,6 +101,7 @@ impl From<&RawRemoteCacheOptions> for ConfigurationOptions {
 
 #[derive(Serialize, Default, Debug, Clone, Iterable, Deserializable)]
 #[serde(rename_all = "camelCase")]
+#[deserializable(unknown_fields = "deny")]
 // The raw deserialized turbo.json file.
 pub struct RawTurboJson {
     #[serde(skip)]
@@ -115,6 +120,8 @@ pub struct RawTurboJson {
     #[serde(skip_serializing_if = "Option::is_none")]
     global_env: Option<Vec<Spanned<UnescapedString>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    global_env_file: Option<Spanned<UnescapedString>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     global_pass_through_env: Option<Vec<Spanned<UnescapedString>>>,
     // Tasks is a map of task entries which define the task graph
     // and cache behavior on a per task or per package-task basis.
@@ -139,6 +146,12 @@ pub struct RawTurboJson {
     pub env_mode: Option<EnvMode>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_dir: Option<Spanned<UnescapedString>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_compression: Option<CacheCompression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform_env_validation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scm_base: Option<Spanned<UnescapedString>>,
 
     #[deserializable(rename = "//")]
     #[serde(skip)]
@@ -182,6 +195,9 @@ pub enum UIMode {
     Stream,
     /// Use the web user interface (experimental)
     Web,
+    /// Write task events as JSON lines to stdout, the same as
+    /// `--log-order=json`
+    Json,
 }
 
 impl Default for UIMode {
@@ -202,6 +218,17 @@ impl UIMode {
     }
 }
 
+// Iterable is required to enumerate allowed keys
+#[derive(Serialize, Default, Debug, PartialEq, Clone, Iterable, Deserializable)]
+#[serde(rename_all = "camelCase")]
+#[deserializable(unknown_fields = "deny")]
+pub struct RawReadyProbe {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log: Option<UnescapedString>,
+}
+
 #[derive(Serialize, Default, Debug, PartialEq, Clone, Iterable, Deserializable)]
 #[serde(rename_all = "camelCase")]
 #[deserializable(unknown_fields = "deny")]
@@ -213,6 +240,8 @@ pub struct RawTaskDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     env: Option<Vec<Spanned<UnescapedString>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    env_values: Option<Spanned<BTreeMap<String, UnescapedString>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     inputs: Option<Vec<Spanned<UnescapedString>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pass_through_env: Option<Vec<Spanned<UnescapedString>>>,
@@ -226,6 +255,16 @@ pub struct RawTaskDefinition {
     output_logs: Option<Spanned<OutputLogsMode>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     interactive: Option<Spanned<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confirm: Option<Spanned<UnescapedString>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ready: Option<Spanned<RawReadyProbe>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_streams: Option<Spanned<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log_replay: Option<Spanned<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<Spanned<UnescapedString>>,
     // TODO: Remove this once we have the ability to load task definitions directly
     // instead of deriving them from a TurboJson
     #[serde(skip)]
@@ -261,8 +300,14 @@ impl RawTaskDefinition {
         set_field!(self, other, persistent);
         set_field!(self, other, interruptible);
         set_field!(self, other, env);
+        set_field!(self, other, env_values);
         set_field!(self, other, pass_through_env);
         set_field!(self, other, interactive);
+        set_field!(self, other, confirm);
+        set_field!(self, other, ready);
+        set_field!(self, other, merge_streams);
+        set_field!(self, other, log_replay);
+        set_field!(self, other, timeout);
         set_field!(self, other, env_mode);
     }
 }
@@ -340,6 +385,44 @@ impl TryFrom<RawTaskDefinition> for TaskDefinition {
             return Err(Error::InterruptibleButNotPersistent { span, text });
         }
 
+        let ready =
+            raw_task
+                .ready
+                .map(|ready| {
+                    let (span, text) = ready.span_and_text("turbo.json");
+                    if !persistent {
+                        return Err(Error::ReadyButNotPersistent { span, text });
+                    }
+                    match ready.into_inner() {
+                        RawReadyProbe {
+                            port: Some(port),
+                            log: None,
+                        } => Ok(ReadyProbe::Port(port)),
+                        RawReadyProbe {
+                            port: None,
+                            log: Some(pattern),
+                        } => {
+                            let pattern: String = pattern.into();
+                            Regex::new(&pattern).map_err(|source| {
+                                Error::InvalidReadyLogPattern { source, span, text }
+                            })?;
+                            Ok(ReadyProbe::Log(pattern))
+                        }
+                        _ => Err(Error::InvalidReadyProbe { span, text }),
+                    }
+                })
+                .transpose()?;
+
+        let timeout = raw_task
+            .timeout
+            .map(|timeout| {
+                humantime::parse_duration(timeout.as_inner()).map_err(|source| {
+                    let (span, text) = timeout.span_and_text("turbo.json");
+                    Error::InvalidTaskTimeout { source, span, text }
+                })
+            })
+            .transpose()?;
+
         let mut env_var_dependencies = HashSet::new();
         let mut topological_dependencies: Vec<Spanned<TaskName>> = Vec::new();
         let mut task_dependencies: Vec<Spanned<TaskName>> = Vec::new();
@@ -379,6 +462,17 @@ impl TryFrom<RawTaskDefinition> for TaskDefinition {
             .transpose()?
             .unwrap_or_default();
 
+        let env_values = raw_task
+            .env_values
+            .map(|env_values| {
+                env_values
+                    .into_inner()
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let inputs = raw_task
             .inputs
             .unwrap_or_default()
@@ -414,12 +508,18 @@ impl TryFrom<RawTaskDefinition> for TaskDefinition {
             topological_dependencies,
             task_dependencies,
             env,
+            env_values,
             inputs,
             pass_through_env,
             output_logs: *raw_task.output_logs.unwrap_or_default(),
             persistent,
             interruptible: *interruptible,
             interactive,
+            confirm: raw_task.confirm.map(|confirm| confirm.into_inner().into()),
+            ready,
+            merge_streams: raw_task.merge_streams.map(|merge_streams| *merge_streams),
+            log_replay: raw_task.log_replay.map(|log_replay| *log_replay),
+            timeout,
             env_mode: raw_task.env_mode,
         })
     }
@@ -531,6 +631,21 @@ impl TryFrom<RawTurboJson> for TurboJson {
             }
         }
 
+        let global_env_file = raw_turbo
+            .global_env_file
+            .map(|global_env_file| {
+                if Utf8Path::new(&global_env_file.value).is_absolute() {
+                    let (span, text) = global_env_file.span_and_text("turbo.json");
+                    return Err(Error::AbsolutePathInConfig {
+                        field: "globalEnvFile",
+                        span,
+                        text,
+                    });
+                }
+                Ok(global_env_file.into_inner().into())
+            })
+            .transpose()?;
+
         Ok(TurboJson {
             text: raw_turbo.span.text,
             path: raw_turbo.span.path,
@@ -539,6 +654,7 @@ impl TryFrom<RawTurboJson> for TurboJson {
                 global_env.sort();
                 global_env
             },
+            global_env_file,
             global_pass_through_env: raw_turbo
                 .global_pass_through_env
                 .map(|env| -> Result<Vec<String>, Error> {
@@ -691,6 +807,8 @@ fn gather_env_vars(
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
     use anyhow::Result;
     use biome_deserialize::json::deserialize_from_json_str;
     use biome_json_parser::JsonParserOptions;
@@ -702,9 +820,10 @@ mod tests {
     use super::{RawTurboJson, Spanned, UIMode};
     use crate::{
         cli::OutputLogsMode,
+        config::Error,
         run::task_id::TaskName,
-        task_graph::{TaskDefinition, TaskOutputs},
-        turbo_json::RawTaskDefinition,
+        task_graph::{ReadyProbe, TaskDefinition, TaskOutputs},
+        turbo_json::{RawReadyProbe, RawTaskDefinition},
     };
 
     #[test_case(
@@ -721,6 +840,100 @@ mod tests {
         TaskDefinition::default()
     ; "just persistent"
     )]
+    #[test_case(
+        r#"{ "confirm": "Deploy to production?" }"#,
+        RawTaskDefinition {
+            confirm: Some(Spanned::<UnescapedString>::new("Deploy to production?".into()).with_range(13..36)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            confirm: Some("Deploy to production?".to_string()),
+            ..TaskDefinition::default()
+        }
+    ; "confirm"
+    )]
+    #[test_case(
+        r#"{ "persistent": true, "ready": { "port": 3000 } }"#,
+        RawTaskDefinition {
+            persistent: Some(Spanned::new(true).with_range(16..20)),
+            ready: Some(Spanned::new(RawReadyProbe { port: Some(3000), log: None }).with_range(31..47)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            persistent: true,
+            ready: Some(ReadyProbe::Port(3000)),
+            ..TaskDefinition::default()
+        }
+    ; "ready port"
+    )]
+    #[test_case(
+        r#"{ "persistent": true, "ready": { "log": "listening on" } }"#,
+        RawTaskDefinition {
+            persistent: Some(Spanned::new(true).with_range(16..20)),
+            ready: Some(Spanned::new(RawReadyProbe { port: None, log: Some("listening on".into()) }).with_range(31..56)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            persistent: true,
+            ready: Some(ReadyProbe::Log("listening on".to_string())),
+            ..TaskDefinition::default()
+        }
+    ; "ready log"
+    )]
+    #[test_case(
+        r#"{ "mergeStreams": true }"#,
+        RawTaskDefinition {
+            merge_streams: Some(Spanned::new(true).with_range(18..22)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            merge_streams: Some(true),
+            ..TaskDefinition::default()
+        }
+    ; "merge streams"
+    )]
+    #[test_case(
+        r#"{ "logReplay": false }"#,
+        RawTaskDefinition {
+            log_replay: Some(Spanned::new(false).with_range(15..20)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            log_replay: Some(false),
+            ..TaskDefinition::default()
+        }
+    ; "log replay"
+    )]
+    #[test_case(
+        r#"{ "envValues": { "NODE_OPTIONS": "--max-old-space-size=4096" } }"#,
+        RawTaskDefinition {
+            env_values: Some(Spanned::new(BTreeMap::from([(
+                "NODE_OPTIONS".to_string(),
+                UnescapedString::from("--max-old-space-size=4096"),
+            )])).with_range(15..62)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            env_values: BTreeMap::from([(
+                "NODE_OPTIONS".to_string(),
+                "--max-old-space-size=4096".to_string(),
+            )]),
+            ..TaskDefinition::default()
+        }
+    ; "env values"
+    )]
+    #[test_case(
+        r#"{ "timeout": "5m" }"#,
+        RawTaskDefinition {
+            timeout: Some(Spanned::<UnescapedString>::new("5m".into()).with_range(13..17)),
+            ..RawTaskDefinition::default()
+        },
+        TaskDefinition {
+            timeout: Some(Duration::from_secs(300)),
+            ..TaskDefinition::default()
+        }
+    ; "timeout"
+    )]
     #[test_case(
         r#"{
           "dependsOn": ["cli#build"],
@@ -745,10 +958,17 @@ mod tests {
             persistent: Some(Spanned::new(true).with_range(278..282)),
             interactive: Some(Spanned::new(true).with_range(309..313)),
             interruptible: Some(Spanned::new(true).with_range(342..346)),
+            env_values: None,
+            confirm: None,
+            ready: None,
+            merge_streams: None,
+            log_replay: None,
+            timeout: None,
             env_mode: None,
         },
         TaskDefinition {
           env: vec!["OS".to_string()],
+          env_values: BTreeMap::new(),
           outputs: TaskOutputs {
               inclusions: vec!["package/a/dist".to_string()],
               exclusions: vec![],
@@ -762,6 +982,11 @@ mod tests {
           persistent: true,
           interactive: true,
           interruptible: true,
+          confirm: None,
+          ready: None,
+          merge_streams: None,
+          log_replay: None,
+          timeout: None,
           env_mode: None,
         }
       ; "full"
@@ -789,10 +1014,17 @@ mod tests {
             persistent: Some(Spanned::new(true).with_range(315..319)),
             interruptible: Some(Spanned::new(true).with_range(352..356)),
             interactive: None,
+            env_values: None,
+            confirm: None,
+            ready: None,
+            merge_streams: None,
+            log_replay: None,
+            timeout: None,
             env_mode: None,
         },
         TaskDefinition {
             env: vec!["OS".to_string()],
+            env_values: BTreeMap::new(),
             outputs: TaskOutputs {
                 inclusions: vec!["package\\a\\dist".to_string()],
                 exclusions: vec![],
@@ -806,6 +1038,11 @@ mod tests {
             persistent: true,
             interruptible: true,
             interactive: false,
+            confirm: None,
+            ready: None,
+            merge_streams: None,
+            log_replay: None,
+            timeout: None,
             env_mode: None,
         }
       ; "full (windows)"
@@ -830,6 +1067,37 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(r#"{ "ready": { "port": 3000 } }"# ; "not persistent")]
+    #[test_case(r#"{ "persistent": true, "ready": {} }"# ; "empty")]
+    #[test_case(r#"{ "persistent": true, "ready": { "port": 3000, "log": "ready" } }"# ; "port and log")]
+    #[test_case(r#"{ "persistent": true, "ready": { "log": "(" } }"# ; "invalid pattern")]
+    fn test_invalid_ready_probe(task_definition_content: &str) {
+        let deserialized_result = deserialize_from_json_str(
+            task_definition_content,
+            JsonParserOptions::default().with_allow_comments(),
+            "turbo.json",
+        );
+        let raw_task_definition: RawTaskDefinition =
+            deserialized_result.into_deserialized().unwrap();
+
+        assert!(TaskDefinition::try_from(raw_task_definition).is_err());
+    }
+
+    #[test_case(r#"{ "timeout": "soon" }"# ; "not a duration")]
+    #[test_case(r#"{ "timeout": "5" }"# ; "no unit")]
+    fn test_invalid_timeout(task_definition_content: &str) {
+        let deserialized_result = deserialize_from_json_str(
+            task_definition_content,
+            JsonParserOptions::default().with_allow_comments(),
+            "turbo.json",
+        );
+        let raw_task_definition: RawTaskDefinition =
+            deserialized_result.into_deserialized().unwrap();
+
+        let err = TaskDefinition::try_from(raw_task_definition).unwrap_err();
+        assert!(matches!(err, Error::InvalidTaskTimeout { .. }));
+    }
+
     #[test_case("[]", TaskOutputs::default() ; "empty")]
     #[test_case(r#"["target/**"]"#, TaskOutputs { inclusions: vec!["target/**".to_string()], exclusions: vec![] })]
     #[test_case(
@@ -903,6 +1171,7 @@ mod tests {
     #[test_case("hash-only", Some(OutputLogsMode::HashOnly) ; "hash-only")]
     #[test_case("new-only", Some(OutputLogsMode::NewOnly) ; "new-only")]
     #[test_case("errors-only", Some(OutputLogsMode::ErrorsOnly) ; "errors-only")]
+    #[test_case("errors-only-with-summary", Some(OutputLogsMode::ErrorsOnlyWithSummary) ; "errors-only-with-summary")]
     #[test_case("none", Some(OutputLogsMode::None) ; "none")]
     #[test_case("junk", None ; "invalid value")]
     fn test_parsing_output_logs_mode(output_logs: &str, expected: Option<OutputLogsMode>) {
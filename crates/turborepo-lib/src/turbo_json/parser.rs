@@ -15,7 +15,7 @@ use turborepo_errors::{ParseDiagnostic, WithMetadata};
 use turborepo_unescape::UnescapedString;
 
 use crate::{
-    run::task_id::TaskName,
+    run::task_id::{TaskName, TASK_DELIMITER},
     turbo_json::{Pipeline, RawTaskDefinition, RawTurboJson, Spanned},
 };
 
@@ -55,6 +55,21 @@ impl Deserializable for TaskName<'static> {
     }
 }
 
+/// Returns why a `<package>#<task>` key is malformed. Keys without a `#` are
+/// always valid.
+fn invalid_task_name_reason(task_name: &str) -> Option<&'static str> {
+    let (package, task) = task_name.split_once(TASK_DELIMITER)?;
+    if package.is_empty() {
+        Some("the package name before the `#` is empty")
+    } else if task.is_empty() {
+        Some("the task name after the `#` is empty")
+    } else if task.contains(TASK_DELIMITER) {
+        Some("a task name can only contain one `#`")
+    } else {
+        None
+    }
+}
+
 impl Deserializable for Pipeline {
     fn deserialize(
         value: &impl DeserializableValue,
@@ -83,6 +98,15 @@ impl DeserializationVisitor for PipelineVisitor {
         for (key, value) in members.flatten() {
             let task_name_range = value.range();
             let task_name = TaskName::deserialize(&key, "", diagnostics)?;
+            if let Some(reason) = invalid_task_name_reason(&task_name.to_string()) {
+                diagnostics.push(
+                    DeserializationDiagnostic::new(format!(
+                        "Invalid task name \"{task_name}\": {reason}"
+                    ))
+                    .with_range(key.range()),
+                );
+                continue;
+            }
             let task_name_start: usize = task_name_range.start().into();
             let task_name_end: usize = task_name_range.end().into();
             result.insert(
@@ -102,6 +126,7 @@ impl WithMetadata for RawTurboJson {
         self.extends.add_text(text.clone());
         self.global_dependencies.add_text(text.clone());
         self.global_env.add_text(text.clone());
+        self.global_env_file.add_text(text.clone());
         self.global_pass_through_env.add_text(text.clone());
         self.tasks.add_text(text.clone());
         self.cache_dir.add_text(text.clone());
@@ -113,6 +138,7 @@ impl WithMetadata for RawTurboJson {
         self.extends.add_path(path.clone());
         self.global_dependencies.add_path(path.clone());
         self.global_env.add_path(path.clone());
+        self.global_env_file.add_path(path.clone());
         self.global_pass_through_env.add_path(path.clone());
         self.tasks.add_path(path.clone());
         self.cache_dir.add_path(path.clone());
@@ -143,13 +169,19 @@ impl WithMetadata for RawTaskDefinition {
             depends_on.value.add_text(text.clone());
         }
         self.env.add_text(text.clone());
+        self.env_values.add_text(text.clone());
         self.inputs.add_text(text.clone());
         self.pass_through_env.add_text(text.clone());
         self.persistent.add_text(text.clone());
         self.interruptible.add_text(text.clone());
         self.outputs.add_text(text.clone());
         self.output_logs.add_text(text.clone());
-        self.interactive.add_text(text);
+        self.interactive.add_text(text.clone());
+        self.confirm.add_text(text.clone());
+        self.ready.add_text(text.clone());
+        self.merge_streams.add_text(text.clone());
+        self.log_replay.add_text(text.clone());
+        self.timeout.add_text(text);
     }
 
     fn add_path(&mut self, path: Arc<str>) {
@@ -158,13 +190,19 @@ impl WithMetadata for RawTaskDefinition {
             depends_on.value.add_path(path.clone());
         }
         self.env.add_path(path.clone());
+        self.env_values.add_path(path.clone());
         self.inputs.add_path(path.clone());
         self.pass_through_env.add_path(path.clone());
         self.persistent.add_path(path.clone());
         self.interruptible.add_path(path.clone());
         self.outputs.add_path(path.clone());
         self.output_logs.add_path(path.clone());
-        self.interactive.add_path(path);
+        self.interactive.add_path(path.clone());
+        self.confirm.add_path(path.clone());
+        self.ready.add_path(path.clone());
+        self.merge_streams.add_path(path.clone());
+        self.log_replay.add_path(path.clone());
+        self.timeout.add_path(path);
     }
 }
 
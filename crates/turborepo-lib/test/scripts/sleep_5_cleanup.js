@@ -0,0 +1,18 @@
+process.on("SIGINT", () => {
+  // simulate a process that takes a while to clean up before exiting
+  setTimeout(() => {
+    console.log("cleaned up");
+    process.exit(0);
+  }, 500);
+});
+
+function delay(time) {
+  return new Promise((resolve) => setTimeout(resolve, time));
+}
+
+async function run() {
+  await delay(5000);
+}
+// signal that our signal handler is registered
+console.log("here");
+run();
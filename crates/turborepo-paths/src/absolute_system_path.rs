@@ -14,6 +14,7 @@ use std::{
 use camino::{Utf8Component, Utf8Components, Utf8Path, Utf8PathBuf};
 use fs_err as fs;
 use path_clean::PathClean;
+use url::Url;
 use wax::CandidatePath;
 
 use crate::{
@@ -122,6 +123,13 @@ impl AbsoluteSystemPath {
         self.0.as_std_path()
     }
 
+    /// Converts the path into a `file://` URL, the inverse of
+    /// `AbsoluteSystemPathBuf::from_url`. Fails for paths that have no URL
+    /// form, such as Windows device paths.
+    pub fn to_url(&self) -> Result<Url, PathError> {
+        Url::from_file_path(self.as_std_path()).map_err(|_| PathError::NotUrlPath(self.to_string()))
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_str().as_bytes()
     }
@@ -360,6 +368,44 @@ impl AbsoluteSystemPath {
             .expect("collapsed path should be absolute")
     }
 
+    /// Returns true if this path is `ancestor` or is nested inside of it.
+    /// Unlike `contains`, both paths are canonicalized first so a symlink
+    /// that points outside of `ancestor` is not considered inside of it.
+    /// Paths that don't exist are resolved through their closest existing
+    /// parent. Only whole components are matched, so `/a/bc` is not inside
+    /// `/a/b`.
+    pub fn is_inside(&self, ancestor: &Self) -> Result<bool, PathError> {
+        let this = self.resolve_existing()?;
+        let ancestor = ancestor.resolve_existing()?;
+        Ok(this.0.starts_with(&ancestor.0))
+    }
+
+    // Canonicalizes the longest prefix of this path that exists on disk and
+    // lexically appends the remaining components.
+    fn resolve_existing(&self) -> Result<AbsoluteSystemPathBuf, PathError> {
+        let collapsed = self.collapse();
+        let mut existing: &AbsoluteSystemPath = &collapsed;
+        loop {
+            match existing.to_realpath() {
+                Ok(realpath) => {
+                    let rest = collapsed
+                        .0
+                        .strip_prefix(&existing.0)
+                        .expect("ancestor should be a prefix of the path");
+                    if rest.as_str().is_empty() {
+                        return Ok(realpath);
+                    }
+                    return Ok(AbsoluteSystemPathBuf(realpath.0.join(rest)));
+                }
+                Err(e) if e.is_io_error(io::ErrorKind::NotFound) => match existing.parent() {
+                    Some(parent) => existing = parent,
+                    None => return Ok(collapsed),
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     // TODO: consider consolidating with `relation_to_path` below
     pub fn contains(&self, other: &Self) -> bool {
         // On windows, trying to get a relative path between files on different volumes
@@ -584,6 +630,62 @@ mod tests {
         assert_eq!(base.contains(&other), expected);
     }
 
+    #[test_case(&["some", "repo"], true ; "reflexive")]
+    #[test_case(&["some", "repo", "packages", "libA", "..", "libB"], true ; "file:../libB inside")]
+    #[test_case(&["some", "repo", "packages", "libA", "..", "..", "..", "otherproject"], false ; "file:../../../otherproject outside")]
+    #[test_case(&["some", "repo2"], false ; "lexical prefix match")]
+    #[test_case(&["some"], false ; "parent")]
+    fn test_is_inside(path: &[&str], expected: bool) {
+        let root_token = match cfg!(windows) {
+            true => "C:\\",
+            false => "/",
+        };
+
+        let root = AbsoluteSystemPathBuf::new(
+            [root_token, "some", "repo"].join(std::path::MAIN_SEPARATOR_STR),
+        )
+        .unwrap();
+        let path = AbsoluteSystemPathBuf::new(
+            std::iter::once(root_token)
+                .chain(path.iter().copied())
+                .collect::<Vec<_>>()
+                .join(std::path::MAIN_SEPARATOR_STR),
+        )
+        .unwrap();
+
+        assert_eq!(path.is_inside(&root).unwrap(), expected);
+    }
+
+    // Creating directory symlinks on Windows requires elevated privileges
+    #[cfg(unix)]
+    #[test]
+    fn test_is_inside_follows_symlinks() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let tmp = AbsoluteSystemPathBuf::try_from(tmp.path())?;
+        let repo = tmp.join_component("repo");
+        let outside = tmp.join_component("outside");
+        let packages = repo.join_component("packages");
+        packages.join_component("libA").create_dir_all()?;
+        outside.create_dir_all()?;
+
+        let escaping = packages.join_component("escaping");
+        escaping.symlink_to_dir(outside.as_str())?;
+        let internal = packages.join_component("internal");
+        internal.symlink_to_dir(packages.join_component("libA").as_str())?;
+
+        // Lexically both links live inside the repo
+        assert!(repo.contains(&escaping));
+        assert!(!escaping.is_inside(&repo)?);
+        assert!(!escaping.join_component("nested").is_inside(&repo)?);
+        assert!(internal.is_inside(&repo)?);
+        assert!(packages.join_component("missing").is_inside(&repo)?);
+        // A symlinked ancestor is resolved the same way as the path
+        let linked_repo = tmp.join_component("linked-repo");
+        linked_repo.symlink_to_dir(repo.as_str())?;
+        assert!(internal.is_inside(&linked_repo)?);
+        Ok(())
+    }
+
     #[test]
     fn test_read_non_existing_to_string() -> Result<()> {
         let test_dir = tempfile::TempDir::with_prefix("read-existing")?;
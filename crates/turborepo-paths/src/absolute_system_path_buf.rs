@@ -5,10 +5,11 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use camino::{Utf8Components, Utf8Path, Utf8PathBuf};
+use camino::{Utf8Component, Utf8Components, Utf8Path, Utf8PathBuf};
 use fs_err as fs;
 use path_clean::PathClean;
 use serde::Serialize;
+use url::Url;
 
 use crate::{AbsoluteSystemPath, AnchoredSystemPathBuf, PathError};
 
@@ -94,6 +95,30 @@ impl AbsoluteSystemPathBuf {
         }
     }
 
+    /// Like `from_unknown`, but errors instead of reinterpreting a path that
+    /// is ambiguous. A path with a root or drive prefix must be fully absolute,
+    /// and a relative path must not escape `base` once it has been cleaned.
+    pub fn from_unknown_strict(
+        base: &AbsoluteSystemPath,
+        unknown: impl Into<Utf8PathBuf>,
+    ) -> Result<Self, PathError> {
+        let unknown: Utf8PathBuf = unknown.into();
+        if unknown.is_absolute() {
+            return Ok(Self(unknown));
+        }
+
+        let has_root = unknown.components().any(|component| {
+            matches!(component, Utf8Component::Prefix(_) | Utf8Component::RootDir)
+        });
+        let escapes_base = unknown.as_std_path().clean().components().next()
+            == Some(std::path::Component::ParentDir);
+        if has_root || escapes_base {
+            return Err(PathError::MalformedPath(unknown.into_string()));
+        }
+
+        Ok(Self::from_unknown(base, unknown))
+    }
+
     pub fn from_cwd(unknown: impl Into<Utf8PathBuf>) -> Result<Self, PathError> {
         let cwd = Self::cwd()?;
         Ok(Self::from_unknown(&cwd, unknown))
@@ -104,6 +129,38 @@ impl AbsoluteSystemPathBuf {
         Ok(Self(Utf8PathBuf::try_from(std::env::current_dir()?)?))
     }
 
+    /// Converts a `file://` URL into a path. Percent-encoded characters are
+    /// decoded and on Windows the `file:///C:/...` form becomes `C:\...`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use turbopath::AbsoluteSystemPathBuf;
+    /// use url::Url;
+    /// #[cfg(not(windows))]
+    /// {
+    ///   let url = Url::parse("file:///Users/user/my%20repo").unwrap();
+    ///   let path = AbsoluteSystemPathBuf::from_url(&url).unwrap();
+    ///   assert_eq!(path.as_str(), "/Users/user/my repo");
+    /// }
+    ///
+    /// #[cfg(windows)]
+    /// {
+    ///   let url = Url::parse("file:///C:/Users/user/my%20repo").unwrap();
+    ///   let path = AbsoluteSystemPathBuf::from_url(&url).unwrap();
+    ///   assert_eq!(path.as_str(), "C:\\Users\\user\\my repo");
+    /// }
+    /// ```
+    pub fn from_url(url: &Url) -> Result<Self, PathError> {
+        if url.scheme() != "file" {
+            return Err(PathError::NotFileUrl(url.to_string()));
+        }
+        let path = url
+            .to_file_path()
+            .map_err(|_| PathError::MalformedPath(url.to_string()))?;
+        Self::try_from(path)
+    }
+
     /// Anchors `path` at `self`.
     ///
     /// # Arguments
@@ -248,6 +305,8 @@ impl AsRef<Path> for AbsoluteSystemPathBuf {
 mod tests {
     use std::assert_matches::assert_matches;
 
+    use url::Url;
+
     use crate::{AbsoluteSystemPathBuf, PathError, RelativeUnixPathBuf};
 
     #[cfg(not(windows))]
@@ -274,6 +333,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_unknown_strict() {
+        let (base, absolute, malformed) = if cfg!(windows) {
+            ("C:\\repo", "C:\\other\\dir", "D:relative")
+        } else {
+            ("/repo", "/other/dir", "../outside")
+        };
+        let base = AbsoluteSystemPathBuf::new(base).unwrap();
+
+        assert_eq!(
+            AbsoluteSystemPathBuf::from_unknown_strict(&base, absolute).unwrap(),
+            AbsoluteSystemPathBuf::new(absolute).unwrap()
+        );
+        assert_eq!(
+            AbsoluteSystemPathBuf::from_unknown_strict(&base, "apps/web/../docs").unwrap(),
+            base.join_components(&["apps", "docs"])
+        );
+        assert_matches!(
+            AbsoluteSystemPathBuf::from_unknown_strict(&base, malformed),
+            Err(PathError::MalformedPath(_))
+        );
+    }
+
     #[cfg(windows)]
     #[test]
     fn test_absolute_system_path_buf_on_windows() {
@@ -300,4 +382,57 @@ mod tests {
             AbsoluteSystemPathBuf::new("C:\\some\\other").unwrap(),
         );
     }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_from_url_on_unix() {
+        let url = Url::parse("file:///Users/user/repo").unwrap();
+        let path = AbsoluteSystemPathBuf::from_url(&url).unwrap();
+        assert_eq!(
+            path,
+            AbsoluteSystemPathBuf::new("/Users/user/repo").unwrap()
+        );
+        assert_eq!(path.to_url().unwrap(), url);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_from_url_on_windows() {
+        let url = Url::parse("file:///C:/Users/user/repo").unwrap();
+        let path = AbsoluteSystemPathBuf::from_url(&url).unwrap();
+        assert_eq!(
+            path,
+            AbsoluteSystemPathBuf::new("C:\\Users\\user\\repo").unwrap()
+        );
+        assert_eq!(path.to_url().unwrap(), url);
+    }
+
+    #[test]
+    fn test_from_url_percent_encoded() {
+        let (url, expected) = if cfg!(windows) {
+            ("file:///C:/my%20repo/a%20b", "C:\\my repo\\a b")
+        } else {
+            ("file:///my%20repo/a%20b", "/my repo/a b")
+        };
+        let url = Url::parse(url).unwrap();
+        let path = AbsoluteSystemPathBuf::from_url(&url).unwrap();
+        assert_eq!(path, AbsoluteSystemPathBuf::new(expected).unwrap());
+        assert_eq!(path.to_url().unwrap(), url);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_to_url_device_path() {
+        let path = AbsoluteSystemPathBuf::new("\\\\.\\pipe\\turbo").unwrap();
+        assert_matches!(path.to_url(), Err(PathError::NotUrlPath(_)));
+    }
+
+    #[test]
+    fn test_from_url_not_file() {
+        let url = Url::parse("https://turbo.build/repo").unwrap();
+        assert_matches!(
+            AbsoluteSystemPathBuf::from_url(&url),
+            Err(PathError::NotFileUrl(_))
+        );
+    }
 }
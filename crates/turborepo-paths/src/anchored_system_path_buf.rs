@@ -8,7 +8,10 @@ use std::{
 use camino::{Utf8Component, Utf8Components, Utf8Path, Utf8PathBuf};
 use serde::{Deserialize, Serialize};
 
-use crate::{check_path, AbsoluteSystemPath, AnchoredSystemPath, PathError, PathValidation};
+use crate::{
+    check_path, AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPath, PathError,
+    PathValidation,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -202,6 +205,39 @@ impl AnchoredSystemPathBuf {
     pub fn join(&self, other: &AnchoredSystemPath) -> AnchoredSystemPathBuf {
         Self(self.0.join(other))
     }
+
+    /// Resolves this path against `base`, the inverse of
+    /// `AnchoredSystemPathBuf::new`. `.` components are dropped and `..`
+    /// components are applied lexically, but a path that would traverse above
+    /// `base` is rejected.
+    pub fn to_absolute(
+        &self,
+        base: &AbsoluteSystemPath,
+    ) -> Result<AbsoluteSystemPathBuf, PathError> {
+        let mut path = base.as_path().to_owned();
+        let mut depth = 0usize;
+        for component in self.0.components() {
+            match component {
+                Utf8Component::CurDir => {}
+                Utf8Component::ParentDir if depth > 0 => {
+                    path.pop();
+                    depth -= 1;
+                }
+                Utf8Component::Normal(segment) => {
+                    path.push(segment);
+                    depth += 1;
+                }
+                Utf8Component::ParentDir | Utf8Component::RootDir | Utf8Component::Prefix(_) => {
+                    return Err(PathError::NotParent(
+                        base.to_string(),
+                        base.as_path().join(&self.0).to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(AbsoluteSystemPathBuf(path))
+    }
 }
 
 impl From<AnchoredSystemPathBuf> for PathBuf {
@@ -224,11 +260,11 @@ impl AsRef<Path> for AnchoredSystemPathBuf {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::{assert_matches::assert_matches, path::Path};
 
     use test_case::test_case;
 
-    use crate::{AbsoluteSystemPathBuf, AnchoredSystemPathBuf};
+    use crate::{AbsoluteSystemPathBuf, AnchoredSystemPathBuf, PathError};
 
     #[test_case(&["a", "b", "c", "..", "c"], &["..", "c"] ; "re-entry self")]
     #[test_case(&["a", "b", "c", "d", "..", "d"], &["d"] ; "re-entry child")]
@@ -260,6 +296,44 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test_case(&["d", "e"] ; "child")]
+    #[test_case(&["d", ".", "e"] ; "current dir")]
+    #[test_case(&["d", "..", "e"] ; "re-entry")]
+    #[test_case(&["."] ; "self")]
+    #[test_case(&[] ; "empty")]
+    fn test_to_absolute_roundtrip(input: &[&str]) {
+        let base = test_base();
+        let anchored =
+            AnchoredSystemPathBuf::from_raw(input.join(std::path::MAIN_SEPARATOR_STR)).unwrap();
+
+        let absolute = anchored.to_absolute(&base).unwrap();
+
+        assert_eq!(absolute, base.resolve(&anchored).clean().unwrap());
+        let reanchored = base.anchor(&absolute).unwrap();
+        assert_eq!(reanchored.to_absolute(&base).unwrap(), absolute);
+    }
+
+    #[test_case(&[".."] ; "parent")]
+    #[test_case(&["d", "..", ".."] ; "re-entry parent")]
+    #[test_case(&["..", "c", "d"] ; "escape and return")]
+    fn test_to_absolute_rejects_escape(input: &[&str]) {
+        let base = test_base();
+        let anchored =
+            AnchoredSystemPathBuf::from_raw(input.join(std::path::MAIN_SEPARATOR_STR)).unwrap();
+
+        assert_matches!(anchored.to_absolute(&base), Err(PathError::NotParent(..)));
+    }
+
+    fn test_base() -> AbsoluteSystemPathBuf {
+        #[cfg(unix)]
+        let root_token = "/";
+        #[cfg(windows)]
+        let root_token = "C:\\";
+
+        AbsoluteSystemPathBuf::new([root_token, "a", "b", "c"].join(std::path::MAIN_SEPARATOR_STR))
+            .unwrap()
+    }
+
     #[test_case(Path::new("test.txt"), Ok("test.txt"), Ok("test.txt") ; "hello world")]
     #[test_case(Path::new("something/"), Ok("something"), Ok("something") ; "Unix directory")]
     #[test_case(Path::new("something\\"), Ok("something\\"), Err("Path is not safe for windows: something\\".to_string()) ; "Windows unsafe")]
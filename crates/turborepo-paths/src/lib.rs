@@ -83,6 +83,10 @@ pub enum PathError {
     IO(#[from] io::Error),
     #[error("{0} is not a prefix for {1}")]
     PrefixError(String, String),
+    #[error("URL is not a file URL: {0}")]
+    NotFileUrl(String),
+    #[error("Path cannot be converted to a file URL: {0}")]
+    NotUrlPath(String),
 }
 
 impl From<std::string::FromUtf8Error> for PathError {
@@ -603,6 +603,9 @@ impl PackageInfo {
 mod test {
     use std::assert_matches::assert_matches;
 
+    use test_case::test_case;
+    use turborepo_errors::Spanned;
+
     use super::*;
 
     struct MockDiscovery;
@@ -655,4 +658,48 @@ mod test {
         }));
         assert_matches!(builder.build().await, Err(Error::DuplicateWorkspace { .. }));
     }
+
+    const PNPM_LOCKFILE: &[u8] = include_bytes!("../../../turborepo-lockfiles/fixtures/pnpm8.yaml");
+    const NPM_LOCKFILE: &[u8] =
+        include_bytes!("../../../turborepo-lockfiles/fixtures/npm-lock.json");
+
+    #[test_case(&[("pnpm-lock.yaml", PNPM_LOCKFILE)], None, PackageManager::Pnpm ; "pnpm")]
+    #[test_case(&[("package-lock.json", NPM_LOCKFILE)], None, PackageManager::Npm ; "npm")]
+    #[test_case(
+        &[("pnpm-lock.yaml", PNPM_LOCKFILE), ("package-lock.json", NPM_LOCKFILE)],
+        Some("pnpm@8.15.0"),
+        PackageManager::Pnpm
+        ; "multiple lockfiles prefer package manager field"
+    )]
+    #[tokio::test]
+    async fn test_lockfile_matches_package_manager(
+        lockfiles: &[(&str, &[u8])],
+        package_manager: Option<&str>,
+        expected: PackageManager,
+    ) {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let root = AbsoluteSystemPathBuf::try_from(tmpdir.path()).unwrap();
+        for (name, contents) in lockfiles {
+            root.join_component(name)
+                .create_with_contents(contents)
+                .unwrap();
+        }
+        let root_package_json = PackageJson {
+            name: Some("root".into()),
+            package_manager: package_manager.map(|pm| Spanned::new(pm.to_string())),
+            ..Default::default()
+        };
+
+        let graph = PackageGraphBuilder::new(&root, root_package_json)
+            .with_allow_no_package_manager(true)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(graph.package_manager(), &expected);
+        assert!(
+            graph.lockfile().is_some(),
+            "expected {expected} lockfile to be parsed"
+        );
+    }
 }
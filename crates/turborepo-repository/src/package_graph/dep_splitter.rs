@@ -137,6 +137,22 @@ impl<'a> DependencyVersion<'a> {
         )
     }
 
+    /// The range specifier of a `workspace:` dependency, which package managers
+    /// use to rewrite the version when publishing. Aliases such as
+    /// `workspace:foo@^` return the trailing specifier and relative paths
+    /// return `None`.
+    #[allow(dead_code)]
+    fn workspace_range(&self) -> Option<&'a str> {
+        if self.protocol != Some("workspace") {
+            return None;
+        }
+        match self.version.rsplit_once('@') {
+            Some((_, range)) if !range.contains('/') => Some(range),
+            _ if self.version.contains('/') => None,
+            _ => Some(self.version),
+        }
+    }
+
     fn is_external(&self) -> bool {
         // The npm protocol for yarn by default still uses the workspace package if the
         // workspace version is in a compatible semver range. See https://github.com/yarnpkg/berry/discussions/4015
@@ -161,11 +177,11 @@ impl<'a> DependencyVersion<'a> {
                 // match and don't check the range for an exact match.
                 true
             }
-            Some("file") | Some("link") => {
+            Some("file") | Some("link") | Some("portal") => {
                 // Default to internal if we have the package but somehow cannot get the path
                 RelativeUnixPathBuf::new(self.version)
                     .map(|file_path| cwd.join_unix_path(file_path))
-                    .map_or(true, |dep_path| root.contains(&dep_path))
+                    .map_or(true, |dep_path| dep_path.is_inside(root).unwrap_or(true))
             }
             Some(_) if self.is_external() => {
                 // Other protocols are assumed to be external references ("github:", etc)
@@ -211,6 +227,20 @@ mod test {
     use super::*;
     use crate::package_json::PackageJson;
 
+    #[test_case("workspace:^", Some("^") ; "caret")]
+    #[test_case("workspace:~", Some("~") ; "tilde")]
+    #[test_case("workspace:*", Some("*") ; "star")]
+    #[test_case("workspace:1.2.3", Some("1.2.3") ; "explicit version")]
+    #[test_case("workspace:^1.2.3", Some("^1.2.3") ; "explicit range")]
+    #[test_case("workspace:@scope/foo@^", Some("^") ; "alias caret")]
+    #[test_case("workspace:@scope/foo@1.2.3", Some("1.2.3") ; "alias version")]
+    #[test_case("workspace:../@scope/foo", None ; "relative path")]
+    #[test_case("^1.2.3", None ; "no protocol")]
+    #[test_case("npm:^1.2.3", None ; "other protocol")]
+    fn test_workspace_range(version: &str, expected: Option<&str>) {
+        assert_eq!(DependencyVersion::new(version).workspace_range(), expected);
+    }
+
     #[test_case("1.2.3", None, "1.2.3", Some("@scope/foo"), true ; "handles exact match")]
     #[test_case("1.2.3", None, "^1.0.0", Some("@scope/foo"), true ; "handles semver range satisfied")]
     #[test_case("2.3.4", None, "^1.0.0", None, true ; "handles semver range not satisfied")]
@@ -230,6 +260,8 @@ mod test {
     #[test_case("1.2.3", None, "file:../../../otherproject", None, true ; "handles file:.. outside repo")]
     #[test_case("1.2.3", None, "link:../libB", Some("@scope/foo"), true ; "handles link:.. inside repo")]
     #[test_case("1.2.3", None, "link:../../../otherproject", None, true ; "handles link:.. outside repo")]
+    #[test_case("1.2.3", None, "portal:../libB", Some("@scope/foo"), true ; "handles portal:.. inside repo")]
+    #[test_case("1.2.3", None, "portal:../../../otherproject", None, true ; "handles portal:.. outside repo")]
     #[test_case("0.0.0-development", None, "*", Some("@scope/foo"), true ; "handles development versions")]
     #[test_case("1.2.3", Some("foo"), "workspace:@scope/foo@*", Some("@scope/foo"), true ; "handles pnpm alias star")]
     #[test_case("1.2.3", Some("foo"), "workspace:@scope/foo@~", Some("@scope/foo"), true ; "handles pnpm alias tilde")]
@@ -314,6 +346,37 @@ mod test {
         );
     }
 
+    // Creating directory symlinks on Windows requires elevated privileges
+    #[cfg(unix)]
+    #[test_case("link:../escaping", false ; "link to symlink outside repo")]
+    #[test_case("file:../escaping", false ; "file to symlink outside repo")]
+    #[test_case("link:../internal", true ; "link to symlink inside repo")]
+    #[test_case("link:../libB", true ; "link inside repo")]
+    fn test_matches_workspace_package_symlink(range: &str, expected: bool) {
+        let tmp = tempfile::tempdir().unwrap();
+        let tmp = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let root = tmp.join_component("repo");
+        let outside = tmp.join_component("otherproject");
+        let packages = root.join_component("packages");
+        let pkg_dir = packages.join_component("libA");
+        pkg_dir.create_dir_all().unwrap();
+        packages.join_component("libB").create_dir_all().unwrap();
+        outside.create_dir_all().unwrap();
+        packages
+            .join_component("escaping")
+            .symlink_to_dir(outside.as_str())
+            .unwrap();
+        packages
+            .join_component("internal")
+            .symlink_to_dir(packages.join_component("libB").as_str())
+            .unwrap();
+
+        assert_eq!(
+            DependencyVersion::new(range).matches_workspace_package("1.2.3", &pkg_dir, &root),
+            expected
+        );
+    }
+
     #[test_case("1.2.3", None ; "non-workspace")]
     #[test_case("workspace:1.2.3", None ; "workspace version")]
     #[test_case("workspace:*", None ; "workspace any")]
@@ -5,7 +5,7 @@ use std::{
 
 use itertools::Itertools;
 use serde::Serialize;
-use tracing::debug;
+use tracing::{debug, warn};
 use turbopath::{
     AbsoluteSystemPath, AbsoluteSystemPathBuf, AnchoredSystemPath, AnchoredSystemPathBuf,
 };
@@ -141,11 +141,46 @@ impl PackageGraph {
                 return Err(Error::PackageJsonMissingName(package_json_path));
             }
         }
-        graph::validate_graph(&self.graph).map_err(Error::InvalidPackageGraph)?;
+        // Only cycles through production dependencies are errors. Cycles that go
+        // through a `devDependencies` entry are surfaced as a warning.
+        let production_graph = self.graph.filter_map(
+            |_, node| Some(node.clone()),
+            |edge, _| {
+                let (dependent, dependency) = self.graph.edge_endpoints(edge)?;
+                (!self.is_dev_dependency(&self.graph[dependent], &self.graph[dependency]))
+                    .then_some(())
+            },
+        );
+        graph::validate_graph(&production_graph).map_err(Error::InvalidPackageGraph)?;
+        if let Err(err) = graph::validate_graph(&self.graph) {
+            warn!("{err}\nThese packages only depend on each other through devDependencies");
+        }
 
         Ok(())
     }
 
+    // Returns true if the only reason `dependent` depends on `dependency` is a
+    // `devDependencies` entry
+    fn is_dev_dependency(&self, dependent: &PackageNode, dependency: &PackageNode) -> bool {
+        let (PackageNode::Workspace(dependent), PackageNode::Workspace(dependency)) =
+            (dependent, dependency)
+        else {
+            return false;
+        };
+        let Some(package_json) = self.package_json(dependent) else {
+            return false;
+        };
+        let dependency = dependency.as_ref();
+        let in_deps = |deps: &Option<BTreeMap<String, String>>| {
+            deps.as_ref()
+                .map_or(false, |deps| deps.contains_key(dependency))
+        };
+
+        in_deps(&package_json.dev_dependencies)
+            && !in_deps(&package_json.dependencies)
+            && !in_deps(&package_json.optional_dependencies)
+    }
+
     pub fn remove_package_dependencies(&mut self) {
         let root_index = self
             .node_lookup
@@ -295,6 +330,97 @@ impl PackageGraph {
         dependents
     }
 
+    /// Returns the names of all internal packages that `package` depends on,
+    /// either directly or transitively. Circular dependencies are tolerated
+    /// and `package` itself is never part of the result.
+    ///
+    /// Example:
+    ///
+    /// a -> b -> c
+    ///
+    /// transitive_dependencies(a) = {b, c}
+    pub fn transitive_dependencies(&self, package: &str) -> HashSet<String> {
+        self.transitive_package_names(package, petgraph::Direction::Outgoing)
+    }
+
+    /// Returns the names of all internal packages that depend on `package`,
+    /// either directly or transitively. Circular dependencies are tolerated
+    /// and `package` itself is never part of the result.
+    ///
+    /// Example:
+    ///
+    /// a -> b -> c
+    ///
+    /// transitive_dependents(c) = {a, b}
+    pub fn transitive_dependents(&self, package: &str) -> HashSet<String> {
+        self.transitive_package_names(package, petgraph::Direction::Incoming)
+    }
+
+    /// Returns the names of packages affected by the changes between two git
+    /// refs: packages containing a changed file along with all of their
+    /// dependents. Files outside of any package mark the root package as
+    /// changed.
+    pub fn changed_packages(
+        &self,
+        repo_root: &AbsoluteSystemPath,
+        from: &str,
+        to: &str,
+    ) -> Result<HashSet<String>, GitChangesError> {
+        let output = std::process::Command::new("git")
+            .args(["diff", "--name-only", "-z", "--relative", from, to, "--"])
+            .current_dir(repo_root)
+            .output()?;
+        if !output.status.success() {
+            return Err(GitChangesError::Git(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        let mut changed = HashSet::new();
+        // Paths are NUL separated so that git doesn't quote unusual file names
+        for file in String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|file| !file.is_empty())
+        {
+            let file = AnchoredSystemPathBuf::from_raw(file)?;
+            // Nested packages are attributed to the innermost package
+            let package = self
+                .packages()
+                .filter(|(name, _)| **name != PackageName::Root)
+                .filter(|(_, info)| file.as_path().starts_with(info.package_path().as_path()))
+                .max_by_key(|(_, info)| info.package_path().as_str().len())
+                .map_or(PackageName::Root, |(name, _)| name.clone());
+            changed.insert(package.to_string());
+        }
+
+        let dependents = changed
+            .iter()
+            .flat_map(|package| self.transitive_dependents(package))
+            .collect::<Vec<_>>();
+        changed.extend(dependents);
+
+        Ok(changed)
+    }
+
+    fn transitive_package_names(
+        &self,
+        package: &str,
+        direction: petgraph::Direction,
+    ) -> HashSet<String> {
+        let node = PackageNode::Workspace(PackageName::from(package));
+        let Some(index) = self.node_lookup.get(&node) else {
+            return HashSet::new();
+        };
+        turborepo_graph_utils::transitive_closure(&self.graph, Some(*index), direction)
+            .into_iter()
+            .filter(|other| **other != node)
+            .filter_map(|other| match other {
+                PackageNode::Workspace(name) => Some(name.to_string()),
+                PackageNode::Root => None,
+            })
+            .collect()
+    }
+
     pub fn root_internal_package_dependencies(&self) -> HashSet<WorkspacePackage> {
         let dependencies = self.root_internal_dependencies();
         dependencies
@@ -504,6 +630,16 @@ pub enum ChangedPackagesError {
     Lockfile(#[from] turborepo_lockfiles::Error),
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum GitChangesError {
+    #[error("failed to run git: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("git diff failed: {0}")]
+    Git(String),
+    #[error(transparent)]
+    Path(#[from] turbopath::PathError),
+}
+
 impl fmt::Display for PackageName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -673,6 +809,219 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_workspace_dependency_edges() {
+        let root =
+            AbsoluteSystemPathBuf::new(if cfg!(windows) { r"C:\repo" } else { "/repo" }).unwrap();
+        let pkg_graph = PackageGraph::builder(
+            &root,
+            PackageJson::from_value(json!({ "name": "root" })).unwrap(),
+        )
+        .with_package_discovery(MockDiscovery)
+        .with_package_jsons(Some({
+            let mut map = HashMap::new();
+            map.insert(
+                root.join_components(&["packages", "a", "package.json"]),
+                PackageJson::from_value(json!({
+                    "name": "a",
+                    "dependencies": {
+                        "react": "^18.2.0"
+                    },
+                    "devDependencies": {
+                        "b": "workspace:*"
+                    }
+                }))
+                .unwrap(),
+            );
+            map.insert(
+                root.join_components(&["packages", "b", "package.json"]),
+                PackageJson::from_value(json!({
+                    "name": "b",
+                    "version": "1.0.0"
+                }))
+                .unwrap(),
+            );
+            map
+        }))
+        .build()
+        .await
+        .unwrap();
+
+        let a = PackageNode::Workspace("a".into());
+        let b = PackageNode::Workspace("b".into());
+        let a_deps = pkg_graph.immediate_dependencies(&a).unwrap();
+        assert_eq!(a_deps, [&b].into_iter().collect::<HashSet<_>>());
+        assert!(pkg_graph.immediate_ancestors(&b).unwrap().contains(&a));
+
+        // External dependencies don't produce edges, they're tracked separately
+        assert!(!pkg_graph
+            .node_lookup
+            .contains_key(&PackageNode::Workspace("react".into())));
+        let a_external = pkg_graph
+            .package_info(&PackageName::from("a"))
+            .unwrap()
+            .unresolved_external_dependencies
+            .as_ref()
+            .unwrap();
+        assert_eq!(a_external.get("react").map(|v| v.as_str()), Some("^18.2.0"));
+        assert!(!a_external.contains_key("b"));
+    }
+
+    #[tokio::test]
+    async fn test_transitive_dependencies_diamond() {
+        let root =
+            AbsoluteSystemPathBuf::new(if cfg!(windows) { r"C:\repo" } else { "/repo" }).unwrap();
+        let pkg_graph = PackageGraph::builder(
+            &root,
+            PackageJson::from_value(json!({ "name": "root" })).unwrap(),
+        )
+        .with_package_discovery(MockDiscovery)
+        .with_package_jsons(Some({
+            let mut map = HashMap::new();
+            for (name, deps) in [
+                ("a", json!({ "b": "workspace:*", "c": "workspace:*" })),
+                ("b", json!({ "d": "workspace:*" })),
+                ("c", json!({ "d": "workspace:*", "react": "^18.2.0" })),
+                ("d", json!({})),
+            ] {
+                map.insert(
+                    root.join_components(&["packages", name, "package.json"]),
+                    PackageJson::from_value(json!({ "name": name, "dependencies": deps })).unwrap(),
+                );
+            }
+            map
+        }))
+        .build()
+        .await
+        .unwrap();
+
+        let set = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<HashSet<_>>()
+        };
+        assert_eq!(
+            pkg_graph.transitive_dependencies("a"),
+            set(&["b", "c", "d"])
+        );
+        assert_eq!(pkg_graph.transitive_dependencies("b"), set(&["d"]));
+        assert_eq!(pkg_graph.transitive_dependencies("d"), set(&[]));
+        assert_eq!(pkg_graph.transitive_dependents("d"), set(&["a", "b", "c"]));
+        assert_eq!(pkg_graph.transitive_dependents("c"), set(&["a"]));
+        assert_eq!(pkg_graph.transitive_dependents("a"), set(&[]));
+        assert_eq!(pkg_graph.transitive_dependencies("missing"), set(&[]));
+    }
+
+    #[tokio::test]
+    async fn test_transitive_dependencies_with_cycle() {
+        let root =
+            AbsoluteSystemPathBuf::new(if cfg!(windows) { r"C:\repo" } else { "/repo" }).unwrap();
+        let pkg_graph = PackageGraph::builder(
+            &root,
+            PackageJson::from_value(json!({ "name": "root" })).unwrap(),
+        )
+        .with_package_discovery(MockDiscovery)
+        .with_package_jsons(Some({
+            let mut map = HashMap::new();
+            map.insert(
+                root.join_components(&["packages", "a", "package.json"]),
+                PackageJson::from_value(json!({
+                    "name": "a",
+                    "devDependencies": { "b": "workspace:*" }
+                }))
+                .unwrap(),
+            );
+            map.insert(
+                root.join_components(&["packages", "b", "package.json"]),
+                PackageJson::from_value(json!({
+                    "name": "b",
+                    "devDependencies": { "a": "workspace:*" }
+                }))
+                .unwrap(),
+            );
+            map
+        }))
+        .build()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            pkg_graph.transitive_dependencies("a"),
+            HashSet::from(["b".to_string()])
+        );
+        assert_eq!(
+            pkg_graph.transitive_dependents("a"),
+            HashSet::from(["b".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changed_packages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = AbsoluteSystemPathBuf::try_from(tmp.path()).unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed: {output:?}");
+        };
+
+        let mut package_jsons = HashMap::new();
+        for (name, deps) in [
+            ("a", json!({ "b": "workspace:*" })),
+            ("b", json!({})),
+            ("c", json!({})),
+        ] {
+            let package_json_path = root.join_components(&["packages", name, "package.json"]);
+            let package_json = json!({ "name": name, "dependencies": deps });
+            package_json_path.ensure_dir().unwrap();
+            package_json_path
+                .create_with_contents(package_json.to_string())
+                .unwrap();
+            package_jsons.insert(
+                package_json_path,
+                PackageJson::from_value(package_json).unwrap(),
+            );
+        }
+        git(&["init", "--quiet"]);
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "initial"]);
+
+        // Without -z git would quote this name and it wouldn't match package b
+        root.join_components(&["packages", "b", "índex file.js"])
+            .create_with_contents("export default 1;")
+            .unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "change b"]);
+
+        let pkg_graph = PackageGraph::builder(
+            &root,
+            PackageJson::from_value(json!({ "name": "root" })).unwrap(),
+        )
+        .with_package_discovery(MockDiscovery)
+        .with_package_jsons(Some(package_jsons))
+        .build()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            pkg_graph.changed_packages(&root, "HEAD~1", "HEAD").unwrap(),
+            HashSet::from(["a".to_string(), "b".to_string()])
+        );
+        assert!(pkg_graph
+            .changed_packages(&root, "HEAD", "HEAD")
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            pkg_graph.changed_packages(&root, "missing-ref", "HEAD"),
+            Err(GitChangesError::Git(_))
+        ));
+    }
+
     #[derive(Debug)]
     struct MockLockfile {}
     impl turborepo_lockfiles::Lockfile for MockLockfile {
@@ -858,6 +1207,87 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_two_package_cycle() {
+        let root =
+            AbsoluteSystemPathBuf::new(if cfg!(windows) { r"C:\repo" } else { "/repo" }).unwrap();
+        let pkg_graph = PackageGraph::builder(
+            &root,
+            PackageJson::from_value(json!({ "name": "root" })).unwrap(),
+        )
+        .with_package_discovery(MockDiscovery)
+        .with_package_jsons(Some({
+            let mut map = HashMap::new();
+            map.insert(
+                root.join_component("package_a"),
+                PackageJson::from_value(json!({
+                    "name": "foo",
+                    "dependencies": { "bar": "*" }
+                }))
+                .unwrap(),
+            );
+            map.insert(
+                root.join_component("package_b"),
+                PackageJson::from_value(json!({
+                    "name": "bar",
+                    "dependencies": { "foo": "*" }
+                }))
+                .unwrap(),
+            );
+            map
+        }))
+        .with_lockfile(Some(Box::new(MockLockfile {})))
+        .build()
+        .await
+        .unwrap();
+
+        let err = pkg_graph.validate().unwrap_err();
+        assert_matches!(
+            err,
+            builder::Error::InvalidPackageGraph(graph::Error::CyclicDependencies(_))
+        );
+        let message = err.to_string();
+        assert!(message.contains("foo"), "{message}");
+        assert!(message.contains("bar"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn test_dev_dependency_cycle_is_allowed() {
+        let root =
+            AbsoluteSystemPathBuf::new(if cfg!(windows) { r"C:\repo" } else { "/repo" }).unwrap();
+        let pkg_graph = PackageGraph::builder(
+            &root,
+            PackageJson::from_value(json!({ "name": "root" })).unwrap(),
+        )
+        .with_package_discovery(MockDiscovery)
+        .with_package_jsons(Some({
+            let mut map = HashMap::new();
+            map.insert(
+                root.join_component("package_a"),
+                PackageJson::from_value(json!({
+                    "name": "foo",
+                    "dependencies": { "bar": "*" }
+                }))
+                .unwrap(),
+            );
+            map.insert(
+                root.join_component("package_b"),
+                PackageJson::from_value(json!({
+                    "name": "bar",
+                    "devDependencies": { "foo": "*" }
+                }))
+                .unwrap(),
+            );
+            map
+        }))
+        .with_lockfile(Some(Box::new(MockLockfile {})))
+        .build()
+        .await
+        .unwrap();
+
+        assert!(pkg_graph.validate().is_ok());
+    }
+
     #[tokio::test]
     async fn test_self_dependency() {
         let root =
@@ -9,21 +9,46 @@ use wax::{any, Glob, Program};
 
 use crate::{package_deps::GitHashes, Error};
 
+// Files are streamed through the hasher in chunks of this size so large inputs
+// aren't read into memory all at once
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
 fn git_like_hash_file(path: &AbsoluteSystemPath) -> Result<String, Error> {
     let mut hasher = Sha1::new();
-    let mut f = path.open()?;
-    let mut buffer = Vec::new();
-    // Note that read_to_end reads the target if f is a symlink. Currently, this can
-    // happen when we are hashing a specific set of files, which in turn only
+    // Note that opening f follows symlinks, so we hash the target. Currently, this
+    // can happen when we are hashing a specific set of files, which in turn only
     // happens for handling dotEnv files. It is likely that in the future we
     // will want to ensure that the target is better accounted for in the set of
     // inputs to the task. Manual hashing, as well as global deps and other
     // places that support globs all ignore symlinks.
-    let size = f.read_to_end(&mut buffer)?;
+    let mut f = path.open()?;
+    // The size goes into the header, so it has to be known before any content is
+    // hashed
+    let size = f.metadata()?.len();
     hasher.update("blob ".as_bytes());
     hasher.update(size.to_string().as_bytes());
     hasher.update([b'\0']);
-    hasher.update(buffer.as_slice());
+
+    let mut buffer = vec![0; HASH_CHUNK_SIZE];
+    let mut hashed = 0;
+    loop {
+        let read = match f.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        hasher.update(&buffer[..read]);
+        hashed += read as u64;
+    }
+    if hashed != size {
+        return Err(std::io::Error::new(
+            ErrorKind::Other,
+            format!("{path} changed while it was being hashed"),
+        )
+        .into());
+    }
+
     let result = hasher.finalize();
     Ok(result.encode_hex::<String>())
 }
@@ -251,6 +276,41 @@ mod tests {
         }
     }
 
+    // The previous implementation, which read the whole file into memory
+    fn buffered_git_like_hash_file(path: &AbsoluteSystemPath) -> String {
+        let buffer = path.read().unwrap();
+        let mut hasher = Sha1::new();
+        hasher.update("blob ".as_bytes());
+        hasher.update(buffer.len().to_string().as_bytes());
+        hasher.update([b'\0']);
+        hasher.update(buffer.as_slice());
+        hasher.finalize().encode_hex::<String>()
+    }
+
+    #[test_case(0 ; "empty")]
+    #[test_case(1 ; "single byte")]
+    #[test_case(HASH_CHUNK_SIZE ; "exactly one chunk")]
+    #[test_case(HASH_CHUNK_SIZE + 1 ; "just over one chunk")]
+    #[test_case(3 * HASH_CHUNK_SIZE + 17 ; "several chunks")]
+    #[test_case(16 * 1024 * 1024 + 5 ; "large file")]
+    fn test_git_like_hash_file_streams(size: usize) {
+        let (_tmp, turbo_root) = tmp_dir();
+        let file = turbo_root.join_component("large-file.bin");
+        let contents = (0..size)
+            .map(|i| (i.wrapping_mul(31) % 251) as u8)
+            .collect::<Vec<_>>();
+        file.create_with_contents(&contents).unwrap();
+
+        let hash = git_like_hash_file(&file).unwrap();
+        assert_eq!(hash, buffered_git_like_hash_file(&file));
+        assert_eq!(
+            hash,
+            git2::Oid::hash_file(git2::ObjectType::Blob, &file)
+                .unwrap()
+                .to_string()
+        );
+    }
+
     #[test]
     fn test_hash_symlink() {
         let (_tmp, turbo_root) = tmp_dir();
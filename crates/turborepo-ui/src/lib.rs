@@ -156,15 +156,28 @@ impl ColorConfig {
     /// Infer the color choice from environment variables and checking if stdout
     /// is a tty
     pub fn infer() -> Self {
-        let env_setting =
-            std::env::var("FORCE_COLOR")
-                .ok()
-                .and_then(|force_color| match force_color.as_str() {
-                    "false" | "0" => Some(true),
-                    "true" | "1" | "2" | "3" => Some(false),
-                    _ => None,
-                });
-        let should_strip_ansi = env_setting.unwrap_or_else(|| !atty::is(atty::Stream::Stdout));
+        let force_color = std::env::var("FORCE_COLOR").ok();
+        let no_color = std::env::var("NO_COLOR").ok();
+        Self::from_env(
+            force_color.as_deref(),
+            no_color.as_deref(),
+            atty::is(atty::Stream::Stdout),
+        )
+    }
+
+    /// `FORCE_COLOR` takes precedence over `NO_COLOR`, with the tty check only
+    /// used if neither is set.
+    fn from_env(force_color: Option<&str>, no_color: Option<&str>, is_tty: bool) -> Self {
+        let force_color_setting = force_color.and_then(|force_color| match force_color {
+            "false" | "0" => Some(true),
+            "true" | "1" | "2" | "3" => Some(false),
+            _ => None,
+        });
+        // Any non-empty value disables color, see https://no-color.org
+        let no_color_setting = no_color
+            .filter(|no_color| !no_color.is_empty())
+            .map(|_| true);
+        let should_strip_ansi = force_color_setting.or(no_color_setting).unwrap_or(!is_tty);
         Self { should_strip_ansi }
     }
 
@@ -234,8 +247,32 @@ pub use dialoguer::theme::ColorfulTheme as DialoguerTheme;
 
 #[cfg(test)]
 mod test {
+    use test_case::test_case;
+
     use super::*;
 
+    #[test_case(None, None, true, false ; "tty")]
+    #[test_case(None, None, false, true ; "not a tty")]
+    #[test_case(None, Some("1"), true, true ; "no color")]
+    #[test_case(None, Some(""), true, false ; "empty no color")]
+    #[test_case(Some("1"), None, false, false ; "force color")]
+    #[test_case(Some("0"), None, true, true ; "force color disabled")]
+    #[test_case(Some("1"), Some("1"), false, false ; "force color beats no color")]
+    #[test_case(Some("false"), Some("1"), true, true ; "force color disabled with no color")]
+    #[test_case(Some("junk"), Some("1"), true, true ; "invalid force color falls back to no color")]
+    #[test_case(Some("junk"), None, true, false ; "invalid force color falls back to tty")]
+    fn test_color_config_from_env(
+        force_color: Option<&str>,
+        no_color: Option<&str>,
+        is_tty: bool,
+        should_strip_ansi: bool,
+    ) {
+        assert_eq!(
+            ColorConfig::from_env(force_color, no_color, is_tty).should_strip_ansi,
+            should_strip_ansi
+        );
+    }
+
     #[test]
     fn test_color_config_strips_ansi() {
         let color_config = ColorConfig::new(true);
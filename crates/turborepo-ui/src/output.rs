@@ -26,6 +26,8 @@ pub struct OutputClient<W> {
     writers: Arc<Mutex<SinkWriters<W>>>,
     primary: Marginals,
     error: Marginals,
+    // If set, bytes written to stderr are sent to the sink's out writer
+    merge_streams: bool,
 }
 
 #[derive(Default)]
@@ -92,6 +94,7 @@ impl<W: Write> OutputSink<W> {
             writers,
             primary: Default::default(),
             error: Default::default(),
+            merge_streams: false,
         }
     }
 }
@@ -113,6 +116,12 @@ impl<W: Write> OutputClient<W> {
         self.error = Marginals { header, footer };
     }
 
+    /// Send everything written to this client's stderr to the sink's out
+    /// writer instead of its err writer.
+    pub fn with_merged_streams(&mut self) {
+        self.merge_streams = true;
+    }
+
     /// A writer that will write to the underlying sink's out writer according
     /// to this client's behavior.
     pub fn stdout(&self) -> OutputWriter<W> {
@@ -128,7 +137,10 @@ impl<W: Write> OutputClient<W> {
     pub fn stderr(&self) -> OutputWriter<W> {
         OutputWriter {
             logger: self,
-            destination: Destination::Stderr,
+            destination: match self.merge_streams {
+                true => Destination::Stdout,
+                false => Destination::Stderr,
+            },
             buffer: Vec::new(),
         }
     }
@@ -142,6 +154,7 @@ impl<W: Write> OutputClient<W> {
             writers,
             primary,
             error,
+            ..
         } = self;
         let buffers = buffer.map(|cell| cell.into_inner().expect("lock poisoned"));
         let header = use_error
@@ -389,6 +402,29 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_merged_streams() -> io::Result<()> {
+        let sink = OutputSink::new(Vec::new(), Vec::new());
+        let merged_logger = {
+            let mut logger = sink.logger(OutputClientBehavior::Grouped);
+            logger.with_merged_streams();
+            logger
+        };
+        let logger = sink.logger(OutputClientBehavior::Passthrough);
+
+        writeln!(&mut merged_logger.stdout(), "output for 1")?;
+        writeln!(&mut merged_logger.stderr(), "warning for 1")?;
+        writeln!(&mut logger.stderr(), "warning for 2")?;
+        merged_logger.finish(false)?;
+        logger.finish(false)?;
+
+        let SinkWriters { out, err } = Arc::into_inner(sink.writers).unwrap().into_inner().unwrap();
+        assert_eq!(out, b"output for 1\nwarning for 1\n");
+        assert_eq!(err, b"warning for 2\n");
+
+        Ok(())
+    }
+
     #[test]
     fn test_marginals() -> io::Result<()> {
         let sink = OutputSink::new(Vec::new(), Vec::new());
@@ -21,6 +21,7 @@ pub struct PrefixedUI<W> {
     out: W,
     err: W,
     default_prefix: StyledObject<String>,
+    timestamps: bool,
 }
 
 impl<W: Write> PrefixedUI<W> {
@@ -33,6 +34,7 @@ impl<W: Write> PrefixedUI<W> {
             warn_prefix: None,
             error_prefix: None,
             default_prefix: Style::new().apply_to(String::new()),
+            timestamps: false,
         }
     }
 
@@ -51,6 +53,12 @@ impl<W: Write> PrefixedUI<W> {
         self
     }
 
+    /// Start every line with an ISO-8601 timestamp ahead of the prefix
+    pub fn with_timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
     pub fn output(&mut self, message: impl Display) {
         self.write_line(message, Command::Output)
     }
@@ -79,7 +87,11 @@ impl<W: Write> PrefixedUI<W> {
         // There's no reason to propagate this error
         // because we don't want our entire program to crash
         // due to a log failure.
-        if let Err(err) = writeln!(writer, "{}{}", prefix, message) {
+        let timestamp = match self.timestamps {
+            true => format!("{} ", timestamp()),
+            false => String::new(),
+        };
+        if let Err(err) = writeln!(writer, "{}{}{}", timestamp, prefix, message) {
             error!("cannot write to logs: {:?}", err);
         }
     }
@@ -87,16 +99,25 @@ impl<W: Write> PrefixedUI<W> {
     /// Construct a PrefixedWriter which will behave the same as `output`, but
     /// without the requirement that messages be valid UTF-8
     pub fn output_prefixed_writer(&mut self) -> PrefixedWriter<&mut W> {
-        PrefixedWriter::new(
+        let inner = PrefixedWriterInner::new(
             self.color_config,
             self.output_prefix
                 .clone()
                 .unwrap_or_else(|| Style::new().apply_to(String::new())),
             &mut self.out,
         )
+        .with_timestamps(self.timestamps);
+        PrefixedWriter {
+            inner: LineWriter::new(inner),
+        }
     }
 }
 
+/// The current time in UTC as an ISO-8601 string with millisecond precision
+fn timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
 //
 #[derive(Debug, Clone, Copy)]
 enum Command {
@@ -133,12 +154,22 @@ impl<W: Write> Write for PrefixedWriter<W> {
 struct PrefixedWriterInner<W> {
     prefix: String,
     writer: W,
+    timestamps: bool,
 }
 
 impl<W: Write> PrefixedWriterInner<W> {
     pub fn new(color_config: ColorConfig, prefix: StyledObject<impl Display>, writer: W) -> Self {
         let prefix = color_config.apply(prefix).to_string();
-        Self { prefix, writer }
+        Self {
+            prefix,
+            writer,
+            timestamps: false,
+        }
+    }
+
+    fn with_timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
     }
 }
 
@@ -153,6 +184,9 @@ impl<W: Write> Write for PrefixedWriterInner<W> {
             //   the line
             // or if the last chunk is just a newline we can skip rewriting the prefix
             if is_first || chunk != b"\n" {
+                if self.timestamps {
+                    write!(self.writer, "{} ", timestamp())?;
+                }
                 self.writer.write_all(self.prefix.as_bytes())?;
             }
             self.writer.write_all(chunk)?;
@@ -222,6 +256,40 @@ mod test {
         assert_eq!(String::from_utf8(buffer).unwrap(), expected);
     }
 
+    #[test]
+    fn test_prefixed_writer_timestamps() {
+        let mut buffer = Vec::new();
+        let mut writer = PrefixedWriterInner::new(
+            ColorConfig::new(true),
+            Style::new().apply_to("foo#build: "),
+            &mut buffer,
+        )
+        .with_timestamps(true);
+        writer.write_all(b"cool!").unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        let (timestamp, rest) = output.split_once(' ').unwrap();
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(timestamp).is_ok(),
+            "expected an ISO-8601 timestamp, got {timestamp}"
+        );
+        assert_eq!(rest, "foo#build: cool!");
+    }
+
+    #[test]
+    fn test_prefix_ui_timestamps() {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        let mut prefixed_ui =
+            prefixed_ui(&mut out, &mut err, ColorConfig::new(true)).with_timestamps(true);
+        prefixed_ui.output("all good");
+
+        let output = String::from_utf8(out).unwrap();
+        let (timestamp, rest) = output.split_once(' ').unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok());
+        assert_eq!(rest, "output all good\n");
+    }
+
     #[test_case("\ra whole message \n", "turbo > \rturbo > a whole message \n" ; "basic prefix cr")]
     #[test_case("no return", "turbo > no return" ; "no return")]
     #[test_case("foo\rbar\rbaz", "turbo > foo\rturbo > bar\rturbo > baz" ; "multiple crs")]
@@ -91,6 +91,9 @@ pub enum OutputLogs {
     NewOnly,
     // Output is only persisted if the task failed
     ErrorsOnly,
+    // Output is only persisted if the task failed, otherwise only the status
+    // line is persisted
+    ErrorsOnlyWithSummary,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -95,6 +95,13 @@ impl<W> TerminalOutput<W> {
                     LogBehavior::Nothing
                 }
             }
+            OutputLogs::ErrorsOnlyWithSummary => {
+                if matches!(self.task_result, Some(TaskResult::Failure)) {
+                    LogBehavior::Full
+                } else {
+                    LogBehavior::Status
+                }
+            }
         }
     }
 
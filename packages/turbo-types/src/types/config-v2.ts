@@ -3,9 +3,11 @@ export type OutputLogs =
   | "hash-only"
   | "new-only"
   | "errors-only"
+  | "errors-only-with-summary"
   | "none";
 export type EnvMode = "strict" | "loose";
-export type UI = "tui" | "stream";
+export type CacheCompression = "zstd" | "gzip" | "none";
+export type UI = "tui" | "stream" | "json";
 
 /**
  * This is a relative Unix-style path (e.g. `./src/index.ts` or `src/index.ts`).  Absolute paths (e.g. `/tmp/foo`) are not valid.
@@ -86,6 +88,14 @@ export interface RootSchema extends BaseSchema {
    */
   globalEnv?: Array<EnvWildcard>;
 
+  /**
+   * A path, relative to the root of the repository, to a file listing
+   * additional environment variables for `globalEnv`, one per line.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#globalenvfile
+   */
+  globalEnvFile?: string;
+
   /**
    * An allowlist of environment variables that should be made to all tasks, but
    * should not contribute to the task's cache key, e.g. `AWS_SECRET_KEY`.
@@ -135,6 +145,16 @@ export interface RootSchema extends BaseSchema {
    */
   cacheDir?: RelativeUnixPath;
 
+  /**
+   * Select the codec used to compress cache artifacts. Artifacts are read
+   * using the codec detected from their header.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#cachecompression
+   *
+   * @defaultValue `"zstd"`
+   */
+  cacheCompression?: CacheCompression;
+
   /**
    * Turborepo runs a background process to pre-calculate some expensive operations. This standalone process (daemon) is a performance optimization, and not required for proper functioning of `turbo`.
    *
@@ -155,6 +175,24 @@ export interface RootSchema extends BaseSchema {
    * @defaultValue `"strict"`
    */
   envMode?: EnvMode;
+
+  /**
+   * Warn about environment variables provided by your CI platform that are missing from a task's environment.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#platformenvvalidation
+   *
+   * @defaultValue `true`
+   */
+  platformEnvValidation?: boolean;
+
+  /**
+   * The Git ref that `--affected` compares against to find changed packages.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#scmbase
+   *
+   * @defaultValue `"main"`
+   */
+  scmBase?: string;
 }
 
 export interface Pipeline {
@@ -201,6 +239,17 @@ export interface Pipeline {
    */
   passThroughEnv?: null | Array<EnvWildcard>;
 
+  /**
+   * Environment variables with fixed values to set for this task, e.g.
+   * `{ "NODE_OPTIONS": "--max-old-space-size=4096" }`. These values
+   * contribute to the task's cache key.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#envvalues
+   *
+   * @defaultValue `{}`
+   */
+  envValues?: Record<string, string>;
+
   /**
    * The set of glob patterns indicating a task's cacheable filesystem outputs.
    *
@@ -253,6 +302,9 @@ export interface Pipeline {
    *
    * "errors-only": Only show output from task failures
    *
+   * "errors-only-with-summary": Only show output from task failures and a
+   * status line for every other task
+   *
    * "none": Hides all task output
    *
    * Documentation: https://turbo.build/repo/docs/reference/run#--output-logs-option
@@ -282,8 +334,68 @@ export interface Pipeline {
    * @defaultValue `false`
    */
   interactive?: boolean;
+
+  /**
+   * A prompt to show before the task runs. If the prompt is declined, only the
+   * task is skipped, tasks that depend on it still run.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#confirm
+   *
+   * @defaultValue `null`
+   */
+  confirm?: string;
+
+  /**
+   * A readiness probe for a persistent task. Tasks that depend on a persistent
+   * task with a probe start once the probe passes.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#ready
+   *
+   * @defaultValue `null`
+   */
+  ready?: ReadyProbe;
+
+  /**
+   * Write the task's stderr to stdout. When unset, the streams are only
+   * merged when using grouped logs on GitHub Actions.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#mergestreams
+   *
+   * @defaultValue `null`
+   */
+  mergeStreams?: boolean;
+
+  /**
+   * Replay the task's logs when it hits the cache. When unset, logs are
+   * replayed unless `--no-log-replay` is passed.
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#logreplay
+   *
+   * @defaultValue `null`
+   */
+  logReplay?: boolean;
+
+  /**
+   * How long the task may run before it is stopped and marked as failed,
+   * e.g. "90s" or "5m".
+   *
+   * Documentation: https://turbo.build/repo/docs/reference/configuration#timeout
+   *
+   * @defaultValue `null`
+   */
+  timeout?: string;
 }
 
+export type ReadyProbe =
+  | {
+      /** Ready once a connection can be opened to this port on localhost. */
+      port: number;
+    }
+  | {
+      /** Ready once a line of the task's output matches this regular expression. */
+      log: string;
+    };
+
 export interface RemoteCache {
   /**
    * Indicates if signature verification is enabled for requests to the remote cache. When
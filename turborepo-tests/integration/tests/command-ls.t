@@ -34,6 +34,41 @@ Run info with json output
     }
   }
 
+Run info with graph
+  $ ${TURBO} ls --graph
+   WARNING  ls command is experimental and may change in the future
+  my-app -> util
+  another
+
+Run info with graph and json output
+  $ ${TURBO} ls --graph --output=json
+   WARNING  ls command is experimental and may change in the future
+  {
+    "nodes": [
+      {
+        "name": "another",
+        "path": "packages(\/|\\\\)another", (re)
+        "internal": true
+      },
+      {
+        "name": "my-app",
+        "path": "apps(\/|\\\\)my-app", (re)
+        "internal": true
+      },
+      {
+        "name": "util",
+        "path": "packages(\/|\\\\)util", (re)
+        "internal": true
+      }
+    ],
+    "edges": [
+      {
+        "from": "my-app",
+        "to": "util"
+      }
+    ]
+  }
+
 Run info with filter
   $ ${TURBO} ls -F my-app...
    WARNING  ls command is experimental and may change in the future
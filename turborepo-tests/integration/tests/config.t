@@ -20,7 +20,8 @@ Run test run
     "envMode": "strict",
     "scmBase": null,
     "scmHead": null,
-    "cacheDir": ".turbo[\\/]+cache" (re)
+    "cacheDir": ".turbo[\\/]+cache", (re)
+    "cacheCompression": "zstd"
   }
 
 Run test run with api overloaded
@@ -35,6 +36,10 @@ Run test run with team overloaded from both env and flag (flag should take prece
   $ TURBO_TEAM=vercel ${TURBO} config --team turbo | jq .teamSlug
   "turbo"
 
+Run test run with cache compression env variable set
+  $ TURBO_CACHE_COMPRESSION=gzip ${TURBO} config | jq .cacheCompression
+  "gzip"
+
 Run test run with remote cache timeout env variable set
   $ TURBO_REMOTE_CACHE_TIMEOUT=123 ${TURBO} config | jq .timeout
   123
@@ -0,0 +1,13 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh
+
+# Each planned task is written out as its own line of JSON
+  $ ${TURBO} run build --dry=ndjson > tmpjson.log
+  $ cat tmpjson.log | wc -l
+  \s*2 (re)
+  $ cat tmpjson.log | jq -r .taskId | sort
+  my-app#build
+  util#build
+  $ cat tmpjson.log | jq -c .cache
+  {"local":false,"remote":false,"status":"MISS","timeSaved":0}
+  {"local":false,"remote":false,"status":"MISS","timeSaved":0}
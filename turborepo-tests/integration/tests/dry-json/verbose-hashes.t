@@ -0,0 +1,36 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh
+
+Tasks don't explain their hash by default
+  $ ${TURBO} run build --dry=json --filter=util | jq '.tasks | map(select(.taskId == "util#build")) | .[0] | has("hashInputs")'
+  false
+
+With --verbose-hashes each task includes the inputs to its hash
+  $ ${TURBO} run build --dry=json --verbose-hashes --filter=util > tmpjson.log
+  $ cat tmpjson.log | jq '.tasks | map(select(.taskId == "util#build")) | .[0].hashInputs | keys'
+  [
+    "env",
+    "envMode",
+    "externalDepsHash",
+    "globalHash",
+    "hashOfFiles",
+    "outputs",
+    "passThroughArgs",
+    "passThroughEnv",
+    "resolvedEnvVars",
+    "taskDependencyHashes"
+  ]
+  $ cat tmpjson.log | jq '.tasks | map(select(.taskId == "util#build")) | .[0].hashInputs | {env, envMode, taskDependencyHashes}'
+  {
+    "env": [
+      "NODE_ENV"
+    ],
+    "envMode": "strict",
+    "taskDependencyHashes": []
+  }
+
+The file list is still reported under inputs
+  $ cat tmpjson.log | jq '.tasks | map(select(.taskId == "util#build")) | .[0].inputs | keys'
+  [
+    "package.json"
+  ]
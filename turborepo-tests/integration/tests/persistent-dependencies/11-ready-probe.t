@@ -0,0 +1,18 @@
+# Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh persistent_dependencies/11-ready-probe
+
+// Workspace Graph
+// - web depends on server
+//
+// server#dev is persistent with a log readiness probe, so web#start can
+// depend on it. web#start records the server's status when it runs, which is
+// only "running" if it started before server#dev exited.
+  $ ${TURBO} run start --force > /dev/null
+  $ cat apps/web/server-status.txt
+  running
+
+The probe still sees the task's output when it isn't shown
+  $ rm apps/web/server-status.txt
+  $ ${TURBO} run start --force --output-logs=none > /dev/null
+  $ cat apps/web/server-status.txt
+  running
@@ -0,0 +1,22 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh monorepo_with_root_dep pnpm@7.25.1
+
+Both web and docs depend on shared, make sure it only gets added once
+  $ ${TURBO} prune web docs
+  Generating pruned monorepo for web, docs in .*(\/|\\)out (re)
+   - Added docs
+   - Added shared
+   - Added util
+   - Added web
+  $ ls out/apps out/packages
+  out/apps:
+  docs
+  web
+  
+  out/packages:
+  shared
+  util
+
+Shared should show up as a single importer in the pruned lockfile
+  $ grep -c "^  packages/shared:" out/pnpm-lock.yaml
+  1
@@ -0,0 +1,13 @@
+# Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh ordered
+
+# Every line written to stdout is a JSON event, the run summary goes to stderr.
+  $ ${TURBO} run build --log-order json --force > events.jsonl 2> summary.txt
+  $ jq -e . events.jsonl > /dev/null
+  $ jq -r 'select(.type != "taskOutput") | "\(.type) \(.task)"' events.jsonl | sort
+  taskFinished my-app#build
+  taskFinished util#build
+  taskStarted my-app#build
+  taskStarted util#build
+  $ grep "Tasks:" summary.txt
+   Tasks:    2 successful, 2 total
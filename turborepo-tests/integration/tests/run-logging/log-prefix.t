@@ -61,7 +61,7 @@ Setup
 # Running with bogus option
   $ ${TURBO} run build --log-prefix=blah
    ERROR  invalid value 'blah' for '--log-prefix <LOG_PREFIX>'
-    [possible values: auto, none, task]
+    [possible values: auto, none, task, timestamp, task-timestamp]
   
   For more information, try '--help'.
   
@@ -70,7 +70,7 @@ Setup
 # Running with missing value for option
   $ ${TURBO} run build --log-prefix
    ERROR  a value is required for '--log-prefix <LOG_PREFIX>' but none was supplied
-    [possible values: auto, none, task]
+    [possible values: auto, none, task, timestamp, task-timestamp]
   
   For more information, try '--help'.
   
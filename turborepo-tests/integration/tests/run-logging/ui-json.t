@@ -0,0 +1,21 @@
+# Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh task_dependencies/topological
+
+# Task events are streamed to stdout even though it isn't a terminal. my-app#build depends on
+# util#build, so util#build finishes before my-app#build starts.
+  $ ${TURBO} run build --ui=json --filter=my-app --filter=util > events.jsonl 2> summary.txt
+  $ jq -e . events.jsonl > /dev/null
+  $ jq -c 'select(.type != "taskOutput" or .line == "building")' events.jsonl
+  {"type":"taskStarted","task":"util#build"}
+  {"type":"taskOutput","task":"util#build","stream":"stdout","line":"building"}
+  {"type":"taskFinished","task":"util#build","exitCode":0,"cacheStatus":"MISS"}
+  {"type":"taskStarted","task":"my-app#build"}
+  {"type":"taskOutput","task":"my-app#build","stream":"stdout","line":"building"}
+  {"type":"taskFinished","task":"my-app#build","exitCode":0,"cacheStatus":"MISS"}
+  $ grep "Tasks:" summary.txt
+   Tasks:    2 successful, 2 total
+
+# A second run restores both tasks from the cache
+  $ ${TURBO} run build --ui=json --filter=my-app --filter=util 2> /dev/null | jq -c 'select(.type == "taskFinished")'
+  {"type":"taskFinished","task":"util#build","exitCode":0,"cacheStatus":"HIT"}
+  {"type":"taskFinished","task":"my-app#build","exitCode":0,"cacheStatus":"HIT"}
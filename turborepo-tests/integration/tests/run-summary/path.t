@@ -0,0 +1,20 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh
+
+# Delete all run summaries to start
+  $ rm -rf .turbo/runs
+
+# Passing a path writes the summary there instead of .turbo/runs
+  $ ${TURBO} run build --summarize=out/summary.json > /dev/null
+   WARNING  no output files found for task my-app#build. Please check your `outputs` key in `turbo.json`
+  $ test -d .turbo/runs
+  [1]
+  $ cat out/summary.json | jq '.tasks | length'
+  2
+  $ cat out/summary.json | jq '.execution.exitCode'
+  0
+
+# The bare flag still writes to .turbo/runs
+  $ ${TURBO} run build --summarize > /dev/null
+  $ /bin/ls .turbo/runs/*.json | wc -l
+  \s*1 (re)
@@ -34,10 +34,32 @@ Graph to stdout
   \\t[A-Z]{4}\("my-app#build"\) --> [A-Z]{4}\("util#build"\).* (re)
   \\t[A-Z]{4}\("util#build"\) --> [A-Z]{4}\("___ROOT___"\).* (re)
 
+  $ ${TURBO} build -F my-app --graph=graph.mmd
+  
+  .*Generated task graph in .*graph\.mmd.* (re)
+
+  $ cat graph.mmd
+  graph TD
+  \\t[A-Z]{4}\("my-app#build"\) --> [A-Z]{4}\("util#build"\).* (re)
+  \\t[A-Z]{4}\("util#build"\) --> [A-Z]{4}\("___ROOT___"\).* (re)
+
+  $ ${TURBO} build -F my-app --graph=mermaid
+  graph TD
+  \\t[A-Z]{4}\("my-app#build"\) --> [A-Z]{4}\("util#build"\).* (re)
+  \\t[A-Z]{4}\("util#build"\) --> [A-Z]{4}\("___ROOT___"\).* (re)
+
   $ ${TURBO} build -F my-app --graph=graph.mdx
-   ERROR  invalid value 'graph.mdx' for '--graph [<GRAPH>]': Invalid file extension: 'mdx'. Allowed extensions are: ["svg", "png", "jpg", "pdf", "json", "html", "mermaid", "dot"]
+   ERROR  invalid value 'graph.mdx' for '--graph [<GRAPH>]': Invalid file extension: 'mdx'. Allowed extensions are: ["svg", "png", "jpg", "pdf", "json", "html", "mermaid", "mmd", "dot"]
   
   For more information, try '--help'.
   
   [1]
 
+
+Graph as JSON
+  $ ${TURBO} build -F my-app --graph-json > graph.json
+  $ cat graph.json | jq -c '.tasks[] | {taskId, dependencies}'
+  {"taskId":"my-app#build","dependencies":["util#build"]}
+  {"taskId":"util#build","dependencies":[]}
+  $ cat graph.json | jq '.tasks[0].definition | keys | length > 0'
+  true
@@ -9,6 +9,12 @@ Run without --root-turbo-json should fail
   
   [1]
 
+Run with a --root-turbo-json that doesn't exist should fail
+  $ ${TURBO} build --filter=my-app --root-turbo-json=turbo.ci.json
+    x Could not find the root turbo.json at .*turbo\.ci\.json (re)
+  
+  [1]
+
 Run with --root-turbo-json should use specified config
   $ ${TURBO} build --filter=my-app --root-turbo-json=turborepo.json
   \xe2\x80\xa2 Packages in scope: my-app (esc)
@@ -0,0 +1,46 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh
+
+Nothing is cached yet, so the task is skipped instead of executed
+  $ ${TURBO} run build --output-logs=hash-only --filter=my-app --only-restore
+  \xe2\x80\xa2 Packages in scope: my-app (esc)
+  \xe2\x80\xa2 Running build in 1 packages (esc)
+  \xe2\x80\xa2 Remote caching disabled (esc)
+  my-app:build: cache miss, skipping 0555ce94ca234049
+  
+    Tasks:    0 successful, 1 total
+   Cached:    0 cached, 1 total
+  Missing:    1 missing, 1 total
+     Time:\s*[\.0-9]+m?s  (re)
+  
+
+  $ test -f apps/my-app/.turbo/turbo-build.log
+  [1]
+
+Populate the cache
+  $ ${TURBO} run build --output-logs=hash-only --filter=my-app > /dev/null 2>&1
+  $ rm -rf apps/my-app/.turbo
+
+Outputs are restored from the cache without running the task
+  $ ${TURBO} run build --output-logs=hash-only --filter=my-app --only-restore
+  \xe2\x80\xa2 Packages in scope: my-app (esc)
+  \xe2\x80\xa2 Running build in 1 packages (esc)
+  \xe2\x80\xa2 Remote caching disabled (esc)
+  my-app:build: cache hit, suppressing logs 0555ce94ca234049
+  
+   Tasks:    1 successful, 1 total
+  Cached:    1 cached, 1 total
+    Time:\s*[\.0-9]+m?s >>> FULL TURBO (re)
+  
+
+  $ test -f apps/my-app/.turbo/turbo-build.log
+
+--only-restore can't be combined with --force
+  $ ${TURBO} run build --only-restore --force
+   ERROR  the argument '--only-restore' cannot be used with '--force [<FORCE>]'
+  
+  Usage: turbo(\.exe)? run --only-restore (re)
+  
+  For more information, try '--help'.
+  
+  [1]
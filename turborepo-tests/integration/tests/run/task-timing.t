@@ -0,0 +1,21 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh task_dependencies/topological
+
+Timing events are built from each task's execution summary, so check that both tasks report
+their timing, exit code and cache status
+  $ ${TURBO} run build --filter=my-app --filter=util --summarize=timing.json > /dev/null
+  $ cat timing.json | jq -c '.tasks[] | {taskId, exitCode: .execution.exitCode, cache: .cache.status}'
+  {"taskId":"my-app#build","exitCode":0,"cache":"MISS"}
+  {"taskId":"util#build","exitCode":0,"cache":"MISS"}
+  $ cat timing.json | jq '[.tasks[].execution | .endTime >= .startTime] | all'
+  true
+
+my-app#build depends on util#build, so it can't start until util#build has finished
+  $ cat timing.json | jq '(.tasks[] | select(.taskId == "util#build") | .execution.endTime) <= (.tasks[] | select(.taskId == "my-app#build") | .execution.startTime)'
+  true
+
+A second run restores both tasks from the cache
+  $ ${TURBO} run build --filter=my-app --filter=util --summarize=timing.json > /dev/null
+  $ cat timing.json | jq -c '.tasks[] | {taskId, exitCode: .execution.exitCode, cache: .cache.status}'
+  {"taskId":"my-app#build","exitCode":0,"cache":"HIT"}
+  {"taskId":"util#build","exitCode":0,"cache":"HIT"}
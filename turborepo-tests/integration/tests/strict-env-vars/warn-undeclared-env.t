@@ -0,0 +1,19 @@
+Setup
+  $ . ${TESTDIR}/../../../helpers/setup_integration_test.sh strict_env_vars
+
+OTHER_VAR is a platform env var that isn't declared in turbo.json
+  $ export OTHER_VAR=hiother
+  $ export TURBO_PLATFORM_ENV=OTHER_VAR
+
+Loose mode tasks get every env var so nothing is reported by default
+  $ ${TURBO} build --env-mode=loose --force 2>&1 | grep "OTHER_VAR"
+  [1]
+
+With --warn-undeclared-env the variable is reported since the task wouldn't get it in strict mode
+  $ ${TURBO} build --env-mode=loose --force --warn-undeclared-env 2>&1 | grep -A1 "^ my-app#build"
+   my-app#build
+  \s+- OTHER_VAR\s* (re)
+
+Nothing is reported when platform env validation is disabled
+  $ ${TURBO} build --env-mode=loose --force --warn-undeclared-env --no-platform-env-validation 2>&1 | grep "OTHER_VAR"
+  [1]
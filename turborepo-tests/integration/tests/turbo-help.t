@@ -40,7 +40,7 @@ Test help flag
         --heap <HEAP>
             Specify a file to save a pprof heap profile
         --ui <UI>
-            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web]
+            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web, json]
         --login <LOGIN>
             Override the login endpoint
         --no-color
@@ -68,11 +68,21 @@ Test help flag
         --cache-workers <CACHE_WORKERS>
             Set the number of concurrent cache operations (default 10) [default: 10]
         --dry-run [<DRY_RUN>]
-            [possible values: text, json]
+            [possible values: text, json, ndjson]
+        --verbose-hashes
+            Include a breakdown of the values that went into each task's hash in task summaries, such as --dry=json output. Useful for debugging cache misses
+        --why-hash <TASK>
+            Print the values that went into the hash of the given task, e.g. "web#build", and compare them with the last run summary that recorded them. Useful for debugging cache misses
         --graph [<GRAPH>]
-            Generate a graph of the task execution and output to a file when a filename is specified (.svg, .png, .jpg, .pdf, .json, .html, .mermaid, .dot). Outputs dot graph to stdout when if no filename is provided
+            Generate a graph of the task execution and output to a file when a filename is specified (.svg, .png, .jpg, .pdf, .json, .html, .mermaid, .mmd, .dot). Outputs dot graph to stdout when if no filename is provided, or a Mermaid graph when "mermaid" is provided
+        --graph-json
+            Print the resolved task graph, including each task's definition, as JSON to stdout without running any tasks
         --no-cache
             Avoid saving task results to the cache. Useful for development/watch tasks
+        --only-restore
+            Restore the outputs of cached tasks without executing tasks that miss the cache. Useful for warming up the local cache from the remote cache ahead of a build
+        --no-log-replay
+            Don't replay the logs of tasks that hit the cache. The cache hit status line is still shown
         --daemon
             Force turbo to use the local daemon. If unset turbo will use the default detection logic
         --no-daemon
@@ -81,18 +91,34 @@ Test help flag
             File to write turbo's performance profile output into. You can load the file up in chrome://tracing to see which parts of your build were slow
         --anon-profile <ANON_PROFILE>
             File to write turbo's performance profile output into. All identifying data omitted from the profile
+        --profile-format <PROFILE_FORMAT>
+            Format of the --profile and --anon-profile output [default: chrome] [possible values: chrome, perfetto]
+        --task-trace <TASK_TRACE>
+            File to write a Chrome trace of task start and end times into. Unlike --profile this only includes tasks, not turbo's internals
         --remote-cache-read-only [<REMOTE_CACHE_READ_ONLY>]
             Treat remote cache as read only [possible values: true, false]
-        --summarize [<SUMMARIZE>]
-            Generate a summary of the turbo run [possible values: true, false]
+        --summarize[=<SUMMARIZE>]
+            Generate a summary of the turbo run, optionally written to the given path relative to the repository root
+        --env-file <ENV_FILE>
+            Load environment variables from a `.env` style file before running tasks. Can be passed multiple times, later files take precedence
+        --only-changed[=<REF>]
+            Only run tasks in packages that changed since the given git ref. Equivalent to `--filter "[<ref>]"` and can be combined with other filters. Defaults to the merge base with the default branch. Requires a git repository
         --parallel
             Execute all tasks in parallel
+        --kill-timeout <SECONDS>
+            Number of seconds to give running tasks to exit after being interrupted when turbo stops early, before they are killed [default: 5]
+        --warn-undeclared-env
+            Warn about platform environment variables that tasks in loose env mode use without declaring them in `env` or `passThroughEnv`. These are the variables that would be missing in strict mode. Has no effect when platform environment variable validation is disabled
+        --no-platform-env-validation
+            Don't check tasks for platform environment variables that are missing from their environment
         --cache-dir <CACHE_DIR>
             Override the filesystem cache directory
+        --cache-compression <CACHE_COMPRESSION>
+            Set the codec used to compress local cache artifacts. Remote cache artifacts are always compressed with zstd. Existing artifacts are read regardless of the codec they were written with [possible values: zstd, gzip, none]
         --concurrency <CONCURRENCY>
             Limit the concurrency of task execution. Use 1 for serial (i.e. one-at-a-time) execution
-        --continue
-            Continue execution even if a task exits with an error or non-zero exit code. The default behavior is to bail
+        --continue[=<CONTINUE>]
+            Continue execution even if a task exits with an error or non-zero exit code. The default behavior is to bail. Use "dependencies-successful" to only skip the tasks that depend on a failed task [default: never] [possible values: never, always, dependencies-successful]
         --single-package
             Run turbo in single-package mode
         --force [<FORCE>]
@@ -108,15 +134,15 @@ Test help flag
         --affected
             Run only tasks that are affected by changes between the current branch and `main`
         --output-logs <OUTPUT_LOGS>
-            Set type of process output logging. Use "full" to show all output. Use "hash-only" to show only turbo-computed task hashes. Use "new-only" to show only new output with only hashes for cached tasks. Use "none" to hide process output. (default full) [possible values: full, none, hash-only, new-only, errors-only]
+            Set type of process output logging. Use "full" to show all output. Use "hash-only" to show only turbo-computed task hashes. Use "new-only" to show only new output with only hashes for cached tasks. Use "none" to hide process output. (default full) [possible values: full, none, hash-only, new-only, errors-only, errors-only-with-summary]
         --log-order <LOG_ORDER>
-            Set type of task output order. Use "stream" to show output as soon as it is available. Use "grouped" to show output when a command has finished execution. Use "auto" to let turbo decide based on its own heuristics. (default auto) [possible values: auto, stream, grouped]
-        --only
-            Only executes the tasks specified, does not execute parent tasks
+            Set type of task output order. Use "stream" to show output as soon as it is available. Use "grouped" to show output when a command has finished execution. Use "json" to emit task events as JSON lines. Use "auto" to let turbo decide based on its own heuristics. (default auto) [possible values: auto, stream, grouped, json]
+        --only[=<ONLY>]
+            Only executes the tasks specified, does not execute parent tasks. Use "direct-deps" to also execute the direct dependencies of the specified tasks, but not their transitive dependencies [possible values: tasks, direct-deps]
         --remote-only [<REMOTE_ONLY>]
             Ignore the local filesystem cache for all tasks. Only allow reading and caching artifacts using the remote cache [possible values: true, false]
         --log-prefix <LOG_PREFIX>
-            Use "none" to remove prefixes from task logs. Use "task" to get task id prefixing. Use "auto" to let turbo decide how to prefix the logs based on the execution environment. In most cases this will be the same as "task". Note that tasks running in parallel interleave their logs, so removing prefixes can make it difficult to associate logs with tasks. Use --log-order=grouped to prevent interleaving. (default auto) [default: auto] [possible values: auto, none, task]
+            Use "none" to remove prefixes from task logs. Use "task" to get task id prefixing. Use "timestamp" to prefix each line with an ISO-8601 timestamp, or "task-timestamp" for both. Use "auto" to let turbo decide how to prefix the logs based on the execution environment. In most cases this will be the same as "task". Note that tasks running in parallel interleave their logs, so removing prefixes can make it difficult to associate logs with tasks. Use --log-order=grouped to prevent interleaving. (default auto) [default: auto] [possible values: auto, none, task, timestamp, task-timestamp]
 
 
 
@@ -174,6 +200,7 @@ Test help flag
             - tui:    Use the terminal user interface
             - stream: Use the standard output stream
             - web:    Use the web user interface (experimental)
+            - json:   Write task events as JSON lines to stdout, the same as `--log-order=json`
   
         --login <LOGIN>
             Override the login endpoint
@@ -217,14 +244,29 @@ Test help flag
             [default: 10]
   
         --dry-run [<DRY_RUN>]
-            [possible values: text, json]
+            [possible values: text, json, ndjson]
+  
+        --verbose-hashes
+            Include a breakdown of the values that went into each task's hash in task summaries, such as --dry=json output. Useful for debugging cache misses
+  
+        --why-hash <TASK>
+            Print the values that went into the hash of the given task, e.g. "web#build", and compare them with the last run summary that recorded them. Useful for debugging cache misses
   
         --graph [<GRAPH>]
-            Generate a graph of the task execution and output to a file when a filename is specified (.svg, .png, .jpg, .pdf, .json, .html, .mermaid, .dot). Outputs dot graph to stdout when if no filename is provided
+            Generate a graph of the task execution and output to a file when a filename is specified (.svg, .png, .jpg, .pdf, .json, .html, .mermaid, .mmd, .dot). Outputs dot graph to stdout when if no filename is provided, or a Mermaid graph when "mermaid" is provided
+  
+        --graph-json
+            Print the resolved task graph, including each task's definition, as JSON to stdout without running any tasks
   
         --no-cache
             Avoid saving task results to the cache. Useful for development/watch tasks
   
+        --only-restore
+            Restore the outputs of cached tasks without executing tasks that miss the cache. Useful for warming up the local cache from the remote cache ahead of a build
+  
+        --no-log-replay
+            Don't replay the logs of tasks that hit the cache. The cache hit status line is still shown
+  
         --daemon
             Force turbo to use the local daemon. If unset turbo will use the default detection logic
   
@@ -237,27 +279,66 @@ Test help flag
         --anon-profile <ANON_PROFILE>
             File to write turbo's performance profile output into. All identifying data omitted from the profile
   
+        --profile-format <PROFILE_FORMAT>
+            Format of the --profile and --anon-profile output
+            
+            [default: chrome]
+            
+            Possible values:
+            - chrome:   JSON that can be loaded in chrome://tracing
+            - perfetto: Protobuf that can be loaded in https://ui.perfetto.dev
+  
+        --task-trace <TASK_TRACE>
+            File to write a Chrome trace of task start and end times into. Unlike --profile this only includes tasks, not turbo's internals
+  
         --remote-cache-read-only [<REMOTE_CACHE_READ_ONLY>]
             Treat remote cache as read only
             
             [possible values: true, false]
   
-        --summarize [<SUMMARIZE>]
-            Generate a summary of the turbo run
-            
-            [possible values: true, false]
+        --summarize[=<SUMMARIZE>]
+            Generate a summary of the turbo run, optionally written to the given path relative to the repository root
+  
+        --env-file <ENV_FILE>
+            Load environment variables from a `.env` style file before running tasks. Can be passed multiple times, later files take precedence
+  
+        --only-changed[=<REF>]
+            Only run tasks in packages that changed since the given git ref. Equivalent to `--filter "[<ref>]"` and can be combined with other filters. Defaults to the merge base with the default branch. Requires a git repository
   
         --parallel
             Execute all tasks in parallel
   
+        --kill-timeout <SECONDS>
+            Number of seconds to give running tasks to exit after being interrupted when turbo stops early, before they are killed
+            
+            [default: 5]
+  
+        --warn-undeclared-env
+            Warn about platform environment variables that tasks in loose env mode use without declaring them in `env` or `passThroughEnv`. These are the variables that would be missing in strict mode. Has no effect when platform environment variable validation is disabled
+  
+        --no-platform-env-validation
+            Don't check tasks for platform environment variables that are missing from their environment
+  
         --cache-dir <CACHE_DIR>
             Override the filesystem cache directory
   
+        --cache-compression <CACHE_COMPRESSION>
+            Set the codec used to compress local cache artifacts. Remote cache artifacts are always compressed with zstd. Existing artifacts are read regardless of the codec they were written with
+            
+            [possible values: zstd, gzip, none]
+  
         --concurrency <CONCURRENCY>
             Limit the concurrency of task execution. Use 1 for serial (i.e. one-at-a-time) execution
   
-        --continue
-            Continue execution even if a task exits with an error or non-zero exit code. The default behavior is to bail
+        --continue[=<CONTINUE>]
+            Continue execution even if a task exits with an error or non-zero exit code. The default behavior is to bail. Use "dependencies-successful" to only skip the tasks that depend on a failed task
+            
+            [default: never]
+            
+            Possible values:
+            - never:                   Stop the run as soon as a task fails
+            - always:                  Keep running all remaining tasks after a failure
+            - dependencies-successful: Keep running tasks whose dependencies all succeeded
   
         --single-package
             Run turbo in single-package mode
@@ -290,15 +371,19 @@ Test help flag
         --output-logs <OUTPUT_LOGS>
             Set type of process output logging. Use "full" to show all output. Use "hash-only" to show only turbo-computed task hashes. Use "new-only" to show only new output with only hashes for cached tasks. Use "none" to hide process output. (default full)
             
-            [possible values: full, none, hash-only, new-only, errors-only]
+            [possible values: full, none, hash-only, new-only, errors-only, errors-only-with-summary]
   
         --log-order <LOG_ORDER>
-            Set type of task output order. Use "stream" to show output as soon as it is available. Use "grouped" to show output when a command has finished execution. Use "auto" to let turbo decide based on its own heuristics. (default auto)
+            Set type of task output order. Use "stream" to show output as soon as it is available. Use "grouped" to show output when a command has finished execution. Use "json" to emit task events as JSON lines. Use "auto" to let turbo decide based on its own heuristics. (default auto)
             
-            [possible values: auto, stream, grouped]
+            [possible values: auto, stream, grouped, json]
   
-        --only
-            Only executes the tasks specified, does not execute parent tasks
+        --only[=<ONLY>]
+            Only executes the tasks specified, does not execute parent tasks. Use "direct-deps" to also execute the direct dependencies of the specified tasks, but not their transitive dependencies
+            
+            Possible values:
+            - tasks:       Only run the specified tasks
+            - direct-deps: Run the specified tasks and their direct dependencies
   
         --remote-only [<REMOTE_ONLY>]
             Ignore the local filesystem cache for all tasks. Only allow reading and caching artifacts using the remote cache
@@ -306,10 +391,10 @@ Test help flag
             [possible values: true, false]
   
         --log-prefix <LOG_PREFIX>
-            Use "none" to remove prefixes from task logs. Use "task" to get task id prefixing. Use "auto" to let turbo decide how to prefix the logs based on the execution environment. In most cases this will be the same as "task". Note that tasks running in parallel interleave their logs, so removing prefixes can make it difficult to associate logs with tasks. Use --log-order=grouped to prevent interleaving. (default auto)
+            Use "none" to remove prefixes from task logs. Use "task" to get task id prefixing. Use "timestamp" to prefix each line with an ISO-8601 timestamp, or "task-timestamp" for both. Use "auto" to let turbo decide how to prefix the logs based on the execution environment. In most cases this will be the same as "task". Note that tasks running in parallel interleave their logs, so removing prefixes can make it difficult to associate logs with tasks. Use --log-order=grouped to prevent interleaving. (default auto)
             
             [default: auto]
-            [possible values: auto, none, task]
+            [possible values: auto, none, task, timestamp, task-timestamp]
 
 Test help flag for link command
   $ ${TURBO} link -h
@@ -337,7 +422,7 @@ Test help flag for link command
         --heap <HEAP>
             Specify a file to save a pprof heap profile
         --ui <UI>
-            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web]
+            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web, json]
         --login <LOGIN>
             Override the login endpoint
         --no-color
@@ -385,7 +470,7 @@ Test help flag for unlink command
         --heap <HEAP>
             Specify a file to save a pprof heap profile
         --ui <UI>
-            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web]
+            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web, json]
         --login <LOGIN>
             Override the login endpoint
         --no-color
@@ -435,7 +520,7 @@ Test help flag for login command
         --heap <HEAP>
             Specify a file to save a pprof heap profile
         --ui <UI>
-            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web]
+            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web, json]
         --login <LOGIN>
             Override the login endpoint
         --no-color
@@ -483,7 +568,7 @@ Test help flag for logout command
         --heap <HEAP>
             Specify a file to save a pprof heap profile
         --ui <UI>
-            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web]
+            Specify whether to use the streaming UI or TUI [possible values: tui, stream, web, json]
         --login <LOGIN>
             Override the login endpoint
         --no-color